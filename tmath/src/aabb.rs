@@ -0,0 +1,342 @@
+use num_traits::real::Real;
+
+use crate::{
+    matrix::{Matrix, Matrix3, Matrix4},
+    vector::{Vector, Vector2, Vector3},
+};
+
+pub type Aabb2<T> = Aabb<2, T>;
+pub type Aabb3<T> = Aabb<3, T>;
+
+macro_rules! aabb_types {
+    ($($n:literal),*) => {
+        paste::paste! {
+            $(
+                pub type [< Aabb $n F >] = [< Aabb $n >]<f32>;
+                pub type [< Aabb $n D >] = [< Aabb $n >]<f64>;
+                pub type [< Aabb $n I >] = [< Aabb $n >]<i32>;
+            )*
+        }
+    };
+}
+
+aabb_types!(2, 3);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Aabb<const N: usize, T> {
+    pub min: Vector<N, T>,
+    pub max: Vector<N, T>,
+}
+
+impl<const N: usize, T> Aabb<N, T> {
+    pub const fn new(min: Vector<N, T>, max: Vector<N, T>) -> Self {
+        Self { min, max }
+    }
+
+    pub fn from_center_half_extents(center: Vector<N, T>, half_extents: Vector<N, T>) -> Self
+    where
+        T: Real,
+    {
+        Self::new(center - half_extents, center + half_extents)
+    }
+
+    pub fn from_points<I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = Vector<N, T>>,
+        T: Copy + PartialOrd,
+    {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+
+        Some(points.fold(Self::new(first, first), |aabb, point| {
+            aabb.include_point(&point)
+        }))
+    }
+
+    // Max isn't below min on any axis
+    pub fn is_valid(&self) -> bool
+    where
+        T: PartialOrd,
+    {
+        self.min
+            .0
+            .iter()
+            .zip(self.max.0.iter())
+            .all(|(min, max)| min <= max)
+    }
+
+    pub fn include_point(&self, point: &Vector<N, T>) -> Self
+    where
+        T: Copy + PartialOrd,
+    {
+        Self::new(self.min.min(point), self.max.max(point))
+    }
+
+    pub fn union(&self, other: &Self) -> Self
+    where
+        T: Copy + PartialOrd,
+    {
+        Self::new(self.min.min(&other.min), self.max.max(&other.max))
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self>
+    where
+        T: Copy + PartialOrd,
+    {
+        let res = Self::new(self.min.max(&other.min), self.max.min(&other.max));
+
+        res.is_valid().then_some(res)
+    }
+
+    pub fn contains_point(&self, point: &Vector<N, T>) -> bool
+    where
+        T: PartialOrd,
+    {
+        (0..N).all(|i| self.min[i] <= point[i] && point[i] <= self.max[i])
+    }
+
+    pub fn contains(&self, other: &Self) -> bool
+    where
+        T: PartialOrd,
+    {
+        self.contains_point(&other.min) && self.contains_point(&other.max)
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool
+    where
+        T: PartialOrd,
+    {
+        (0..N).all(|i| self.min[i] <= other.max[i] && other.min[i] <= self.max[i])
+    }
+
+    pub fn center(&self) -> Vector<N, T>
+    where
+        T: Real,
+    {
+        (self.min + self.max) / (T::one() + T::one())
+    }
+
+    pub fn extents(&self) -> Vector<N, T>
+    where
+        T: Copy + std::ops::Sub<Output = T>,
+    {
+        self.max - self.min
+    }
+
+    pub fn half_extents(&self) -> Vector<N, T>
+    where
+        T: Real,
+    {
+        self.extents() / (T::one() + T::one())
+    }
+
+    // Measure of the boundary: perimeter in 2D, surface area in 3D
+    pub fn surface_area(&self) -> T
+    where
+        T: Real,
+    {
+        let extents = self.extents();
+
+        let sum = (0..N).fold(T::zero(), |sum, skip| {
+            sum + (0..N)
+                .filter(|&i| i != skip)
+                .fold(T::one(), |product, i| product * extents[i])
+        });
+
+        sum * (T::one() + T::one())
+    }
+
+    // Area in 2D, volume in 3D
+    pub fn volume(&self) -> T
+    where
+        T: Real,
+    {
+        self.extents()
+            .0
+            .iter()
+            .fold(T::one(), |product, &val| product * val)
+    }
+
+    pub fn longest_axis(&self) -> usize
+    where
+        T: Real,
+    {
+        let extents = self.extents();
+
+        (1..N).fold(
+            0,
+            |best, i| {
+                if extents[i] > extents[best] {
+                    i
+                } else {
+                    best
+                }
+            },
+        )
+    }
+
+    pub fn closest_point(&self, point: &Vector<N, T>) -> Vector<N, T>
+    where
+        T: Copy + PartialOrd,
+    {
+        point.max(&self.min).min(&self.max)
+    }
+
+    pub fn distance_squared(&self, point: &Vector<N, T>) -> T
+    where
+        T: Real,
+    {
+        (self.closest_point(point) - point).length_squared()
+    }
+
+    // Grows the box by margin on every side, negative margins shrink it
+    pub fn expand(&self, margin: T) -> Self
+    where
+        T: Real,
+    {
+        let margin = Vector::new_val(margin);
+
+        Self::new(self.min - margin, self.max + margin)
+    }
+
+    // Conservative box around the transformed one (Arvo's method)
+    pub fn transform(&self, linear: &Matrix<N, N, T>, translation: &Vector<N, T>) -> Self
+    where
+        T: Default + Real,
+    {
+        let center = linear * self.center() + translation;
+        let half_extents = self.half_extents();
+        let abs = Matrix(linear.0.map(|row| Vector::new(row.0.map(|val| val.abs()))));
+
+        Self::from_center_half_extents(center, abs * half_extents)
+    }
+}
+
+impl<T> Aabb2<T> {
+    // Affine transform by a homogeneous matrix, the bottom row is ignored
+    pub fn transform_affine(&self, m: &Matrix3<T>) -> Self
+    where
+        T: Default + Real,
+    {
+        let linear = Matrix::new([[m[0][0], m[0][1]], [m[1][0], m[1][1]]]);
+        let translation = Vector2::new([m[0][2], m[1][2]]);
+
+        self.transform(&linear, &translation)
+    }
+}
+
+impl<T> Aabb3<T> {
+    // Affine transform by a homogeneous matrix, the bottom row is ignored
+    pub fn transform_affine(&self, m: &Matrix4<T>) -> Self
+    where
+        T: Default + Real,
+    {
+        let linear = Matrix::from_rows([m[0], m[1], m[2]].map(Vector3::from));
+        let translation = Vector3::new([m[0][3], m[1][3], m[2][3]]);
+
+        self.transform(&linear, &translation)
+    }
+
+    pub fn corners(&self) -> [Vector3<T>; 8]
+    where
+        T: Copy,
+    {
+        std::array::from_fn(|i| {
+            Vector3::new(std::array::from_fn(|axis| {
+                if i & (1 << axis) == 0 {
+                    self.min[axis]
+                } else {
+                    self.max[axis]
+                }
+            }))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{matrix, vector};
+
+    #[test]
+    fn from_points() {
+        let aabb =
+            Aabb::from_points([vector!(1, 5, -2), vector!(-3, 2, 4), vector!(0, 7, 0)]).unwrap();
+
+        assert_eq!(aabb, Aabb3I::new(vector!(-3, 2, -2), vector!(1, 7, 4)));
+        assert!(Aabb3I::from_points([]).is_none());
+    }
+
+    #[test]
+    fn union_intersection() {
+        let a = Aabb2I::new(vector!(0, 0), vector!(4, 4));
+        let b = Aabb2I::new(vector!(2, -1), vector!(6, 3));
+        let c = Aabb2I::new(vector!(5, 5), vector!(6, 6));
+
+        assert_eq!(a.union(&b), Aabb::new(vector!(0, -1), vector!(6, 4)));
+        assert_eq!(
+            a.intersection(&b),
+            Some(Aabb::new(vector!(2, 0), vector!(4, 3)))
+        );
+        assert_eq!(a.intersection(&c), None);
+
+        assert!(a.intersects(&b));
+        assert!(!a.intersects(&c));
+        assert!(a.union(&c).contains(&a));
+        assert!(!a.union(&c).contains(&b));
+    }
+
+    #[test]
+    fn queries() {
+        let aabb = Aabb3D::new(vector!(-1.0, 0.0, 2.0), vector!(1.0, 4.0, 3.0));
+
+        assert!(aabb.contains_point(&vector!(0.0, 4.0, 2.5)));
+        assert!(!aabb.contains_point(&vector!(0.0, 4.1, 2.5)));
+
+        assert_eq!(aabb.center(), vector!(0.0, 2.0, 2.5));
+        assert_eq!(aabb.extents(), vector!(2.0, 4.0, 1.0));
+        assert_eq!(aabb.longest_axis(), 1);
+        assert_eq!(aabb.surface_area(), 2.0 * (8.0 + 2.0 + 4.0));
+        assert_eq!(aabb.volume(), 8.0);
+
+        assert_eq!(
+            aabb.closest_point(&vector!(5.0, 1.0, -3.0)),
+            vector!(1.0, 1.0, 2.0)
+        );
+        assert_eq!(aabb.distance_squared(&vector!(5.0, 1.0, 2.5)), 16.0);
+        assert_eq!(
+            aabb.expand(1.0),
+            Aabb::new(vector!(-2.0, -1.0, 1.0), vector!(2.0, 5.0, 4.0))
+        );
+
+        let rect = Aabb2D::new(vector!(0.0, 0.0), vector!(2.0, 3.0));
+        assert_eq!(rect.surface_area(), 10.0);
+        assert_eq!(rect.volume(), 6.0);
+    }
+
+    #[test]
+    fn transform() {
+        let aabb = Aabb3D::new(vector!(-1.0, -1.0, -1.0), vector!(1.0, 1.0, 1.0));
+
+        // 45° around z then moved by (10, 0, 0)
+        let (s, c) = std::f64::consts::FRAC_PI_4.sin_cos();
+        let m = matrix!(
+            [c, -s, 0.0, 10.0],
+            [s, c, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0]
+        );
+        let transformed = aabb.transform_affine(&m);
+        let sqrt2 = std::f64::consts::SQRT_2;
+
+        assert!((transformed.min - vector!(10.0 - sqrt2, -sqrt2, -1.0)).length() < 1e-9);
+        assert!((transformed.max - vector!(10.0 + sqrt2, sqrt2, 1.0)).length() < 1e-9);
+
+        // Every transformed corner stays inside
+        for corner in aabb.corners() {
+            let p = Vector3::from(m * crate::vector::Vector4::from((corner, 1.0)));
+            assert!(transformed.expand(1e-9).contains_point(&p));
+        }
+    }
+}
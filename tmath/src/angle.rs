@@ -0,0 +1,409 @@
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+
+use num_traits::real::Real;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, PartialOrd)]
+pub struct Rad<T>(pub T);
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, PartialOrd)]
+pub struct Deg<T>(pub T);
+
+pub type RadF = Rad<f32>;
+pub type RadD = Rad<f64>;
+pub type DegF = Deg<f32>;
+pub type DegD = Deg<f64>;
+
+impl<T> Rad<T> {
+    pub fn full_turn() -> Self
+    where
+        T: Real,
+    {
+        Self(T::from(std::f64::consts::TAU).unwrap())
+    }
+
+    pub fn to_degrees(self) -> Deg<T>
+    where
+        T: Real,
+    {
+        Deg(self.0.to_degrees())
+    }
+
+    pub fn sin(self) -> T
+    where
+        T: Real,
+    {
+        self.0.sin()
+    }
+
+    pub fn cos(self) -> T
+    where
+        T: Real,
+    {
+        self.0.cos()
+    }
+
+    pub fn tan(self) -> T
+    where
+        T: Real,
+    {
+        self.0.tan()
+    }
+
+    pub fn sin_cos(self) -> (T, T)
+    where
+        T: Real,
+    {
+        self.0.sin_cos()
+    }
+
+    pub fn asin(val: T) -> Self
+    where
+        T: Real,
+    {
+        Self(val.asin())
+    }
+
+    pub fn acos(val: T) -> Self
+    where
+        T: Real,
+    {
+        Self(val.acos())
+    }
+
+    pub fn atan(val: T) -> Self
+    where
+        T: Real,
+    {
+        Self(val.atan())
+    }
+
+    pub fn atan2(y: T, x: T) -> Self
+    where
+        T: Real,
+    {
+        Self(y.atan2(x))
+    }
+}
+
+impl<T> Deg<T> {
+    pub fn full_turn() -> Self
+    where
+        T: Real,
+    {
+        Self(T::from(360.0).unwrap())
+    }
+
+    pub fn to_radians(self) -> Rad<T>
+    where
+        T: Real,
+    {
+        Rad(self.0.to_radians())
+    }
+
+    pub fn sin(self) -> T
+    where
+        T: Real,
+    {
+        self.to_radians().sin()
+    }
+
+    pub fn cos(self) -> T
+    where
+        T: Real,
+    {
+        self.to_radians().cos()
+    }
+
+    pub fn tan(self) -> T
+    where
+        T: Real,
+    {
+        self.to_radians().tan()
+    }
+
+    pub fn sin_cos(self) -> (T, T)
+    where
+        T: Real,
+    {
+        self.to_radians().sin_cos()
+    }
+}
+
+impl<T> From<Deg<T>> for Rad<T>
+where
+    T: Real,
+{
+    fn from(deg: Deg<T>) -> Self {
+        deg.to_radians()
+    }
+}
+
+impl<T> From<Rad<T>> for Deg<T>
+where
+    T: Real,
+{
+    fn from(rad: Rad<T>) -> Self {
+        rad.to_degrees()
+    }
+}
+
+macro_rules! impl_angle {
+    ($($angle:ident),*) => {
+        $(
+            impl<T> $angle<T> {
+                pub const fn new(val: T) -> Self {
+                    Self(val)
+                }
+
+                pub fn zero() -> Self
+                where
+                    T: Real,
+                {
+                    Self(T::zero())
+                }
+
+                pub fn half_turn() -> Self
+                where
+                    T: Real,
+                {
+                    Self::full_turn() / (T::one() + T::one())
+                }
+
+                pub fn quarter_turn() -> Self
+                where
+                    T: Real,
+                {
+                    Self::half_turn() / (T::one() + T::one())
+                }
+
+                // 0 <= angle < full turn
+                pub fn wrap_unsigned(self) -> Self
+                where
+                    T: Real,
+                {
+                    let full = Self::full_turn().0;
+                    let mut res = self.0 % full;
+
+                    if res < T::zero() {
+                        res = res + full;
+                    }
+
+                    // Adding a full turn to a tiny negative remainder can round up to it
+                    if res >= full {
+                        res = T::zero();
+                    }
+
+                    Self(res)
+                }
+
+                // -half turn <= angle < half turn
+                pub fn wrap_signed(self) -> Self
+                where
+                    T: Real,
+                {
+                    let half = Self::half_turn();
+
+                    (self + half).wrap_unsigned() - half
+                }
+
+                // Signed angle to rotate from self to other along the shorter way around
+                pub fn shortest_difference(self, other: Self) -> Self
+                where
+                    T: Real,
+                {
+                    (other - self).wrap_signed()
+                }
+
+                pub fn lerp(self, other: Self, t: T) -> Self
+                where
+                    T: Real,
+                {
+                    self + self.shortest_difference(other) * t
+                }
+
+                pub fn abs(self) -> Self
+                where
+                    T: Real,
+                {
+                    Self(self.0.abs())
+                }
+            }
+
+            impl<T> Add for $angle<T>
+            where
+                T: Add<Output = T>,
+            {
+                type Output = Self;
+
+                fn add(self, rhs: Self) -> Self::Output {
+                    Self(self.0 + rhs.0)
+                }
+            }
+
+            impl<T> Sub for $angle<T>
+            where
+                T: Sub<Output = T>,
+            {
+                type Output = Self;
+
+                fn sub(self, rhs: Self) -> Self::Output {
+                    Self(self.0 - rhs.0)
+                }
+            }
+
+            impl<T> Neg for $angle<T>
+            where
+                T: Neg<Output = T>,
+            {
+                type Output = Self;
+
+                fn neg(self) -> Self::Output {
+                    Self(-self.0)
+                }
+            }
+
+            impl<T> Mul<T> for $angle<T>
+            where
+                T: Mul<Output = T>,
+            {
+                type Output = Self;
+
+                fn mul(self, rhs: T) -> Self::Output {
+                    Self(self.0 * rhs)
+                }
+            }
+
+            impl<T> Div<T> for $angle<T>
+            where
+                T: Div<Output = T>,
+            {
+                type Output = Self;
+
+                fn div(self, rhs: T) -> Self::Output {
+                    Self(self.0 / rhs)
+                }
+            }
+
+            impl<T> Div for $angle<T>
+            where
+                T: Div<Output = T>,
+            {
+                type Output = T;
+
+                fn div(self, rhs: Self) -> Self::Output {
+                    self.0 / rhs.0
+                }
+            }
+
+            impl<T> Rem for $angle<T>
+            where
+                T: Rem<Output = T>,
+            {
+                type Output = Self;
+
+                fn rem(self, rhs: Self) -> Self::Output {
+                    Self(self.0 % rhs.0)
+                }
+            }
+
+            impl<T> AddAssign for $angle<T>
+            where
+                T: AddAssign,
+            {
+                fn add_assign(&mut self, rhs: Self) {
+                    self.0 += rhs.0;
+                }
+            }
+
+            impl<T> SubAssign for $angle<T>
+            where
+                T: SubAssign,
+            {
+                fn sub_assign(&mut self, rhs: Self) {
+                    self.0 -= rhs.0;
+                }
+            }
+
+            impl<T> MulAssign<T> for $angle<T>
+            where
+                T: MulAssign,
+            {
+                fn mul_assign(&mut self, rhs: T) {
+                    self.0 *= rhs;
+                }
+            }
+
+            impl<T> DivAssign<T> for $angle<T>
+            where
+                T: DivAssign,
+            {
+                fn div_assign(&mut self, rhs: T) {
+                    self.0 /= rhs;
+                }
+            }
+        )*
+    };
+}
+
+impl_angle!(Rad, Deg);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    fn assert_rad_close<A: Into<Rad<f64>>>(a: A, b: f64) {
+        let a = a.into().0;
+        assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn conversions() {
+        assert_rad_close(DegD::new(180.0), PI);
+        assert!((Deg::from(Rad(FRAC_PI_2)).0 - 90.0_f64).abs() < 1e-9);
+        assert!((DegD::new(30.0).sin() - 0.5).abs() < 1e-9);
+        assert_eq!(RadD::atan2(1.0, 0.0), Rad(FRAC_PI_2));
+    }
+
+    #[test]
+    fn arithmetic() {
+        let mut a = Rad(1.0);
+        a += Rad(0.5);
+        a *= 2.0;
+
+        assert_eq!(a, Rad(3.0));
+        assert_eq!(-a / 3.0, Rad(-1.0));
+        assert_eq!(a / Rad(1.5), 2.0);
+        assert_eq!(DegD::new(90.0) - DegD::new(45.0), Deg(45.0));
+    }
+
+    #[test]
+    fn wrapping() {
+        assert_eq!(DegD::new(370.0).wrap_unsigned(), Deg(10.0));
+        assert_eq!(DegD::new(-10.0).wrap_unsigned(), Deg(350.0));
+        assert_eq!(DegD::new(190.0).wrap_signed(), Deg(-170.0));
+        assert_eq!(DegD::new(-180.0).wrap_signed(), Deg(-180.0));
+        assert_eq!(DegD::new(720.0).wrap_signed(), Deg(0.0));
+
+        assert_rad_close(RadD::new(3.0 * PI).wrap_signed(), -PI);
+        assert_rad_close(RadD::new(-FRAC_PI_2).wrap_unsigned(), 3.0 * FRAC_PI_2);
+    }
+
+    #[test]
+    fn shortest_difference() {
+        assert_eq!(DegD::new(350.0).shortest_difference(Deg(10.0)), Deg(20.0));
+        assert_eq!(DegD::new(10.0).shortest_difference(Deg(350.0)), Deg(-20.0));
+        assert_eq!(
+            DegD::new(-170.0).shortest_difference(Deg(170.0)),
+            Deg(-20.0)
+        );
+    }
+
+    #[test]
+    fn lerp() {
+        assert_eq!(DegD::new(350.0).lerp(Deg(10.0), 0.5), Deg(360.0));
+        assert_eq!(DegD::new(0.0).lerp(Deg(90.0), 0.25), Deg(22.5));
+        assert_rad_close(RadD::new(-3.0).lerp(Rad(3.0), 0.5).abs(), PI);
+    }
+}
@@ -0,0 +1,443 @@
+use num_traits::real::Real;
+
+use crate::{aabb::Aabb, vector::Vector};
+
+pub type Bezier2<const D: usize, T> = Bezier<D, 2, T>;
+pub type Bezier3<const D: usize, T> = Bezier<D, 3, T>;
+
+pub type QuadraticBezier<const N: usize, T> = Bezier<3, N, T>;
+pub type CubicBezier<const N: usize, T> = Bezier<4, N, T>;
+
+macro_rules! bezier_types {
+    ($($name:ident),*) => {
+        paste::paste! {
+            $(
+                pub type [< $name 2 >]<T> = $name<2, T>;
+                pub type [< $name 3 >]<T> = $name<3, T>;
+                pub type [< $name 2F >] = [< $name 2 >]<f32>;
+                pub type [< $name 2D >] = [< $name 2 >]<f64>;
+                pub type [< $name 3F >] = [< $name 3 >]<f32>;
+                pub type [< $name 3D >] = [< $name 3 >]<f64>;
+            )*
+        }
+    };
+}
+
+bezier_types!(QuadraticBezier, CubicBezier);
+
+// Collapses the points into the first one by repeated lerping
+fn de_casteljau<const N: usize, T>(points: &mut [Vector<N, T>], t: T) -> Vector<N, T>
+where
+    T: Real,
+{
+    for len in (1..points.len()).rev() {
+        for i in 0..len {
+            points[i] = points[i] + (points[i + 1] - points[i]) * t;
+        }
+    }
+
+    points[0]
+}
+
+// Gauss-Legendre on [a, b] with 5 points, exact for polynomials up to degree 9
+fn gauss_legendre<T, F>(f: &F, a: T, b: T) -> T
+where
+    T: Real,
+    F: Fn(T) -> T,
+{
+    const NODES: [(f64, f64); 5] = [
+        (0.0, 0.568_888_888_888_888_9),
+        (-0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+        (0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+        (-0.906_179_845_938_664, 0.236_926_885_056_189_1),
+        (0.906_179_845_938_664, 0.236_926_885_056_189_1),
+    ];
+
+    let two = T::one() + T::one();
+    let (mid, half) = ((a + b) / two, (b - a) / two);
+
+    NODES.iter().fold(T::zero(), |sum, &(x, w)| {
+        sum + T::from(w).unwrap() * f(mid + half * T::from(x).unwrap())
+    }) * half
+}
+
+// Integral of f from a to b, halving the interval until both halves agree with the whole
+pub(crate) fn integrate<T, F>(f: &F, a: T, b: T) -> T
+where
+    T: Real,
+    F: Fn(T) -> T,
+{
+    fn refine<T: Real, F: Fn(T) -> T>(f: &F, a: T, b: T, whole: T, depth: usize) -> T {
+        let mid = (a + b) / (T::one() + T::one());
+        let (left, right) = (gauss_legendre(f, a, mid), gauss_legendre(f, mid, b));
+        let sum = left + right;
+
+        let tolerance = T::epsilon() * T::from(1024).unwrap() * sum.abs().max(T::one());
+        if depth == 0 || (sum - whole).abs() <= tolerance {
+            return sum;
+        }
+
+        refine(f, a, mid, left, depth - 1) + refine(f, mid, b, right, depth - 1)
+    }
+
+    refine(f, a, b, gauss_legendre(f, a, b), 16)
+}
+
+// Parameters where a polynomial in Bernstein form on lo..hi may change sign, found by splitting
+// until the coefficients, which bound the polynomial, all share a sign
+fn bernstein_roots<T>(coeffs: &[T], lo: T, hi: T, depth: usize, roots: &mut Vec<T>)
+where
+    T: Real,
+{
+    if coeffs.iter().all(|&c| c > T::zero())
+        || coeffs.iter().all(|&c| c < T::zero())
+        || coeffs.iter().all(|&c| c == T::zero())
+    {
+        return;
+    }
+
+    let two = T::one() + T::one();
+    let mid = (lo + hi) / two;
+    if depth == 0 {
+        roots.push(mid);
+        return;
+    }
+
+    let mut right = coeffs.to_vec();
+    let mut left = Vec::with_capacity(coeffs.len());
+    for len in (0..coeffs.len()).rev() {
+        left.push(right[0]);
+        for i in 0..len {
+            right[i] = (right[i] + right[i + 1]) / two;
+        }
+    }
+
+    bernstein_roots(&left, lo, mid, depth - 1, roots);
+    bernstein_roots(&right, mid, hi, depth - 1, roots);
+}
+
+// D control points, so the curve has a degree of D - 1
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Bezier<const D: usize, const N: usize, T> {
+    pub points: [Vector<N, T>; D],
+}
+
+impl<const D: usize, const N: usize, T> Bezier<D, N, T> {
+    pub const fn new(points: [Vector<N, T>; D]) -> Self {
+        Self { points }
+    }
+
+    pub const fn degree(&self) -> usize {
+        D.saturating_sub(1)
+    }
+
+    pub fn point(&self, t: T) -> Vector<N, T>
+    where
+        T: Real,
+    {
+        let mut points = self.points;
+
+        de_casteljau(&mut points, t)
+    }
+
+    // Differences of the points order times, so a curve of degree D - 1 - order, scaled by the
+    // falling factorial of the degree
+    fn nth_derivative(&self, t: T, order: usize) -> Vector<N, T>
+    where
+        T: Real,
+    {
+        if order >= D {
+            return Vector::new_val(T::zero());
+        }
+
+        let mut diffs = self.points;
+        let mut scale = T::one();
+        for len in (D - order..D).rev() {
+            for i in 0..len {
+                diffs[i] = diffs[i + 1] - diffs[i];
+            }
+            scale = scale * T::from(len).unwrap();
+        }
+
+        de_casteljau(&mut diffs[..D - order], t) * scale
+    }
+
+    // Tangent with the speed of the parametrization
+    pub fn derivative(&self, t: T) -> Vector<N, T>
+    where
+        T: Real,
+    {
+        self.nth_derivative(t, 1)
+    }
+
+    pub fn second_derivative(&self, t: T) -> Vector<N, T>
+    where
+        T: Real,
+    {
+        self.nth_derivative(t, 2)
+    }
+
+    pub fn third_derivative(&self, t: T) -> Vector<N, T>
+    where
+        T: Real,
+    {
+        self.nth_derivative(t, 3)
+    }
+
+    // The parts before and after t, each running over 0..1 again
+    pub fn split(&self, t: T) -> (Self, Self)
+    where
+        T: Real,
+    {
+        let mut left = self.points;
+        let mut right = self.points;
+
+        for len in (0..D).rev() {
+            left[D - 1 - len] = right[0];
+            for i in 0..len {
+                right[i] = right[i] + (right[i + 1] - right[i]) * t;
+            }
+        }
+
+        (Self::new(left), Self::new(right))
+    }
+
+    // Curve sitting in a range of 0..1 of this one
+    pub fn segment(&self, from: T, to: T) -> Self
+    where
+        T: Real,
+    {
+        let (_, tail) = self.split(from);
+        if from >= T::one() {
+            return tail;
+        }
+
+        tail.split((to - from) / (T::one() - from)).0
+    }
+
+    // Tight box around the curve, not just the control points
+    pub fn bounding_box(&self) -> Option<Aabb<N, T>>
+    where
+        T: Real,
+    {
+        let mut ts = vec![T::zero(), T::one()];
+
+        for axis in 0..N {
+            let coeffs: Vec<_> = self
+                .points
+                .windows(2)
+                .map(|w| w[1][axis] - w[0][axis])
+                .collect();
+
+            bernstein_roots(&coeffs, T::zero(), T::one(), 32, &mut ts);
+        }
+
+        Aabb::from_points(ts.into_iter().map(|t| self.point(t)))
+    }
+
+    // Parameter and position of the curve's point nearest to the query, from the best of a few
+    // samples polished with Newton's method
+    pub fn closest_point(&self, point: &Vector<N, T>) -> (T, Vector<N, T>)
+    where
+        T: Default + Real,
+    {
+        let samples = 8 * D.max(1);
+        let distance = |t: T| (self.point(t) - point).length_squared();
+
+        let mut best = (0..=samples)
+            .map(|i| T::from(i).unwrap() / T::from(samples).unwrap())
+            .map(|t| (t, distance(t)))
+            .fold((T::zero(), T::max_value()), |best, sample| {
+                if sample.1 < best.1 {
+                    sample
+                } else {
+                    best
+                }
+            });
+
+        for _ in 0..16 {
+            let t = best.0;
+            let (offset, d1, d2) = (
+                self.point(t) - point,
+                self.derivative(t),
+                self.second_derivative(t),
+            );
+
+            let slope = d1.dot(&d1) + offset.dot(&d2);
+            if slope <= T::zero() {
+                break;
+            }
+
+            let next = (t - offset.dot(&d1) / slope).max(T::zero()).min(T::one());
+            let next_distance = distance(next);
+            if next_distance >= best.1 {
+                break;
+            }
+
+            best = (next, next_distance);
+        }
+
+        (best.0, self.point(best.0))
+    }
+
+    pub fn arc_length(&self) -> T
+    where
+        T: Default + Real,
+    {
+        self.arc_length_to(T::one())
+    }
+
+    // Length of the curve from its start up to t
+    pub fn arc_length_to(&self, t: T) -> T
+    where
+        T: Default + Real,
+    {
+        integrate(&|t| self.derivative(t).length(), T::zero(), t)
+    }
+
+    // Same curve with one more control point. E has to be D + 1
+    pub fn elevate<const E: usize>(&self) -> Bezier<E, N, T>
+    where
+        T: Real,
+    {
+        assert_eq!(E, D + 1, "elevating adds exactly one control point");
+
+        let d = T::from(D).unwrap();
+
+        Bezier::new(std::array::from_fn(|i| {
+            if i == 0 {
+                self.points[0]
+            } else if i == D {
+                self.points[D - 1]
+            } else {
+                let a = T::from(i).unwrap() / d;
+                self.points[i - 1] * a + self.points[i] * (T::one() - a)
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{test_util::assert_close, vector};
+
+    fn cubic() -> CubicBezier2D {
+        Bezier::new([
+            vector!(0.0, 0.0),
+            vector!(1.0, 3.0),
+            vector!(4.0, -2.0),
+            vector!(5.0, 1.0),
+        ])
+    }
+
+    #[test]
+    fn evaluate() {
+        let curve = cubic();
+        assert_eq!(curve.degree(), 3);
+        assert_close(curve.point(0.0), vector!(0.0, 0.0), 1e-6);
+        assert_close(curve.point(1.0), vector!(5.0, 1.0), 1e-6);
+
+        // Power basis: B(t) = (3t + 6t² - 4t³, 9t - 24t² + 16t³)
+        for t in [0.0, 0.2, 0.5, 0.9] {
+            let (t2, t3) = (t * t, t * t * t);
+            assert_close(
+                curve.point(t),
+                vector!(
+                    3.0 * t + 6.0 * t2 - 4.0 * t3,
+                    9.0 * t - 24.0 * t2 + 16.0 * t3
+                ),
+                1e-6,
+            );
+            assert_close(
+                curve.derivative(t),
+                vector!(3.0 + 12.0 * t - 12.0 * t2, 9.0 - 48.0 * t + 48.0 * t2),
+                1e-6,
+            );
+            assert_close(
+                curve.second_derivative(t),
+                vector!(12.0 - 24.0 * t, -48.0 + 96.0 * t),
+                1e-6,
+            );
+            assert_close(curve.third_derivative(t), vector!(-24.0, 96.0), 1e-6);
+        }
+
+        let line = Bezier2::<2, f32>::new([vector!(0.0, 0.0), vector!(2.0, 4.0)]);
+        assert_eq!(line.point(0.25), vector!(0.5, 1.0));
+        assert_eq!(line.second_derivative(0.5), vector!(0.0, 0.0));
+    }
+
+    #[test]
+    fn split_and_elevate() {
+        let curve = cubic();
+        let (left, right) = curve.split(0.3);
+
+        for t in [0.0, 0.25, 0.5, 1.0] {
+            assert_close(left.point(t), curve.point(0.3 * t), 1e-6);
+            assert_close(right.point(t), curve.point(0.3 + 0.7 * t), 1e-6);
+        }
+
+        let middle = curve.segment(0.2, 0.6);
+        assert_close(middle.point(0.5), curve.point(0.4), 1e-6);
+
+        let elevated: Bezier2<5, f64> = curve.elevate();
+        for t in [0.1, 0.4, 0.8] {
+            assert_close(elevated.point(t), curve.point(t), 1e-6);
+        }
+    }
+
+    #[test]
+    fn bounds_and_closest() {
+        let curve = cubic();
+        let aabb = curve.bounding_box().unwrap();
+
+        let samples: Vec<_> = (0..=10000)
+            .map(|i| curve.point(i as f64 / 10000.0))
+            .collect();
+        let sampled = Aabb::from_points(samples.iter().copied()).unwrap();
+        assert_close(aabb.min, sampled.min, 1e-6);
+        assert_close(aabb.max, sampled.max, 1e-6);
+        assert!(aabb.max[0] <= 5.0 && aabb.max[1] < 3.0);
+
+        for query in [vector!(2.0, 2.0), vector!(-1.0, -1.0), vector!(3.0, -1.0)] {
+            let (t, closest) = curve.closest_point(&query);
+            assert_close(closest, curve.point(t), 1e-6);
+
+            let brute = samples
+                .iter()
+                .map(|p| (*p - query).length())
+                .fold(f64::MAX, f64::min);
+            assert!((closest - query).length() <= brute + 1e-9);
+        }
+    }
+
+    #[test]
+    fn arc_length() {
+        let line = QuadraticBezier3D::new([
+            vector!(0.0, 0.0, 0.0),
+            vector!(1.0, 2.0, 2.0),
+            vector!(2.0, 4.0, 4.0),
+        ]);
+        assert!((line.arc_length() - 6.0).abs() < 1e-9);
+        assert!((line.arc_length_to(0.5) - 3.0).abs() < 1e-9);
+
+        // Quarter circle approximation, off by well under a percent
+        let k = 0.552_284_8;
+        let arc = CubicBezier2F::new([
+            vector!(1.0, 0.0),
+            vector!(1.0, k),
+            vector!(k, 1.0),
+            vector!(0.0, 1.0),
+        ]);
+        assert!((arc.arc_length() - std::f32::consts::FRAC_PI_2).abs() < 1e-3);
+
+        let curve = cubic();
+        let polyline: f64 = (0..10000)
+            .map(|i| {
+                (curve.point((i + 1) as f64 / 10000.0) - curve.point(i as f64 / 10000.0)).length()
+            })
+            .sum();
+        assert!((curve.arc_length() - polyline).abs() < 1e-6);
+    }
+}
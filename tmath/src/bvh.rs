@@ -0,0 +1,644 @@
+use num_traits::real::Real;
+
+use crate::{aabb::Aabb, ray::Ray, vector::Vector};
+
+pub type Bvh2<T> = Bvh<2, T>;
+pub type Bvh3<T> = Bvh<3, T>;
+
+macro_rules! bvh_types {
+    ($($n:literal),*) => {
+        paste::paste! {
+            $(
+                pub type [< Bvh $n F >] = [< Bvh $n >]<f32>;
+                pub type [< Bvh $n D >] = [< Bvh $n >]<f64>;
+            )*
+        }
+    };
+}
+
+bvh_types!(2, 3);
+
+const MAX_LEAF_SIZE: usize = 4;
+const SAH_BINS: usize = 16;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum BvhSplit {
+    // Surface area heuristic over binned centroids, slower to build but faster to query
+    #[default]
+    Sah,
+    // Halves along the longest axis of the centroids
+    Median,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum NodeKind {
+    Leaf { start: usize, count: usize },
+    Interior { left: usize, right: usize },
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Node<const N: usize, T> {
+    bounds: Aabb<N, T>,
+    kind: NodeKind,
+}
+
+// Items are referred to by their index in the bounds it was built from. Children always come after
+// their parent in the node list
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bvh<const N: usize, T> {
+    nodes: Vec<Node<N, T>>,
+    indices: Vec<usize>,
+    bounds: Vec<Aabb<N, T>>,
+}
+
+fn union_all<const N: usize, T, I>(boxes: I) -> Option<Aabb<N, T>>
+where
+    T: Copy + PartialOrd,
+    I: IntoIterator<Item = Aabb<N, T>>,
+{
+    boxes.into_iter().fold(None, |acc, aabb| {
+        Some(acc.map_or(aabb, |acc| acc.union(&aabb)))
+    })
+}
+
+// Distance along the ray where it enters the box, zero when starting inside. None if it misses it
+// within max_distance
+pub(crate) fn slab_entry<const N: usize, T>(
+    ray: &Ray<N, T>,
+    aabb: &Aabb<N, T>,
+    max_distance: T,
+) -> Option<T>
+where
+    T: Real,
+{
+    let mut near = T::zero();
+    let mut far = max_distance;
+
+    for axis in 0..N {
+        let (origin, dir) = (ray.origin[axis], ray.direction[axis]);
+
+        if dir == T::zero() {
+            if origin < aabb.min[axis] || origin > aabb.max[axis] {
+                return None;
+            }
+
+            continue;
+        }
+
+        let t1 = (aabb.min[axis] - origin) / dir;
+        let t2 = (aabb.max[axis] - origin) / dir;
+
+        near = near.max(t1.min(t2));
+        far = far.min(t1.max(t2));
+        if near > far {
+            return None;
+        }
+    }
+
+    Some(near)
+}
+
+impl<const N: usize, T> Bvh<N, T> {
+    pub fn build(bounds: &[Aabb<N, T>], split: BvhSplit) -> Self
+    where
+        T: Real,
+    {
+        let mut bvh = Self {
+            nodes: Vec::new(),
+            indices: (0..bounds.len()).collect(),
+            bounds: bounds.to_vec(),
+        };
+
+        if !bounds.is_empty() {
+            let centroids: Vec<_> = bounds
+                .iter()
+                .map(|aabb| (aabb.min + aabb.max) / (T::one() + T::one()))
+                .collect();
+
+            bvh.build_node(&centroids, 0, bounds.len(), split);
+        }
+
+        bvh
+    }
+
+    fn build_node(
+        &mut self,
+        centroids: &[Vector<N, T>],
+        start: usize,
+        end: usize,
+        split: BvhSplit,
+    ) -> usize
+    where
+        T: Real,
+    {
+        let items = &self.indices[start..end];
+        let bounds = union_all(items.iter().map(|&i| self.bounds[i])).unwrap();
+
+        let index = self.nodes.len();
+        self.nodes.push(Node {
+            bounds,
+            kind: NodeKind::Leaf {
+                start,
+                count: end - start,
+            },
+        });
+
+        if end - start <= MAX_LEAF_SIZE {
+            return index;
+        }
+
+        let mid = match split {
+            BvhSplit::Sah => self.sah_split(centroids, start, end),
+            BvhSplit::Median => None,
+        }
+        .unwrap_or_else(|| self.median_split(centroids, start, end));
+
+        let left = self.build_node(centroids, start, mid, split);
+        let right = self.build_node(centroids, mid, end, split);
+        self.nodes[index].kind = NodeKind::Interior { left, right };
+
+        index
+    }
+
+    fn median_split(&mut self, centroids: &[Vector<N, T>], start: usize, end: usize) -> usize
+    where
+        T: Real,
+    {
+        let items = &mut self.indices[start..end];
+        let axis = Aabb::from_points(items.iter().map(|&i| centroids[i]))
+            .unwrap()
+            .longest_axis();
+
+        let mid = items.len() / 2;
+        items.select_nth_unstable_by(mid, |&a, &b| {
+            centroids[a][axis]
+                .partial_cmp(&centroids[b][axis])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        start + mid
+    }
+
+    // Cheapest split between bins of the centroids by the surface area heuristic, None when the
+    // centroids all coincide
+    fn sah_split(&mut self, centroids: &[Vector<N, T>], start: usize, end: usize) -> Option<usize>
+    where
+        T: Real,
+    {
+        let items = &mut self.indices[start..end];
+        let centroid_bounds = Aabb::from_points(items.iter().map(|&i| centroids[i]))?;
+        let scale = T::from(SAH_BINS).unwrap();
+
+        let bin = |item: usize, axis: usize| {
+            let extent = centroid_bounds.max[axis] - centroid_bounds.min[axis];
+            let offset = (centroids[item][axis] - centroid_bounds.min[axis]) / extent * scale;
+
+            offset.to_usize().unwrap_or(0).min(SAH_BINS - 1)
+        };
+
+        let mut best: Option<(T, usize, usize)> = None;
+        for axis in 0..N {
+            if centroid_bounds.max[axis] <= centroid_bounds.min[axis] {
+                continue;
+            }
+
+            let mut bins: [(Option<Aabb<N, T>>, usize); SAH_BINS] = [(None, 0); SAH_BINS];
+            for &item in items.iter() {
+                let (aabb, count) = &mut bins[bin(item, axis)];
+                *aabb = Some(aabb.map_or(self.bounds[item], |aabb| aabb.union(&self.bounds[item])));
+                *count += 1;
+            }
+
+            // Area times count of everything right of each split, then sweep in from the left
+            let mut right = [T::zero(); SAH_BINS];
+            let mut acc: (Option<Aabb<N, T>>, usize) = (None, 0);
+            for split in (1..SAH_BINS).rev() {
+                acc = (
+                    union_all(acc.0.into_iter().chain(bins[split].0)),
+                    acc.1 + bins[split].1,
+                );
+                right[split] =
+                    acc.0.map_or(T::zero(), |aabb| aabb.surface_area()) * T::from(acc.1).unwrap();
+            }
+
+            let mut acc: (Option<Aabb<N, T>>, usize) = (None, 0);
+            for split in 1..SAH_BINS {
+                acc = (
+                    union_all(acc.0.into_iter().chain(bins[split - 1].0)),
+                    acc.1 + bins[split - 1].1,
+                );
+                if acc.1 == 0 || acc.1 == items.len() {
+                    continue;
+                }
+
+                let left =
+                    acc.0.map_or(T::zero(), |aabb| aabb.surface_area()) * T::from(acc.1).unwrap();
+                let cost = left + right[split];
+
+                if best.is_none_or(|(best, _, _)| cost < best) {
+                    best = Some((cost, axis, split));
+                }
+            }
+        }
+
+        let (_, axis, split) = best?;
+
+        let (left, right): (Vec<usize>, Vec<usize>) =
+            items.iter().partition(|&&item| bin(item, axis) < split);
+        let mid = left.len();
+        for (slot, item) in items.iter_mut().zip(left.into_iter().chain(right)) {
+            *slot = item;
+        }
+
+        Some(start + mid)
+    }
+
+    // Number of items
+    pub fn len(&self) -> usize {
+        self.bounds.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bounds.is_empty()
+    }
+
+    pub fn bounding_box(&self) -> Option<Aabb<N, T>>
+    where
+        T: Copy,
+    {
+        self.nodes.first().map(|node| node.bounds)
+    }
+
+    // New bounds for the same items, keeping the tree as it was built. Quality drops the further
+    // the items move from where they were
+    pub fn refit(&mut self, bounds: &[Aabb<N, T>])
+    where
+        T: Copy + PartialOrd,
+    {
+        assert_eq!(
+            bounds.len(),
+            self.bounds.len(),
+            "refit with a different item count"
+        );
+        self.bounds.copy_from_slice(bounds);
+
+        for index in (0..self.nodes.len()).rev() {
+            self.nodes[index].bounds = match self.nodes[index].kind {
+                NodeKind::Leaf { start, count } => union_all(
+                    self.indices[start..start + count]
+                        .iter()
+                        .map(|&i| self.bounds[i]),
+                )
+                .unwrap(),
+                NodeKind::Interior { left, right } => {
+                    self.nodes[left].bounds.union(&self.nodes[right].bounds)
+                }
+            };
+        }
+    }
+
+    // Items whose bounds overlap the box
+    pub fn query_aabb(&self, aabb: &Aabb<N, T>) -> Vec<usize>
+    where
+        T: Copy + PartialOrd,
+    {
+        let mut res = Vec::new();
+        let mut stack: Vec<usize> = self.nodes.first().map(|_| 0).into_iter().collect();
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !node.bounds.intersects(aabb) {
+                continue;
+            }
+
+            match node.kind {
+                NodeKind::Leaf { start, count } => res.extend(
+                    self.indices[start..start + count]
+                        .iter()
+                        .filter(|&&i| self.bounds[i].intersects(aabb)),
+                ),
+                NodeKind::Interior { left, right } => stack.extend([right, left]),
+            }
+        }
+
+        res
+    }
+
+    // Closest item the ray hits within max_distance, intersect gives the distance to an item along
+    // the ray if it's hit
+    pub fn cast_ray<F>(
+        &self,
+        ray: &Ray<N, T>,
+        max_distance: T,
+        mut intersect: F,
+    ) -> Option<(usize, T)>
+    where
+        T: Real,
+        F: FnMut(usize) -> Option<T>,
+    {
+        let mut best: Option<(usize, T)> = None;
+        let mut stack: Vec<(usize, T)> = Vec::new();
+
+        if let Some(root) = self.nodes.first() {
+            stack.extend(slab_entry(ray, &root.bounds, max_distance).map(|t| (0, t)));
+        }
+
+        while let Some((index, entry)) = stack.pop() {
+            let limit = best.map_or(max_distance, |(_, t)| t);
+            if entry > limit {
+                continue;
+            }
+
+            match self.nodes[index].kind {
+                NodeKind::Leaf { start, count } => {
+                    for &item in &self.indices[start..start + count] {
+                        let limit = best.map_or(max_distance, |(_, t)| t);
+                        if slab_entry(ray, &self.bounds[item], limit).is_none() {
+                            continue;
+                        }
+
+                        if let Some(t) = intersect(item).filter(|&t| t >= T::zero() && t <= limit) {
+                            best = Some((item, t));
+                        }
+                    }
+                }
+                NodeKind::Interior { left, right } => {
+                    let near = slab_entry(ray, &self.nodes[left].bounds, limit);
+                    let far = slab_entry(ray, &self.nodes[right].bounds, limit);
+
+                    // Nearer child on top of the stack
+                    let mut children: Vec<_> = [(left, near), (right, far)]
+                        .into_iter()
+                        .filter_map(|(child, t)| t.map(|t| (child, t)))
+                        .collect();
+                    children
+                        .sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+                    stack.extend(children);
+                }
+            }
+        }
+
+        best
+    }
+
+    // First item found that the ray hits within max_distance, e.g. for shadow rays
+    pub fn cast_ray_any<F>(
+        &self,
+        ray: &Ray<N, T>,
+        max_distance: T,
+        mut intersect: F,
+    ) -> Option<usize>
+    where
+        T: Real,
+        F: FnMut(usize) -> Option<T>,
+    {
+        let mut stack: Vec<usize> = self.nodes.first().map(|_| 0).into_iter().collect();
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if slab_entry(ray, &node.bounds, max_distance).is_none() {
+                continue;
+            }
+
+            match node.kind {
+                NodeKind::Leaf { start, count } => {
+                    let hit = self.indices[start..start + count].iter().find(|&&item| {
+                        slab_entry(ray, &self.bounds[item], max_distance).is_some()
+                            && intersect(item).is_some_and(|t| t >= T::zero() && t <= max_distance)
+                    });
+
+                    if hit.is_some() {
+                        return hit.copied();
+                    }
+                }
+                NodeKind::Interior { left, right } => stack.extend([right, left]),
+            }
+        }
+
+        None
+    }
+
+    // Item with the closest point, closest gives the point on an item nearest to the query
+    pub fn nearest<F>(&self, point: &Vector<N, T>, mut closest: F) -> Option<(usize, Vector<N, T>)>
+    where
+        T: Real,
+        F: FnMut(usize) -> Vector<N, T>,
+    {
+        let mut best: Option<(usize, Vector<N, T>, T)> = None;
+        let mut stack: Vec<(usize, T)> = self
+            .nodes
+            .first()
+            .map(|root| (0, root.bounds.distance_squared(point)))
+            .into_iter()
+            .collect();
+
+        while let Some((index, distance)) = stack.pop() {
+            if best.is_some_and(|(_, _, best)| distance >= best) {
+                continue;
+            }
+
+            match self.nodes[index].kind {
+                NodeKind::Leaf { start, count } => {
+                    for &item in &self.indices[start..start + count] {
+                        if best.is_some_and(|(_, _, best)| {
+                            self.bounds[item].distance_squared(point) >= best
+                        }) {
+                            continue;
+                        }
+
+                        let candidate = closest(item);
+                        let distance = (candidate - point).length_squared();
+                        if best.is_none_or(|(_, _, best)| distance < best) {
+                            best = Some((item, candidate, distance));
+                        }
+                    }
+                }
+                NodeKind::Interior { left, right } => {
+                    let a = (left, self.nodes[left].bounds.distance_squared(point));
+                    let b = (right, self.nodes[right].bounds.distance_squared(point));
+
+                    // Nearer child on top of the stack
+                    if a.1 <= b.1 {
+                        stack.extend([b, a]);
+                    } else {
+                        stack.extend([a, b]);
+                    }
+                }
+            }
+        }
+
+        best.map(|(item, point, _)| (item, point))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::{
+        aabb::Aabb3,
+        triangle::Triangle3,
+        vector,
+        vector::{Vector2, Vector3},
+    };
+
+    fn triangles(count: usize) -> Vec<Triangle3<f64>> {
+        let mut rng = StdRng::seed_from_u64(7);
+        let point = |rng: &mut StdRng| {
+            vector!(
+                rng.gen_range(-10.0..10.0),
+                rng.gen_range(-10.0..10.0),
+                rng.gen_range(-10.0..10.0)
+            )
+        };
+
+        (0..count)
+            .map(|_| {
+                let a = point(&mut rng);
+                let offset = |rng: &mut StdRng| {
+                    vector!(
+                        rng.gen_range(-1.0..1.0),
+                        rng.gen_range(-1.0..1.0),
+                        rng.gen_range(-1.0..1.0)
+                    )
+                };
+
+                Triangle3::new(a, a + offset(&mut rng), a + offset(&mut rng))
+            })
+            .collect()
+    }
+
+    fn bounds(triangles: &[Triangle3<f64>]) -> Vec<Aabb3<f64>> {
+        triangles
+            .iter()
+            .map(|t| Aabb::from_points(t.vertices()).unwrap())
+            .collect()
+    }
+
+    fn hit(triangle: &Triangle3<f64>, ray: &Ray<3, f64>) -> Option<f64> {
+        ray.intersect_triangle(&triangle.a, &triangle.b, &triangle.c)
+            .map(|hit| hit.distance)
+    }
+
+    #[test]
+    fn ray_casts() {
+        let triangles = triangles(500);
+        let mut rng = StdRng::seed_from_u64(3);
+
+        for split in [BvhSplit::Sah, BvhSplit::Median] {
+            let bvh = Bvh::build(&bounds(&triangles), split);
+            assert_eq!(bvh.len(), 500);
+
+            for _ in 0..200 {
+                let origin = vector!(
+                    rng.gen_range(-15.0..15.0),
+                    rng.gen_range(-15.0..15.0),
+                    -15.0
+                );
+                let target = vector!(rng.gen_range(-5.0..5.0), rng.gen_range(-5.0..5.0), 0.0);
+                let ray = Ray::through(origin, target);
+
+                let expected = triangles
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, t)| hit(t, &ray).map(|d| (i, d)))
+                    .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+
+                let actual = bvh.cast_ray(&ray, f64::MAX, |i| hit(&triangles[i], &ray));
+                assert_eq!(actual, expected);
+
+                let any = bvh.cast_ray_any(&ray, f64::MAX, |i| hit(&triangles[i], &ray));
+                assert_eq!(any.is_some(), expected.is_some());
+
+                // Shadow ray stopping just short of the closest hit
+                if let Some((_, distance)) = expected {
+                    let short =
+                        bvh.cast_ray_any(&ray, distance * 0.999, |i| hit(&triangles[i], &ray));
+                    assert!(short.is_none());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn overlap_and_nearest() {
+        let triangles = triangles(300);
+        let boxes = bounds(&triangles);
+        let bvh = Bvh::build(&boxes, BvhSplit::Sah);
+
+        let query = Aabb::new(vector!(-3.0, -3.0, -3.0), vector!(2.0, 4.0, 1.0));
+        let mut found = bvh.query_aabb(&query);
+        found.sort_unstable();
+        let expected: Vec<_> = (0..boxes.len())
+            .filter(|&i| boxes[i].intersects(&query))
+            .collect();
+        assert_eq!(found, expected);
+
+        let mut rng = StdRng::seed_from_u64(11);
+        for _ in 0..50 {
+            let point = vector!(
+                rng.gen_range(-12.0..12.0),
+                rng.gen_range(-12.0..12.0),
+                rng.gen_range(-12.0..12.0)
+            );
+            let distance = |p: Vector3<f64>| (p - point).length();
+
+            let (_, closest) = bvh
+                .nearest(&point, |i| triangles[i].closest_point(&point))
+                .unwrap();
+            let expected = triangles
+                .iter()
+                .map(|t| distance(t.closest_point(&point)))
+                .fold(f64::MAX, f64::min);
+            assert!((distance(closest) - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn refit() {
+        let points: Vec<Vector2<f64>> = (0..64)
+            .map(|i| vector!((i % 8) as f64, (i / 8) as f64))
+            .collect();
+        let boxes = |offset: Vector2<f64>| -> Vec<_> {
+            points
+                .iter()
+                .map(|p| Aabb::new(*p + offset, *p + offset + vector!(0.5, 0.5)))
+                .collect()
+        };
+
+        let mut bvh = Bvh::build(&boxes(vector!(0.0, 0.0)), BvhSplit::Median);
+        assert_eq!(
+            bvh.bounding_box(),
+            Some(Aabb::new(vector!(0.0, 0.0), vector!(7.5, 7.5)))
+        );
+
+        let moved = boxes(vector!(10.0, 0.0));
+        bvh.refit(&moved);
+        assert_eq!(
+            bvh.bounding_box(),
+            Some(Aabb::new(vector!(10.0, 0.0), vector!(17.5, 7.5)))
+        );
+
+        let ray = Ray::new(vector!(0.0, 3.25), vector!(1.0, 0.0));
+        let (item, distance) = bvh
+            .cast_ray(&ray, f64::MAX, |i| {
+                ray.intersect_aabb(&moved[i]).map(|hit| hit.distance)
+            })
+            .unwrap();
+        assert_eq!(item, 24);
+        assert_eq!(distance, 10.0);
+
+        assert_eq!(
+            bvh.query_aabb(&Aabb::new(vector!(0.0, 0.0), vector!(9.0, 9.0))),
+            Vec::<usize>::new()
+        );
+
+        let empty = Bvh2::<f64>::build(&[], BvhSplit::Sah);
+        assert!(empty.is_empty());
+        assert!(empty
+            .nearest(&vector!(0.0, 0.0), |_| unreachable!())
+            .is_none());
+    }
+}
@@ -0,0 +1,496 @@
+use std::cmp::Ordering;
+
+use num_traits::real::Real;
+
+use crate::{
+    aabb::Aabb,
+    gjk::{gjk_distance, gjk_intersects},
+    support_map::SupportMap,
+    vector::{Vector, Vector2, Vector3},
+};
+
+pub type ConvexHull2<T> = ConvexHull<2, T>;
+pub type ConvexHull3<T> = ConvexHull<3, T>;
+
+macro_rules! convex_hull_types {
+    ($($n:literal),*) => {
+        paste::paste! {
+            $(
+                pub type [< ConvexHull $n F >] = [< ConvexHull $n >]<f32>;
+                pub type [< ConvexHull $n D >] = [< ConvexHull $n >]<f64>;
+            )*
+        }
+    };
+}
+
+convex_hull_types!(2, 3);
+
+fn cross_2d<T>(o: &Vector2<T>, a: &Vector2<T>, b: &Vector2<T>) -> T
+where
+    T: Real,
+{
+    (a.x() - o.x()) * (b.y() - o.y()) - (a.y() - o.y()) * (b.x() - o.x())
+}
+
+// Andrew's monotone chain. Indices of the hull vertices in counter-clockwise order, starting from
+// the lowest x. Collinear points and duplicates are left out
+pub fn convex_hull_2d<T>(points: &[Vector2<T>]) -> Vec<usize>
+where
+    T: Real,
+{
+    let mut order: Vec<_> = (0..points.len()).collect();
+    order.sort_by(|&a, &b| {
+        (points[a].x(), points[a].y())
+            .partial_cmp(&(points[b].x(), points[b].y()))
+            .unwrap_or(Ordering::Equal)
+    });
+    order.dedup_by(|a, b| points[*a] == points[*b]);
+
+    if order.len() < 3 {
+        return order;
+    }
+
+    let mut hull: Vec<usize> = Vec::with_capacity(order.len() + 1);
+    let turns_left = |hull: &[usize], index: usize| {
+        cross_2d(
+            &points[hull[hull.len() - 2]],
+            &points[hull[hull.len() - 1]],
+            &points[index],
+        ) > T::zero()
+    };
+
+    // Lower chain left to right, then the upper one back
+    for &index in &order {
+        while hull.len() >= 2 && !turns_left(&hull, index) {
+            hull.pop();
+        }
+        hull.push(index);
+    }
+
+    let lower_len = hull.len() + 1;
+    for &index in order.iter().rev().skip(1) {
+        while hull.len() >= lower_len && !turns_left(&hull, index) {
+            hull.pop();
+        }
+        hull.push(index);
+    }
+
+    // The last one is the starting point again
+    hull.pop();
+
+    hull
+}
+
+struct Face<T> {
+    vertices: [usize; 3],
+    normal: Vector3<T>,
+    offset: T,
+    outside: Vec<usize>,
+}
+
+impl<T> Face<T>
+where
+    T: Default + Real,
+{
+    fn new(points: &[Vector3<T>], vertices: [usize; 3]) -> Self {
+        let [a, b, c] = vertices.map(|i| points[i]);
+        let normal = (b - a).cross(&(c - a)).normalized();
+
+        Self {
+            vertices,
+            offset: normal.dot(&a),
+            normal,
+            outside: Vec::new(),
+        }
+    }
+
+    fn distance(&self, point: &Vector3<T>) -> T {
+        self.normal.dot(point) - self.offset
+    }
+}
+
+// Item with the largest measure, the first one on ties so duplicates of a point lose to it
+fn furthest_by<I, T, F>(items: impl Iterator<Item = I>, measure: F) -> Option<(I, T)>
+where
+    T: Real,
+    F: Fn(I) -> T,
+    I: Copy,
+{
+    items.fold(None, |best, item| {
+        let val = measure(item);
+
+        match best {
+            Some((_, best_val)) if best_val >= val => best,
+            _ => Some((item, val)),
+        }
+    })
+}
+
+// Quickhull. Indices of the hull vertices and its triangles, wound counter-clockwise seen from
+// outside. None if the points are all coplanar. Points within a tolerance of the hull surface, such
+// as duplicates or ones on a face, are left out
+pub fn convex_hull_3d<T>(points: &[Vector3<T>]) -> Option<(Vec<usize>, Vec<[usize; 3]>)>
+where
+    T: Default + Real,
+{
+    let extent = points.iter().fold(T::zero(), |max, point| {
+        max.max(point.0.iter().fold(T::zero(), |sum, val| sum + val.abs()))
+    });
+    let eps = extent * T::epsilon() * T::from(16.0).unwrap();
+
+    // Initial tetrahedron from extreme points
+    let extremes: Vec<usize> = (0..3)
+        .flat_map(|axis| {
+            let by_axis = |i: usize| points[i][axis];
+            let max = furthest_by(0..points.len(), by_axis);
+            let min = furthest_by(0..points.len(), |i| -by_axis(i));
+
+            [min, max]
+        })
+        .flatten()
+        .map(|(i, _)| i)
+        .collect();
+
+    let ((a, b), _) = furthest_by(
+        extremes
+            .iter()
+            .flat_map(|&a| extremes.iter().map(move |&b| (a, b))),
+        |(a, b)| (points[a] - points[b]).length_squared(),
+    )?;
+    let ab = points[b] - points[a];
+
+    let (c, c_dist) = furthest_by(0..points.len(), |i| {
+        ab.cross(&(points[i] - points[a])).length() / ab.length().max(T::epsilon())
+    })?;
+    if c_dist <= eps {
+        return None;
+    }
+
+    let base = Face::new(points, [a, b, c]);
+    let (d, d_dist) = furthest_by(0..points.len(), |i| base.distance(&points[i]).abs())?;
+    if d_dist <= eps {
+        return None;
+    }
+
+    let mut faces = if base.distance(&points[d]) > T::zero() {
+        vec![[a, c, b], [a, b, d], [b, c, d], [c, a, d]]
+    } else {
+        vec![[a, b, c], [a, d, b], [b, d, c], [c, d, a]]
+    }
+    .into_iter()
+    .map(|vertices| Some(Face::new(points, vertices)))
+    .collect::<Vec<_>>();
+
+    let assign = |faces: &mut [Option<Face<T>>], candidates: &mut dyn Iterator<Item = usize>| {
+        for i in candidates {
+            let best = furthest_by(
+                faces
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, face)| face.is_some())
+                    .map(|(f, _)| f),
+                |f| faces[f].as_ref().unwrap().distance(&points[i]),
+            );
+
+            if let Some((f, dist)) = best {
+                if dist > eps {
+                    faces[f].as_mut().unwrap().outside.push(i);
+                }
+            }
+        }
+    };
+
+    let initial = [a, b, c, d];
+    assign(
+        &mut faces,
+        &mut (0..points.len()).filter(|i| !initial.contains(i)),
+    );
+
+    while let Some(f) = faces
+        .iter()
+        .position(|face| face.as_ref().is_some_and(|face| !face.outside.is_empty()))
+    {
+        let face = faces[f].as_ref().unwrap();
+        let (eye, _) = furthest_by(face.outside.iter().copied(), |i| face.distance(&points[i]))?;
+        let eye_point = points[eye];
+
+        let visible: Vec<usize> = (0..faces.len())
+            .filter(|&f| {
+                faces[f]
+                    .as_ref()
+                    .is_some_and(|face| face.distance(&eye_point) > eps)
+            })
+            .collect();
+
+        // Directed edges of the visible region without their reverse are its boundary
+        let edges: Vec<(usize, usize)> = visible
+            .iter()
+            .flat_map(|&f| {
+                let [a, b, c] = faces[f].as_ref().unwrap().vertices;
+
+                [(a, b), (b, c), (c, a)]
+            })
+            .collect();
+        let horizon: Vec<_> = edges
+            .iter()
+            .filter(|&&(a, b)| !edges.contains(&(b, a)))
+            .copied()
+            .collect();
+
+        let mut orphans = Vec::new();
+        for &f in &visible {
+            orphans.extend(faces[f].take().unwrap().outside);
+        }
+
+        let first_new = faces.len();
+        faces.extend(
+            horizon
+                .into_iter()
+                .map(|(a, b)| Some(Face::new(points, [a, b, eye]))),
+        );
+
+        let (_, new_faces) = faces.split_at_mut(first_new);
+        assign(new_faces, &mut orphans.into_iter().filter(|&i| i != eye));
+    }
+
+    let triangles: Vec<[usize; 3]> = faces
+        .into_iter()
+        .flatten()
+        .map(|face| face.vertices)
+        .collect();
+    let mut vertices: Vec<usize> = triangles.iter().flatten().copied().collect();
+    vertices.sort_unstable();
+    vertices.dedup();
+
+    Some((vertices, triangles))
+}
+
+// Convex polygon in 2D with edges as faces, convex polyhedron in 3D with triangles as faces. Face
+// indices refer to the hull's own vertices
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvexHull<const N: usize, T> {
+    pub(crate) vertices: Vec<Vector<N, T>>,
+    pub(crate) faces: Vec<[usize; N]>,
+}
+
+impl<const N: usize, T> ConvexHull<N, T> {
+    pub fn vertices(&self) -> &[Vector<N, T>] {
+        &self.vertices
+    }
+
+    pub fn faces(&self) -> &[[usize; N]] {
+        &self.faces
+    }
+
+    pub fn bounding_box(&self) -> Aabb<N, T>
+    where
+        T: Copy + PartialOrd,
+    {
+        Aabb::from_points(self.vertices.iter().copied()).unwrap()
+    }
+
+    pub fn contains_point(&self, point: &Vector<N, T>) -> bool
+    where
+        T: Default + Real,
+    {
+        gjk_intersects(self, point)
+    }
+
+    pub fn closest_point(&self, point: &Vector<N, T>) -> Vector<N, T>
+    where
+        T: Default + Real,
+    {
+        gjk_distance(self, point).map_or(*point, |separation| separation.point_a)
+    }
+
+    pub fn intersects<S>(&self, other: &S) -> bool
+    where
+        T: Default + Real,
+        S: SupportMap<N, T> + ?Sized,
+    {
+        gjk_intersects(self, other)
+    }
+
+    // Zero when they intersect
+    pub fn distance<S>(&self, other: &S) -> T
+    where
+        T: Default + Real,
+        S: SupportMap<N, T> + ?Sized,
+    {
+        gjk_distance(self, other).map_or(T::zero(), |separation| separation.distance)
+    }
+}
+
+impl<T> ConvexHull2<T> {
+    // Vertices counter-clockwise, None without any points
+    pub fn from_points(points: &[Vector2<T>]) -> Option<Self>
+    where
+        T: Real,
+    {
+        let indices = convex_hull_2d(points);
+        if indices.is_empty() {
+            return None;
+        }
+
+        let count = indices.len();
+
+        Some(Self {
+            vertices: indices.into_iter().map(|i| points[i]).collect(),
+            faces: (0..count).map(|i| [i, (i + 1) % count]).collect(),
+        })
+    }
+}
+
+impl<T> ConvexHull3<T> {
+    // None if the points are all coplanar
+    pub fn from_points(points: &[Vector3<T>]) -> Option<Self>
+    where
+        T: Default + Real,
+    {
+        let (indices, triangles) = convex_hull_3d(points)?;
+
+        let remap = |index: usize| indices.binary_search(&index).unwrap();
+
+        Some(Self {
+            vertices: indices.iter().map(|&i| points[i]).collect(),
+            faces: triangles
+                .into_iter()
+                .map(|triangle| triangle.map(remap))
+                .collect(),
+        })
+    }
+}
+
+impl<const N: usize, T> SupportMap<N, T> for ConvexHull<N, T>
+where
+    T: Default + Real,
+{
+    fn support(&self, direction: &Vector<N, T>) -> Vector<N, T> {
+        self.vertices.support(direction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{shapes::Sphere, vector};
+
+    #[test]
+    fn hull_2d() {
+        let points = [
+            vector!(0.0, 0.0),
+            vector!(1.0, 1.0),
+            vector!(2.0, 0.0),
+            vector!(2.0, 2.0),
+            vector!(0.0, 2.0),
+            vector!(1.0, 0.0),
+            vector!(2.0, 2.0),
+            vector!(0.5, 1.5),
+        ];
+
+        assert_eq!(convex_hull_2d(&points), vec![0, 2, 3, 4]);
+
+        let collinear = [vector!(0.0, 0.0), vector!(1.0, 1.0), vector!(2.0, 2.0)];
+        assert_eq!(convex_hull_2d(&collinear), vec![0, 2]);
+        assert_eq!(convex_hull_2d::<f64>(&[]), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn hull_3d() {
+        let mut points = vec![];
+        for i in 0..8 {
+            points.push(vector!(
+                (i & 1) as f64,
+                ((i >> 1) & 1) as f64,
+                ((i >> 2) & 1) as f64
+            ));
+        }
+        // Inside, duplicates, on faces and on edges
+        points.extend([
+            vector!(0.5, 0.5, 0.5),
+            vector!(1.0, 1.0, 1.0),
+            vector!(0.0, 0.0, 0.0),
+            vector!(0.5, 0.5, 1.0),
+            vector!(0.5, 0.0, 0.0),
+            vector!(0.2, 0.7, 0.3),
+        ]);
+
+        let (vertices, faces) = convex_hull_3d(&points).unwrap();
+        assert_eq!(vertices, (0..8).collect::<Vec<_>>());
+        assert_eq!(faces.len(), 12);
+
+        // Every face is wound outwards
+        let center = vector!(0.5, 0.5, 0.5);
+        for [a, b, c] in &faces {
+            let normal = (points[*b] - points[*a]).cross(&(points[*c] - points[*a]));
+            assert!(normal.dot(&(points[*a] - center)) > 0.0);
+        }
+
+        // Fibonacci sphere, every point ends up on the hull and behind every face
+        let sphere: Vec<_> = (0..200)
+            .map(|i| {
+                let y = 1.0 - 2.0 * (i as f64 + 0.5) / 200.0;
+                let r = (1.0 - y * y).sqrt();
+                let theta = i as f64 * 2.399963229728653;
+
+                vector!(r * theta.cos(), y, r * theta.sin())
+            })
+            .collect();
+        let (vertices, faces) = convex_hull_3d(&sphere).unwrap();
+        assert_eq!(vertices.len(), 200);
+        assert_eq!(faces.len(), 2 * 200 - 4);
+        for [a, b, c] in &faces {
+            let normal = (sphere[*b] - sphere[*a]).cross(&(sphere[*c] - sphere[*a]));
+            assert!(sphere
+                .iter()
+                .all(|point| normal.dot(&(*point - sphere[*a])) <= 1e-12));
+        }
+
+        let flat = [
+            vector!(0.0, 0.0, 0.0),
+            vector!(1.0, 0.0, 0.0),
+            vector!(0.0, 1.0, 0.0),
+            vector!(1.0, 1.0, 0.0),
+        ];
+        assert!(convex_hull_3d(&flat).is_none());
+    }
+
+    #[test]
+    fn queries() {
+        let hull = ConvexHull2D::from_points(&[
+            vector!(0.0, 0.0),
+            vector!(4.0, 0.0),
+            vector!(4.0, 2.0),
+            vector!(0.0, 2.0),
+            vector!(1.0, 1.0),
+        ])
+        .unwrap();
+
+        assert_eq!(hull.vertices().len(), 4);
+        assert_eq!(hull.faces()[3], [3, 0]);
+        assert!(hull.contains_point(&vector!(3.0, 1.0)));
+        assert!(!hull.contains_point(&vector!(5.0, 1.0)));
+        assert!((hull.closest_point(&vector!(5.0, 1.0)) - vector!(4.0, 1.0)).length() < 1e-9);
+        assert_eq!(hull.closest_point(&vector!(1.0, 1.0)), vector!(1.0, 1.0));
+
+        let octahedron = ConvexHull3D::from_points(&[
+            vector!(1.0, 0.0, 0.0),
+            vector!(-1.0, 0.0, 0.0),
+            vector!(0.0, 1.0, 0.0),
+            vector!(0.0, -1.0, 0.0),
+            vector!(0.0, 0.0, 1.0),
+            vector!(0.0, 0.0, -1.0),
+        ])
+        .unwrap();
+
+        assert_eq!(octahedron.faces().len(), 8);
+        assert_eq!(
+            octahedron.bounding_box(),
+            Aabb::new(vector!(-1.0, -1.0, -1.0), vector!(1.0, 1.0, 1.0))
+        );
+        assert!(octahedron.intersects(&Sphere::new(vector!(1.0, 1.0, 0.0), 0.75)));
+        assert!(!octahedron.intersects(&Sphere::new(vector!(1.0, 1.0, 0.0), 0.7)));
+        assert!((octahedron.distance(&vector!(1.0, 1.0, 1.0)) - 2.0 / 3.0_f64.sqrt()).abs() < 1e-9);
+    }
+}
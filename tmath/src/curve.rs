@@ -0,0 +1,200 @@
+use num_traits::real::Real;
+
+use crate::{
+    bezier::{integrate, Bezier},
+    vector::Vector,
+};
+
+// Parametric curve over the parameters from domain().0 to domain().1, evaluating outside of it
+// clamps to the ends
+pub trait Curve<const N: usize, T> {
+    fn domain(&self) -> (T, T);
+    fn point(&self, t: T) -> Vector<N, T>;
+    fn derivative(&self, t: T) -> Vector<N, T>;
+    fn second_derivative(&self, t: T) -> Vector<N, T>;
+    fn third_derivative(&self, t: T) -> Vector<N, T>;
+}
+
+impl<const N: usize, T, C> Curve<N, T> for &C
+where
+    C: Curve<N, T> + ?Sized,
+{
+    fn domain(&self) -> (T, T) {
+        (**self).domain()
+    }
+
+    fn point(&self, t: T) -> Vector<N, T> {
+        (**self).point(t)
+    }
+
+    fn derivative(&self, t: T) -> Vector<N, T> {
+        (**self).derivative(t)
+    }
+
+    fn second_derivative(&self, t: T) -> Vector<N, T> {
+        (**self).second_derivative(t)
+    }
+
+    fn third_derivative(&self, t: T) -> Vector<N, T> {
+        (**self).third_derivative(t)
+    }
+}
+
+impl<const D: usize, const N: usize, T> Curve<N, T> for Bezier<D, N, T>
+where
+    T: Real,
+{
+    fn domain(&self) -> (T, T) {
+        (T::zero(), T::one())
+    }
+
+    fn point(&self, t: T) -> Vector<N, T> {
+        Bezier::point(self, t.max(T::zero()).min(T::one()))
+    }
+
+    fn derivative(&self, t: T) -> Vector<N, T> {
+        Bezier::derivative(self, t.max(T::zero()).min(T::one()))
+    }
+
+    fn second_derivative(&self, t: T) -> Vector<N, T> {
+        Bezier::second_derivative(self, t.max(T::zero()).min(T::one()))
+    }
+
+    fn third_derivative(&self, t: T) -> Vector<N, T> {
+        Bezier::third_derivative(self, t.max(T::zero()).min(T::one()))
+    }
+}
+
+// Cumulative length at evenly spaced parameters, to move along a curve by distance instead of by
+// parameter. In between the samples the parameter is interpolated linearly
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArcLengthTable<T> {
+    // (parameter, length up to it) with both increasing
+    samples: Vec<(T, T)>,
+}
+
+impl<T> ArcLengthTable<T> {
+    pub fn new<const N: usize, C>(curve: &C, samples: usize) -> Self
+    where
+        T: Default + Real,
+        C: Curve<N, T>,
+    {
+        let (start, end) = curve.domain();
+        let count = samples.max(1);
+        let step = (end - start) / T::from(count).unwrap();
+        let speed = |t: T| curve.derivative(t).length();
+
+        let mut table = Vec::with_capacity(count + 1);
+        table.push((start, T::zero()));
+
+        for i in 1..=count {
+            let (t0, length) = table[i - 1];
+            let t1 = if i == count {
+                end
+            } else {
+                start + step * T::from(i).unwrap()
+            };
+
+            table.push((t1, length + integrate(&speed, t0, t1)));
+        }
+
+        Self { samples: table }
+    }
+
+    pub fn length(&self) -> T
+    where
+        T: Copy,
+    {
+        self.samples[self.samples.len() - 1].1
+    }
+
+    // Parameter at the distance along the curve, clamped to the ends
+    pub fn parameter(&self, distance: T) -> T
+    where
+        T: Real,
+    {
+        let i = self
+            .samples
+            .partition_point(|&(_, length)| length < distance)
+            .clamp(1, self.samples.len() - 1);
+        let ((t0, s0), (t1, s1)) = (self.samples[i - 1], self.samples[i]);
+
+        if s1 <= s0 {
+            return t0;
+        }
+
+        let along = ((distance - s0) / (s1 - s0)).max(T::zero()).min(T::one());
+
+        t0 + (t1 - t0) * along
+    }
+
+    // Distance along the curve up to the parameter, the inverse of parameter()
+    pub fn distance(&self, t: T) -> T
+    where
+        T: Real,
+    {
+        let i = self
+            .samples
+            .partition_point(|&(sample, _)| sample < t)
+            .clamp(1, self.samples.len() - 1);
+        let ((t0, s0), (t1, s1)) = (self.samples[i - 1], self.samples[i]);
+
+        if t1 <= t0 {
+            return s0;
+        }
+
+        let along = ((t - t0) / (t1 - t0)).max(T::zero()).min(T::one());
+
+        s0 + (s1 - s0) * along
+    }
+
+    // Parameters spaced evenly by length, count of them including both ends
+    pub fn uniform_parameters(&self, count: usize) -> impl Iterator<Item = T> + '_
+    where
+        T: Real,
+    {
+        let length = self.length();
+        let last = T::from(count.saturating_sub(1).max(1)).unwrap();
+
+        (0..count).map(move |i| self.parameter(length * T::from(i).unwrap() / last))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{bezier::CubicBezier2D, vector};
+
+    #[test]
+    fn arc_length_table() {
+        let curve = CubicBezier2D::new([
+            vector!(0.0, 0.0),
+            vector!(0.2, 3.0),
+            vector!(4.0, 3.0),
+            vector!(5.0, 0.0),
+        ]);
+        let table = ArcLengthTable::new(&curve, 256);
+
+        assert!((table.length() - curve.arc_length()).abs() < 1e-9);
+        assert_eq!(table.parameter(-1.0), 0.0);
+        assert_eq!(table.parameter(table.length() + 1.0), 1.0);
+
+        for t in [0.1, 0.35, 0.8] {
+            assert!((table.distance(t) - curve.arc_length_to(t)).abs() < 1e-3);
+            assert!((table.parameter(table.distance(t)) - t).abs() < 1e-9);
+        }
+
+        // Steps of equal length along the curve
+        let points: Vec<_> = table
+            .uniform_parameters(21)
+            .map(|t| Curve::point(&curve, t))
+            .collect();
+        assert_eq!(points.len(), 21);
+
+        let step = table.length() / 20.0;
+        for w in points.windows(2) {
+            assert!(((w[1] - w[0]).length() - step).abs() < 1e-2 * step);
+        }
+    }
+}
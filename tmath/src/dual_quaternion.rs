@@ -0,0 +1,442 @@
+use num_traits::{real::Real, One, Zero};
+
+use crate::{quaternion::Quaternion, vector::Vector3};
+
+pub type DualQuaternionF = DualQuaternion<f32>;
+pub type DualQuaternionD = DualQuaternion<f64>;
+
+// real + ε dual, where ε² = 0
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DualQuaternion<T> {
+    pub real: Quaternion<T>,
+    pub dual: Quaternion<T>,
+}
+
+impl<T> Default for DualQuaternion<T>
+where
+    T: Copy + Zero + One,
+{
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl<T> DualQuaternion<T> {
+    pub const fn new(real: Quaternion<T>, dual: Quaternion<T>) -> Self {
+        Self { real, dual }
+    }
+
+    pub fn identity() -> Self
+    where
+        T: Copy + Zero + One,
+    {
+        Self::new(
+            Quaternion::identity(),
+            Quaternion::new(T::zero(), T::zero(), T::zero(), T::zero()),
+        )
+    }
+
+    // Rotation is applied first, then translation
+    pub fn from_rotation_translation(rotation: Quaternion<T>, translation: Vector3<T>) -> Self
+    where
+        T: Real,
+    {
+        let half = T::one() / (T::one() + T::one());
+        let dual = Quaternion::from_parts(translation, T::zero()) * rotation * half;
+
+        Self::new(rotation, dual)
+    }
+
+    pub fn from_rotation(rotation: Quaternion<T>) -> Self
+    where
+        T: Real,
+    {
+        Self::from_rotation_translation(rotation, Vector3::new_val(T::zero()))
+    }
+
+    pub fn from_translation(translation: Vector3<T>) -> Self
+    where
+        T: Real,
+    {
+        Self::from_rotation_translation(Quaternion::identity(), translation)
+    }
+
+    pub fn rotation(&self) -> Quaternion<T>
+    where
+        T: Copy,
+    {
+        self.real
+    }
+
+    pub fn translation(&self) -> Vector3<T>
+    where
+        T: Real,
+    {
+        let two = T::one() + T::one();
+
+        (self.dual * self.real.conjugate()).vector() * two
+    }
+
+    // Quaternion conjugate of both parts, the inverse of a unit dual quaternion
+    pub fn conjugate(&self) -> Self
+    where
+        T: Real,
+    {
+        Self::new(self.real.conjugate(), self.dual.conjugate())
+    }
+
+    pub fn dual_conjugate(&self) -> Self
+    where
+        T: Real,
+    {
+        Self::new(self.real, -self.dual)
+    }
+
+    pub fn combined_conjugate(&self) -> Self
+    where
+        T: Real,
+    {
+        Self::new(self.real.conjugate(), -self.dual.conjugate())
+    }
+
+    pub fn inverse(&self) -> Option<Self>
+    where
+        T: Real,
+    {
+        let real = self.real.inverse()?;
+
+        Some(Self::new(real, -(real * self.dual * real)))
+    }
+
+    pub fn is_unit(&self) -> bool
+    where
+        T: Default + Real,
+    {
+        let eps = T::epsilon().sqrt();
+
+        (self.real.length_squared() - T::one()).abs() <= eps
+            && self.real.dot(&self.dual).abs() <= eps
+    }
+
+    pub fn normalized(&self) -> Option<Self>
+    where
+        T: Default + Real,
+    {
+        let len = self.real.length();
+
+        if len <= T::epsilon() {
+            return None;
+        }
+
+        let real = self.real * (T::one() / len);
+        let dual = self.dual * (T::one() / len);
+
+        // Drop the component of the dual part that isn't orthogonal to the real one
+        Some(Self::new(real, dual - real * real.dot(&dual)))
+    }
+
+    pub fn normalize(&mut self)
+    where
+        T: Default + Real,
+    {
+        if let Some(normalized) = self.normalized() {
+            *self = normalized;
+        }
+    }
+
+    pub fn transform_point(&self, point: Vector3<T>) -> Vector3<T>
+    where
+        T: Real,
+    {
+        self.real.rotate(point) + self.translation()
+    }
+
+    pub fn transform_vector(&self, vector: Vector3<T>) -> Vector3<T>
+    where
+        T: Real,
+    {
+        self.real.rotate(vector)
+    }
+
+    // Screw parameters (angle, pitch, direction, moment) of a unit dual quaternion,
+    // None for pure translations where the screw axis is undefined
+    fn screw(&self) -> Option<(T, T, Vector3<T>, Vector3<T>)>
+    where
+        T: Real,
+    {
+        let two = T::one() + T::one();
+        let sin_half = self.real.vector().length();
+
+        if sin_half <= T::epsilon().sqrt() {
+            return None;
+        }
+
+        let cos_half = self.real.w();
+        let angle = two * sin_half.atan2(cos_half);
+        let direction = self.real.vector() / sin_half;
+        let pitch = -two * self.dual.w() / sin_half;
+        let moment = (self.dual.vector() - direction * (pitch * cos_half / two)) / sin_half;
+
+        Some((angle, pitch, direction, moment))
+    }
+
+    pub fn pow(&self, exponent: T) -> Self
+    where
+        T: Real,
+    {
+        let two = T::one() + T::one();
+
+        let Some((angle, pitch, direction, moment)) = self.screw() else {
+            return Self::new(self.real, self.dual * exponent);
+        };
+
+        let (angle, pitch) = (angle * exponent, pitch * exponent);
+        let (sin_half, cos_half) = (angle / two).sin_cos();
+
+        Self::new(
+            Quaternion::from_parts(direction * sin_half, cos_half),
+            Quaternion::from_parts(
+                moment * sin_half + direction * (pitch / two * cos_half),
+                -pitch / two * sin_half,
+            ),
+        )
+    }
+
+    // Screw linear interpolation between two unit dual quaternions, always taking the shortest path
+    pub fn sclerp(&self, other: &Self, t: T) -> Self
+    where
+        T: Default + Real,
+    {
+        let other = if self.real.dot(&other.real) < T::zero() {
+            -*other
+        } else {
+            *other
+        };
+
+        *self * (self.conjugate() * other).pow(t)
+    }
+
+    // Dual quaternion linear blending (Kavan et al.), None when the weighted sum degenerates
+    pub fn blend<I>(weighted: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = (Self, T)>,
+        T: Default + Real,
+    {
+        let mut iter = weighted.into_iter();
+        let (pivot, weight) = iter.next()?;
+
+        iter.fold(pivot * weight, |sum, (dq, weight)| {
+            // Keep every rotation in the same hemisphere as the first one
+            let weight = if pivot.real.dot(&dq.real) < T::zero() {
+                -weight
+            } else {
+                weight
+            };
+
+            sum + dq * weight
+        })
+        .normalized()
+    }
+}
+
+mod ops {
+    use super::*;
+
+    use std::ops::{Add, Mul, Neg, Sub};
+
+    impl<T> Mul for DualQuaternion<T>
+    where
+        T: Real,
+    {
+        type Output = DualQuaternion<T>;
+
+        fn mul(self, rhs: DualQuaternion<T>) -> Self::Output {
+            DualQuaternion::new(
+                self.real * rhs.real,
+                self.real * rhs.dual + self.dual * rhs.real,
+            )
+        }
+    }
+
+    impl<'b, T> Mul<&'b DualQuaternion<T>> for &DualQuaternion<T>
+    where
+        T: Real,
+    {
+        type Output = DualQuaternion<T>;
+
+        fn mul(self, rhs: &'b DualQuaternion<T>) -> Self::Output {
+            (*self).mul(*rhs)
+        }
+    }
+
+    impl<T> Mul<T> for DualQuaternion<T>
+    where
+        T: Copy + Mul<Output = T>,
+    {
+        type Output = DualQuaternion<T>;
+
+        fn mul(self, rhs: T) -> Self::Output {
+            DualQuaternion::new(self.real * rhs, self.dual * rhs)
+        }
+    }
+
+    impl<T> Add for DualQuaternion<T>
+    where
+        T: Copy + Add<Output = T>,
+    {
+        type Output = DualQuaternion<T>;
+
+        fn add(self, rhs: DualQuaternion<T>) -> Self::Output {
+            DualQuaternion::new(self.real + rhs.real, self.dual + rhs.dual)
+        }
+    }
+
+    impl<T> Sub for DualQuaternion<T>
+    where
+        T: Copy + Sub<Output = T>,
+    {
+        type Output = DualQuaternion<T>;
+
+        fn sub(self, rhs: DualQuaternion<T>) -> Self::Output {
+            DualQuaternion::new(self.real - rhs.real, self.dual - rhs.dual)
+        }
+    }
+
+    impl<T> Neg for DualQuaternion<T>
+    where
+        T: Neg<Output = T>,
+    {
+        type Output = DualQuaternion<T>;
+
+        fn neg(self) -> Self::Output {
+            DualQuaternion::new(-self.real, -self.dual)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::f64::consts::FRAC_PI_2;
+
+    use crate::{test_util::assert_close, vector};
+
+    fn quarter_turn_z() -> Quaternion<f64> {
+        Quaternion::from_axis_angle(vector!(0.0, 0.0, 1.0), FRAC_PI_2)
+    }
+
+    #[test]
+    fn rotation_translation() {
+        let dq =
+            DualQuaternionD::from_rotation_translation(quarter_turn_z(), vector!(1.0, 2.0, 3.0));
+
+        assert_close(dq.translation(), vector!(1.0, 2.0, 3.0), 1e-9);
+        assert_close(
+            dq.transform_point(vector!(1.0, 0.0, 0.0)),
+            vector!(1.0, 3.0, 3.0),
+            1e-9,
+        );
+        assert_close(
+            dq.transform_vector(vector!(1.0, 0.0, 0.0)),
+            vector!(0.0, 1.0, 0.0),
+            1e-9,
+        );
+        assert!(dq.is_unit());
+    }
+
+    #[test]
+    fn composition() {
+        let a =
+            DualQuaternionD::from_rotation_translation(quarter_turn_z(), vector!(1.0, 0.0, 0.0));
+        let b = DualQuaternionD::from_rotation_translation(
+            Quaternion::from_axis_angle(vector!(1.0, 1.0, 0.0), 0.7),
+            vector!(0.0, -2.0, 0.5),
+        );
+        let p = vector!(0.3, -1.0, 2.0);
+
+        assert_close(
+            (a * b).transform_point(p),
+            a.transform_point(b.transform_point(p)),
+            1e-9,
+        );
+        assert_close((a * a.conjugate()).transform_point(p), p, 1e-9);
+        assert_close((b * b.inverse().unwrap()).transform_point(p), p, 1e-9);
+    }
+
+    #[test]
+    fn normalization() {
+        let dq =
+            DualQuaternionD::from_rotation_translation(quarter_turn_z(), vector!(1.0, 2.0, 3.0));
+        let scaled = dq * 3.0;
+
+        assert!(!scaled.is_unit());
+
+        let normalized = scaled.normalized().unwrap();
+        assert!(normalized.is_unit());
+        assert_close(normalized.translation(), vector!(1.0, 2.0, 3.0), 1e-9);
+    }
+
+    #[test]
+    fn sclerp() {
+        let start = DualQuaternionD::identity();
+        let end =
+            DualQuaternionD::from_rotation_translation(quarter_turn_z(), vector!(0.0, 0.0, 4.0));
+
+        assert_close(
+            start
+                .sclerp(&end, 0.0)
+                .transform_point(vector!(1.0, 0.0, 0.0)),
+            vector!(1.0, 0.0, 0.0),
+            1e-9,
+        );
+        assert_close(
+            start
+                .sclerp(&end, 1.0)
+                .transform_point(vector!(1.0, 0.0, 0.0)),
+            vector!(0.0, 1.0, 4.0),
+            1e-9,
+        );
+
+        // Screw motion around and along z: half the angle and half the translation
+        let half = std::f64::consts::FRAC_1_SQRT_2;
+        let mid = start.sclerp(&end, 0.5);
+
+        assert_close(
+            mid.transform_point(vector!(1.0, 0.0, 0.0)),
+            vector!(half, half, 2.0),
+            1e-9,
+        );
+        assert!(mid.is_unit());
+
+        // Pure translations interpolate linearly
+        let moved = DualQuaternionD::from_translation(vector!(2.0, 0.0, 0.0));
+        assert_close(
+            start.sclerp(&moved, 0.25).translation(),
+            vector!(0.5, 0.0, 0.0),
+            1e-9,
+        );
+    }
+
+    #[test]
+    fn blend() {
+        let a =
+            DualQuaternionD::from_rotation_translation(quarter_turn_z(), vector!(1.0, 0.0, 0.0));
+        let b = DualQuaternionD::from_translation(vector!(3.0, 0.0, 0.0));
+
+        let same = DualQuaternion::blend([(a, 0.5), (-a, 0.5)]).unwrap();
+        assert_close(
+            same.transform_point(vector!(1.0, 1.0, 1.0)),
+            a.transform_point(vector!(1.0, 1.0, 1.0)),
+            1e-9,
+        );
+
+        let only_b = DualQuaternion::blend([(a, 0.0), (b, 2.0)]).unwrap();
+        assert_close(only_b.translation(), vector!(3.0, 0.0, 0.0), 1e-9);
+
+        let mixed = DualQuaternion::blend([(a, 0.5), (b, 0.5)]).unwrap();
+        assert!(mixed.is_unit());
+
+        assert!(DualQuaternionD::blend([]).is_none());
+    }
+}
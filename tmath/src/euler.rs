@@ -0,0 +1,359 @@
+use num_traits::real::Real;
+
+use crate::{
+    matrix::Matrix3,
+    quaternion::Quaternion,
+    vector::{Vector, Vector3},
+};
+
+pub type EulerAnglesF = EulerAngles<f32>;
+pub type EulerAnglesD = EulerAngles<f64>;
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EulerOrder {
+    #[default]
+    XYZ,
+    XZY,
+    YXZ,
+    YZX,
+    ZXY,
+    ZYX,
+    XYX,
+    XZX,
+    YXY,
+    YZY,
+    ZXZ,
+    ZYZ,
+}
+
+impl EulerOrder {
+    pub const ALL: [EulerOrder; 12] = [
+        EulerOrder::XYZ,
+        EulerOrder::XZY,
+        EulerOrder::YXZ,
+        EulerOrder::YZX,
+        EulerOrder::ZXY,
+        EulerOrder::ZYX,
+        EulerOrder::XYX,
+        EulerOrder::XZX,
+        EulerOrder::YXY,
+        EulerOrder::YZY,
+        EulerOrder::ZXZ,
+        EulerOrder::ZYZ,
+    ];
+
+    pub const fn axes(&self) -> [usize; 3] {
+        match self {
+            EulerOrder::XYZ => [0, 1, 2],
+            EulerOrder::XZY => [0, 2, 1],
+            EulerOrder::YXZ => [1, 0, 2],
+            EulerOrder::YZX => [1, 2, 0],
+            EulerOrder::ZXY => [2, 0, 1],
+            EulerOrder::ZYX => [2, 1, 0],
+            EulerOrder::XYX => [0, 1, 0],
+            EulerOrder::XZX => [0, 2, 0],
+            EulerOrder::YXY => [1, 0, 1],
+            EulerOrder::YZY => [1, 2, 1],
+            EulerOrder::ZXZ => [2, 0, 2],
+            EulerOrder::ZYZ => [2, 1, 2],
+        }
+    }
+
+    // Proper Euler orders repeat their first axis, Tait-Bryan orders use all three
+    pub const fn is_proper(&self) -> bool {
+        let axes = self.axes();
+
+        axes[0] == axes[2]
+    }
+}
+
+// Intrinsic rotations are applied about the rotating body axes, extrinsic ones about the fixed world axes
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EulerFrame {
+    #[default]
+    Intrinsic,
+    Extrinsic,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct EulerAngles<T> {
+    pub angles: Vector3<T>,
+    pub order: EulerOrder,
+    pub frame: EulerFrame,
+}
+
+impl<T> EulerAngles<T> {
+    pub const fn new(angles: Vector3<T>, order: EulerOrder, frame: EulerFrame) -> Self {
+        Self {
+            angles,
+            order,
+            frame,
+        }
+    }
+
+    pub const fn intrinsic(angles: Vector3<T>, order: EulerOrder) -> Self {
+        Self::new(angles, order, EulerFrame::Intrinsic)
+    }
+
+    pub const fn extrinsic(angles: Vector3<T>, order: EulerOrder) -> Self {
+        Self::new(angles, order, EulerFrame::Extrinsic)
+    }
+
+    // Elementary rotations in the order they are multiplied together (leftmost first)
+    fn elementary(&self) -> [(usize, T); 3]
+    where
+        T: Copy,
+    {
+        let axes = self.order.axes();
+        let rotations = [
+            (axes[0], self.angles[0]),
+            (axes[1], self.angles[1]),
+            (axes[2], self.angles[2]),
+        ];
+
+        match self.frame {
+            EulerFrame::Intrinsic => rotations,
+            EulerFrame::Extrinsic => [rotations[2], rotations[1], rotations[0]],
+        }
+    }
+
+    pub fn to_rotation_matrix(&self) -> Matrix3<T>
+    where
+        T: Default + Real,
+    {
+        self.elementary()
+            .iter()
+            .map(|&(axis, angle)| match axis {
+                0 => Matrix3::rotation_x(angle),
+                1 => Matrix3::rotation_y(angle),
+                _ => Matrix3::rotation_z(angle),
+            })
+            .fold(Matrix3::identity(), |res, m| res * m)
+    }
+
+    pub fn to_quaternion(&self) -> Quaternion<T>
+    where
+        T: Default + Real,
+    {
+        self.elementary()
+            .iter()
+            .map(|&(axis, angle)| {
+                let mut basis = Vector::new_val(T::zero());
+                basis[axis] = T::one();
+
+                Quaternion::from_axis_angle(basis, angle)
+            })
+            .fold(Quaternion::identity(), |res, q| res * q)
+    }
+
+    pub fn from_rotation_matrix(m: &Matrix3<T>, order: EulerOrder, frame: EulerFrame) -> Self
+    where
+        T: Default + Real,
+    {
+        Self::from_quaternion(&Quaternion::from_rotation_matrix(m), order, frame)
+    }
+
+    // Bernardes & Viollet, "Quaternion to Euler angles conversion: A direct, general and
+    // computationally efficient method" (2022). On gimbal lock the third angle is set to zero
+    // and the whole rotation about the locked axis is carried by the first one.
+    pub fn from_quaternion(q: &Quaternion<T>, order: EulerOrder, frame: EulerFrame) -> Self
+    where
+        T: Default + Real,
+    {
+        let one = T::one();
+        let two = one + one;
+        let pi = T::from(std::f64::consts::PI).unwrap();
+
+        let extrinsic = frame == EulerFrame::Extrinsic;
+        let proper = order.is_proper();
+
+        let [mut i, j, mut k] = order.axes();
+        if !extrinsic {
+            std::mem::swap(&mut i, &mut k);
+        }
+        if proper {
+            k = 3 - i - j;
+        }
+
+        // +1 for even (cyclic) axis permutations, -1 for odd ones
+        let sign = if (i + 1) % 3 == j { one } else { -one };
+
+        let q = q.as_vector();
+        let w = q[3];
+        let (a, b, c, d) = if proper {
+            (w, q[i], q[j], q[k] * sign)
+        } else {
+            (w - q[j], q[i] + q[k] * sign, q[j] + w, q[k] * sign - q[i])
+        };
+
+        let mut angles = [T::zero(); 3];
+        angles[1] = two * c.hypot(d).atan2(a.hypot(b));
+
+        let half_sum = b.atan2(a);
+        let half_diff = d.atan2(c);
+        let eps = T::epsilon().sqrt();
+
+        // Index of the free angle before intrinsic angles get reversed back, the other one is zeroed
+        let free = if extrinsic { 0 } else { 2 };
+
+        if angles[1].abs() <= eps {
+            angles[free] = two * half_sum;
+        } else if (angles[1] - pi).abs() <= eps {
+            angles[free] = if extrinsic {
+                -two * half_diff
+            } else {
+                two * half_diff
+            };
+        } else {
+            angles[0] = half_sum - half_diff;
+            angles[2] = half_sum + half_diff;
+        }
+
+        if !proper {
+            angles[2] = angles[2] * sign;
+            angles[1] = angles[1] - pi / two;
+        }
+
+        if !extrinsic {
+            angles.swap(0, 2);
+        }
+
+        let angles = angles.map(|angle| {
+            if angle < -pi {
+                angle + two * pi
+            } else if angle > pi {
+                angle - two * pi
+            } else {
+                angle
+            }
+        });
+
+        Self::new(Vector3::new(angles), order, frame)
+    }
+}
+
+impl<T> From<EulerAngles<T>> for Quaternion<T>
+where
+    T: Default + Real,
+{
+    fn from(euler: EulerAngles<T>) -> Self {
+        euler.to_quaternion()
+    }
+}
+
+impl<T> From<EulerAngles<T>> for Matrix3<T>
+where
+    T: Default + Real,
+{
+    fn from(euler: EulerAngles<T>) -> Self {
+        euler.to_rotation_matrix()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    use crate::vector;
+
+    const FRAMES: [EulerFrame; 2] = [EulerFrame::Intrinsic, EulerFrame::Extrinsic];
+
+    fn assert_same_rotation(a: &Matrix3<f64>, b: &Matrix3<f64>) {
+        for r in 0..3 {
+            assert!((a[r] - b[r]).length() < 1e-9, "{:?} != {:?}", a, b);
+        }
+    }
+
+    fn sample_angles(order: EulerOrder) -> Vec<Vector3<f64>> {
+        // Middle angle spans 0..pi for proper orders and -pi/2..pi/2 for Tait-Bryan ones
+        let middle = if order.is_proper() {
+            [0.4, 1.3, 2.9]
+        } else {
+            [-1.2, 0.1, 1.4]
+        };
+
+        [(0.3, -2.1), (-3.0, 0.7), (1.9, 2.5)]
+            .iter()
+            .zip(middle)
+            .map(|(&(first, last), middle)| vector!(first, middle, last))
+            .collect()
+    }
+
+    #[test]
+    fn elementary_order() {
+        let angles = vector!(0.3, -0.5, 1.2);
+        let intrinsic = EulerAngles::intrinsic(angles, EulerOrder::ZYX).to_rotation_matrix();
+        let target =
+            Matrix3::rotation_z(0.3) * Matrix3::rotation_y(-0.5) * Matrix3::rotation_x(1.2);
+
+        assert_same_rotation(&intrinsic, &target);
+
+        // Extrinsic XYZ is the same rotation as intrinsic ZYX with reversed angles
+        let extrinsic =
+            EulerAngles::extrinsic(vector!(1.2, -0.5, 0.3), EulerOrder::XYZ).to_rotation_matrix();
+
+        assert_same_rotation(&extrinsic, &target);
+    }
+
+    #[test]
+    fn quaternion_matches_matrix() {
+        for order in EulerOrder::ALL {
+            for frame in FRAMES {
+                for angles in sample_angles(order) {
+                    let euler = EulerAngles::new(angles, order, frame);
+
+                    assert_same_rotation(
+                        &euler.to_quaternion().to_rotation_matrix(),
+                        &euler.to_rotation_matrix(),
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for order in EulerOrder::ALL {
+            for frame in FRAMES {
+                for angles in sample_angles(order) {
+                    let m = EulerAngles::new(angles, order, frame).to_rotation_matrix();
+                    let euler = EulerAngles::from_rotation_matrix(&m, order, frame);
+
+                    assert!(
+                        (euler.angles - angles).length() < 1e-9,
+                        "{:?} {:?}: {:?} != {:?}",
+                        order,
+                        frame,
+                        euler.angles,
+                        angles
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn gimbal_lock() {
+        for order in EulerOrder::ALL {
+            let locked = if order.is_proper() {
+                [0.0, PI]
+            } else {
+                [FRAC_PI_2, -FRAC_PI_2]
+            };
+
+            for frame in FRAMES {
+                for middle in locked {
+                    let m = EulerAngles::new(vector!(0.7, middle, -0.4), order, frame)
+                        .to_rotation_matrix();
+                    let euler = EulerAngles::from_rotation_matrix(&m, order, frame);
+
+                    assert_same_rotation(&euler.to_rotation_matrix(), &m);
+                    assert_eq!(euler.angles[2], 0.0);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,309 @@
+use num_traits::real::Real;
+
+use crate::{curve::Curve, vector::Vector3};
+
+pub type FrameF = Frame<f32>;
+pub type FrameD = Frame<f64>;
+
+// Orthonormal and right handed, tangent × normal = binormal
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Frame<T> {
+    pub point: Vector3<T>,
+    pub tangent: Vector3<T>,
+    pub normal: Vector3<T>,
+    pub binormal: Vector3<T>,
+}
+
+// How sharply the curve bends, the inverse of the radius of the circle best fitting it at t
+pub fn curvature<T, C>(curve: &C, t: T) -> T
+where
+    T: Default + Real,
+    C: Curve<3, T>,
+{
+    let d1 = curve.derivative(t);
+    let speed = d1.length();
+
+    if speed > T::zero() {
+        d1.cross(&curve.second_derivative(t)).length() / (speed * speed * speed)
+    } else {
+        T::zero()
+    }
+}
+
+// How fast the curve twists out of its plane of bending, zero where it doesn't bend
+pub fn torsion<T, C>(curve: &C, t: T) -> T
+where
+    T: Default + Real,
+    C: Curve<3, T>,
+{
+    let bend = curve.derivative(t).cross(&curve.second_derivative(t));
+    let length_squared = bend.length_squared();
+
+    if length_squared > T::zero() {
+        bend.dot(&curve.third_derivative(t)) / length_squared
+    } else {
+        T::zero()
+    }
+}
+
+// Frenet-Serret frame, with the normal towards the center of curvature. None where the curve stops
+// or runs straight, as the normal isn't defined there
+pub fn frenet_frame<T, C>(curve: &C, t: T) -> Option<Frame<T>>
+where
+    T: Default + Real,
+    C: Curve<3, T>,
+{
+    let (d1, d2) = (curve.derivative(t), curve.second_derivative(t));
+    let bend = d1.cross(&d2);
+
+    if bend.length() <= T::epsilon().sqrt() * d1.length() * d2.length() {
+        return None;
+    }
+
+    let tangent = d1.normalized();
+    let binormal = bend.normalized();
+
+    Some(Frame {
+        point: curve.point(t),
+        tangent,
+        normal: binormal.cross(&tangent),
+        binormal,
+    })
+}
+
+pub fn frenet_frames<T, C, I>(curve: &C, parameters: I) -> Vec<Option<Frame<T>>>
+where
+    T: Default + Real,
+    C: Curve<3, T>,
+    I: IntoIterator<Item = T>,
+{
+    parameters
+        .into_iter()
+        .map(|t| frenet_frame(curve, t))
+        .collect()
+}
+
+// Some unit vector at a right angle to the unit vector v
+fn perpendicular<T>(v: &Vector3<T>) -> Vector3<T>
+where
+    T: Default + Real,
+{
+    let axis = if v[0].abs() <= v[1].abs() && v[0].abs() <= v[2].abs() {
+        Vector3::new([T::one(), T::zero(), T::zero()])
+    } else if v[1].abs() <= v[2].abs() {
+        Vector3::new([T::zero(), T::one(), T::zero()])
+    } else {
+        Vector3::new([T::zero(), T::zero(), T::one()])
+    };
+
+    v.cross(&axis).normalized()
+}
+
+// Frames that turn as little as possible around the tangent from one parameter to the next, so
+// tubes built from them don't twist the way Frenet frames do, and exist along straight parts too.
+// The first normal is up made perpendicular to the tangent. Double reflection method from Wang
+// et al. 2008, "Computation of rotation minimizing frames"
+pub fn rotation_minimizing_frames<T, C, I>(
+    curve: &C,
+    parameters: I,
+    up: Vector3<T>,
+) -> Vec<Frame<T>>
+where
+    T: Default + Real,
+    C: Curve<3, T>,
+    I: IntoIterator<Item = T>,
+{
+    let two = T::one() + T::one();
+    let mut frames: Vec<Frame<T>> = Vec::new();
+
+    for t in parameters {
+        let point = curve.point(t);
+        let d1 = curve.derivative(t);
+
+        // Where the curve stops the direction it's heading in is kept
+        let tangent = if d1.length() > T::zero() {
+            d1.normalized()
+        } else {
+            frames.last().map_or_else(
+                || curve.derivative(t + T::epsilon().sqrt()).normalized(),
+                |previous| previous.tangent,
+            )
+        };
+
+        let normal = match frames.last() {
+            None => {
+                let normal = up - tangent * up.dot(&tangent);
+
+                if normal.length() > T::epsilon().sqrt() * up.length() {
+                    normal.normalized()
+                } else {
+                    perpendicular(&tangent)
+                }
+            }
+            Some(previous) => {
+                // Reflect across the plane between the two points, then across the one between the
+                // reflected tangent and the new one
+                let v1 = point - previous.point;
+                let c1 = v1.dot(&v1);
+                let (reflected_normal, reflected_tangent) = if c1 > T::zero() {
+                    (
+                        previous.normal - v1 * (two * v1.dot(&previous.normal) / c1),
+                        previous.tangent - v1 * (two * v1.dot(&previous.tangent) / c1),
+                    )
+                } else {
+                    (previous.normal, previous.tangent)
+                };
+
+                let v2 = tangent - reflected_tangent;
+                let c2 = v2.dot(&v2);
+                let normal = if c2 > T::zero() {
+                    reflected_normal - v2 * (two * v2.dot(&reflected_normal) / c2)
+                } else {
+                    reflected_normal
+                };
+
+                // Keeps rounding from building up
+                (normal - tangent * normal.dot(&tangent)).normalized()
+            }
+        };
+
+        frames.push(Frame {
+            point,
+            tangent,
+            normal,
+            binormal: tangent.cross(&normal),
+        });
+    }
+
+    frames
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{bezier::CubicBezier3D, vector};
+
+    // (a cos t, a sin t, b t)
+    struct Helix {
+        a: f64,
+        b: f64,
+    }
+
+    impl Curve<3, f64> for Helix {
+        fn domain(&self) -> (f64, f64) {
+            (0.0, 10.0)
+        }
+
+        fn point(&self, t: f64) -> Vector3<f64> {
+            vector!(self.a * t.cos(), self.a * t.sin(), self.b * t)
+        }
+
+        fn derivative(&self, t: f64) -> Vector3<f64> {
+            vector!(-self.a * t.sin(), self.a * t.cos(), self.b)
+        }
+
+        fn second_derivative(&self, t: f64) -> Vector3<f64> {
+            vector!(-self.a * t.cos(), -self.a * t.sin(), 0.0)
+        }
+
+        fn third_derivative(&self, t: f64) -> Vector3<f64> {
+            vector!(self.a * t.sin(), -self.a * t.cos(), 0.0)
+        }
+    }
+
+    fn assert_orthonormal(frame: &Frame<f64>) {
+        for v in [frame.tangent, frame.normal, frame.binormal] {
+            assert!((v.length() - 1.0).abs() < 1e-9);
+        }
+        assert!(frame.tangent.dot(&frame.normal).abs() < 1e-9);
+        assert!((frame.tangent.cross(&frame.normal) - frame.binormal).length() < 1e-9);
+    }
+
+    #[test]
+    fn helix() {
+        let helix = Helix { a: 2.0, b: 0.5 };
+        let denom = 2.0 * 2.0 + 0.5 * 0.5;
+
+        for t in [0.0, 1.3, 4.0] {
+            assert!((curvature(&helix, t) - 2.0 / denom).abs() < 1e-12);
+            assert!((torsion(&helix, t) - 0.5 / denom).abs() < 1e-12);
+
+            // The normal points at the axis
+            let frame = frenet_frame(&helix, t).unwrap();
+            assert_orthonormal(&frame);
+            assert!((frame.normal - vector!(-t.cos(), -t.sin(), 0.0)).length() < 1e-12);
+        }
+
+        let parameters: Vec<_> = (0..=1000).map(|i| i as f64 / 100.0).collect();
+        let frenet = frenet_frames(&helix, parameters.iter().copied());
+        let rmf =
+            rotation_minimizing_frames(&helix, parameters.iter().copied(), vector!(0.0, 0.0, 1.0));
+        assert_eq!(rmf.len(), 1001);
+
+        // The first normal leans towards up
+        assert_orthonormal(&rmf[0]);
+        assert!(rmf[0].normal[2] > 0.0);
+        assert!(rmf[0].binormal[2].abs() < 1e-12);
+
+        // Frenet frames spin around the tangent at the torsion, rotation minimizing ones don't
+        for i in 0..1000 {
+            let (f0, f1) = (frenet[i].unwrap(), frenet[i + 1].unwrap());
+            let (r0, r1) = (rmf[i], rmf[i + 1]);
+            assert_orthonormal(&r1);
+            assert!((r1.tangent - f1.tangent).length() < 1e-12);
+
+            let ds = (f1.point - f0.point).length();
+            assert!(((f1.normal - f0.normal).dot(&f0.binormal) / ds - 0.5 / denom).abs() < 1e-3);
+            assert!(
+                (r1.normal - r0.normal)
+                    .dot(&(r0.binormal + r1.binormal))
+                    .abs()
+                    / ds
+                    < 1e-4
+            );
+        }
+    }
+
+    #[test]
+    fn straight() {
+        let line = CubicBezier3D::new([
+            vector!(0.0, 0.0, 0.0),
+            vector!(1.0, 1.0, 0.0),
+            vector!(2.0, 2.0, 0.0),
+            vector!(3.0, 3.0, 0.0),
+        ]);
+
+        assert_eq!(curvature(&line, 0.5), 0.0);
+        assert_eq!(torsion(&line, 0.5), 0.0);
+        assert!(frenet_frame(&line, 0.5).is_none());
+
+        // Up along the tangent falls back to any normal
+        let frames = rotation_minimizing_frames(
+            &line,
+            (0..=10).map(|i| i as f64 / 10.0),
+            vector!(1.0, 1.0, 0.0),
+        );
+        for frame in &frames {
+            assert_orthonormal(frame);
+            assert!((frame.normal - frames[0].normal).length() < 1e-12);
+        }
+
+        // Bends after the straight part keep the normal turning smoothly
+        let bend = CubicBezier3D::new([
+            vector!(0.0, 0.0, 0.0),
+            vector!(0.0, 0.0, 2.0),
+            vector!(2.0, 0.0, 2.0),
+            vector!(2.0, 3.0, 4.0),
+        ]);
+        let frames = rotation_minimizing_frames(
+            &bend,
+            (0..=200).map(|i| i as f64 / 200.0),
+            vector!(0.0, 1.0, 0.0),
+        );
+        for w in frames.windows(2) {
+            assert_orthonormal(&w[1]);
+            assert!((w[1].normal - w[0].normal).length() < 0.1);
+        }
+    }
+}
@@ -0,0 +1,338 @@
+use num_traits::real::Real;
+
+use crate::{
+    aabb::Aabb3,
+    matrix::Matrix4,
+    shapes::{Plane, Sphere3},
+    vector::{Vector3, Vector4},
+};
+
+pub type FrustumF = Frustum<f32>;
+pub type FrustumD = Frustum<f64>;
+
+// Clip space depth range of the projection, -1..1 for OpenGL, 0..1 for Direct3D, Vulkan and Metal
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum DepthRange {
+    #[default]
+    NegativeOneToOne,
+    ZeroToOne,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Containment {
+    Outside,
+    Intersecting,
+    Inside,
+}
+
+// Planes face outwards, in the order left, right, bottom, top, near, far. A depth plane at infinity,
+// as with infinite far or reversed infinite projections, has a zero normal and an infinite distance
+// so that everything is inside of it
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Frustum<T> {
+    pub planes: [Plane<T>; 6],
+}
+
+impl<T> Frustum<T> {
+    pub const fn new(planes: [Plane<T>; 6]) -> Self {
+        Self { planes }
+    }
+
+    // Gribb/Hartmann plane extraction
+    pub fn from_view_projection(view_proj: &Matrix4<T>, depth: DepthRange) -> Self
+    where
+        T: Default + Real,
+    {
+        let [r0, r1, r2, r3] = [0, 1, 2, 3].map(|i| view_proj[i]);
+        let near = match depth {
+            DepthRange::NegativeOneToOne => r3 + r2,
+            DepthRange::ZeroToOne => r2,
+        };
+
+        // Each row combination is ax + by + cz + d >= 0 on the inside
+        let plane = |inside: Vector4<T>| {
+            let normal = -Vector3::from(inside);
+            let len = normal.length();
+
+            if len <= T::epsilon() * inside.length() {
+                Plane::new(Vector3::default(), infinity())
+            } else {
+                Plane::new(normal / len, inside.w() / len)
+            }
+        };
+
+        Self::new([
+            plane(r3 + r0),
+            plane(r3 - r0),
+            plane(r3 + r1),
+            plane(r3 - r1),
+            plane(near),
+            plane(r3 - r2),
+        ])
+    }
+
+    pub fn contains_point(&self, point: &Vector3<T>) -> bool
+    where
+        T: Default + Real,
+    {
+        self.planes
+            .iter()
+            .all(|plane| is_unbounded(plane) || plane.contains(point))
+    }
+
+    pub fn intersects_sphere(&self, sphere: &Sphere3<T>) -> Containment
+    where
+        T: Default + Real,
+    {
+        self.classify(|plane| (plane.signed_distance(&sphere.center), sphere.radius))
+    }
+
+    pub fn intersects_aabb(&self, aabb: &Aabb3<T>) -> Containment
+    where
+        T: Default + Real,
+    {
+        let center = aabb.center();
+        let half_extents = aabb.half_extents();
+
+        self.classify(|plane| {
+            let normal = Vector3::new(plane.normal.0.map(|val| val.abs()));
+
+            (plane.signed_distance(&center), half_extents.dot(&normal))
+        })
+    }
+
+    // Shapes given as a signed distance of their center and radius along each plane
+    fn classify<F>(&self, extent: F) -> Containment
+    where
+        T: Real,
+        F: Fn(&Plane<T>) -> (T, T),
+    {
+        let mut res = Containment::Inside;
+
+        for plane in self.planes.iter().filter(|plane| !is_unbounded(plane)) {
+            let (distance, radius) = extent(plane);
+
+            if distance > radius {
+                return Containment::Outside;
+            }
+            if distance > -radius {
+                res = Containment::Intersecting;
+            }
+        }
+
+        res
+    }
+
+    // Same bit order as Aabb3::corners, x picks left/right, y bottom/top and z near/far. Corners on
+    // a depth plane at infinity are infinite along the edges running out to them
+    pub fn corners(&self) -> [Vector3<T>; 8]
+    where
+        T: Default + Real,
+    {
+        std::array::from_fn(|i| {
+            let [x, y, z] = [0, 1, 2].map(|axis| (i >> axis) & 1);
+            let (a, b) = (&self.planes[x], &self.planes[2 + y]);
+            let (depth, opposite) = (&self.planes[4 + z], &self.planes[5 - z]);
+
+            if !is_unbounded(depth) {
+                return intersect_planes(a, b, depth);
+            }
+
+            // Along the edge between the side planes, away from the other depth plane
+            let start = intersect_planes(a, b, opposite);
+            let mut direction = a.normal.cross(&b.normal);
+            if direction.dot(&opposite.normal) > T::zero() {
+                direction = -direction;
+            }
+
+            Vector3::new(std::array::from_fn(|axis| {
+                if direction[axis] == T::zero() {
+                    start[axis]
+                } else {
+                    direction[axis].signum() * infinity()
+                }
+            }))
+        })
+    }
+}
+
+fn infinity<T>() -> T
+where
+    T: Real,
+{
+    T::from(f64::INFINITY).unwrap()
+}
+
+fn is_unbounded<T>(plane: &Plane<T>) -> bool
+where
+    T: Real,
+{
+    plane.distance == infinity()
+}
+
+fn intersect_planes<T>(a: &Plane<T>, b: &Plane<T>, c: &Plane<T>) -> Vector3<T>
+where
+    T: Default + Real,
+{
+    let bc = b.normal.cross(&c.normal);
+    let ca = c.normal.cross(&a.normal);
+    let ab = a.normal.cross(&b.normal);
+
+    (bc * a.distance + ca * b.distance + ab * c.distance) / a.normal.dot(&bc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{matrix, test_util::assert_close, vector};
+
+    // 90° vertical field of view, square aspect, near 1, far 10, looking down -z
+    fn projection(depth: DepthRange) -> Matrix4<f64> {
+        let (n, f) = (1.0, 10.0);
+        let (z, w) = match depth {
+            DepthRange::NegativeOneToOne => ((f + n) / (n - f), 2.0 * f * n / (n - f)),
+            DepthRange::ZeroToOne => (f / (n - f), f * n / (n - f)),
+        };
+
+        matrix!(
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, z, w],
+            [0.0, 0.0, -1.0, 0.0]
+        )
+    }
+
+    #[test]
+    fn planes() {
+        for depth in [DepthRange::NegativeOneToOne, DepthRange::ZeroToOne] {
+            let frustum = Frustum::from_view_projection(&projection(depth), depth);
+
+            assert_close(frustum.planes[4].normal, vector!(0.0, 0.0, 1.0), 1e-9);
+            assert!((frustum.planes[4].distance + 1.0).abs() < 1e-9);
+            assert!((frustum.planes[5].distance - 10.0).abs() < 1e-9);
+
+            assert!(frustum.contains_point(&vector!(0.0, 0.0, -5.0)));
+            assert!(frustum.contains_point(&vector!(4.9, -4.9, -5.0)));
+            assert!(!frustum.contains_point(&vector!(0.0, 0.0, -0.5)));
+            assert!(!frustum.contains_point(&vector!(0.0, 0.0, -11.0)));
+            assert!(!frustum.contains_point(&vector!(5.1, 0.0, -5.0)));
+        }
+    }
+
+    #[test]
+    fn corners() {
+        let gl = Frustum::from_view_projection(
+            &projection(DepthRange::NegativeOneToOne),
+            DepthRange::NegativeOneToOne,
+        );
+        let dx = Frustum::from_view_projection(
+            &projection(DepthRange::ZeroToOne),
+            DepthRange::ZeroToOne,
+        );
+
+        let corners = gl.corners();
+        assert_close(corners[0], vector!(-1.0, -1.0, -1.0), 1e-9);
+        assert_close(corners[3], vector!(1.0, 1.0, -1.0), 1e-9);
+        assert_close(corners[4], vector!(-10.0, -10.0, -10.0), 1e-9);
+        assert_close(corners[7], vector!(10.0, 10.0, -10.0), 1e-9);
+
+        for (a, b) in corners.into_iter().zip(dx.corners()) {
+            assert_close(a, b, 1e-9);
+        }
+    }
+
+    #[test]
+    fn culling() {
+        let frustum = Frustum::from_view_projection(
+            &projection(DepthRange::NegativeOneToOne),
+            DepthRange::NegativeOneToOne,
+        );
+
+        let sphere = |x, z, r| Sphere3::new(vector!(x, 0.0, z), r);
+        assert_eq!(
+            frustum.intersects_sphere(&sphere(0.0, -5.0, 1.0)),
+            Containment::Inside
+        );
+        assert_eq!(
+            frustum.intersects_sphere(&sphere(5.0, -5.0, 1.0)),
+            Containment::Intersecting
+        );
+        assert_eq!(
+            frustum.intersects_sphere(&sphere(8.0, -5.0, 1.0)),
+            Containment::Outside
+        );
+        assert_eq!(
+            frustum.intersects_sphere(&sphere(0.0, 1.0, 1.0)),
+            Containment::Outside
+        );
+
+        let aabb = |min: Vector3<f64>, max| Aabb3::new(min, max);
+        assert_eq!(
+            frustum.intersects_aabb(&aabb(vector!(-1.0, -1.0, -6.0), vector!(1.0, 1.0, -4.0))),
+            Containment::Inside
+        );
+        assert_eq!(
+            frustum.intersects_aabb(&aabb(vector!(-1.0, -1.0, -12.0), vector!(1.0, 1.0, -8.0))),
+            Containment::Intersecting
+        );
+        assert_eq!(
+            frustum.intersects_aabb(&aabb(vector!(6.0, -1.0, -5.0), vector!(7.0, 1.0, -4.0))),
+            Containment::Outside
+        );
+        assert_eq!(
+            frustum.intersects_aabb(&aabb(vector!(-1.0, -1.0, 0.0), vector!(1.0, 1.0, 2.0))),
+            Containment::Outside
+        );
+    }
+
+    #[test]
+    fn infinite() {
+        // Same as projection() but with the far plane at infinity, and reversed with depth going
+        // from 1 at the near plane to 0 at infinity
+        let gl = matrix!(
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, -1.0, -2.0],
+            [0.0, 0.0, -1.0, 0.0]
+        );
+        let reversed = matrix!(
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+            [0.0, 0.0, -1.0, 0.0]
+        );
+
+        for frustum in [
+            Frustum::from_view_projection(&gl, DepthRange::NegativeOneToOne),
+            Frustum::from_view_projection(&reversed, DepthRange::ZeroToOne),
+        ] {
+            assert!(frustum.contains_point(&vector!(0.0, 0.0, -5.0)));
+            assert!(frustum.contains_point(&vector!(0.0, 0.0, -1e9)));
+            assert!(!frustum.contains_point(&vector!(0.0, 0.0, -0.5)));
+            assert!(!frustum.contains_point(&vector!(5.1, 0.0, -5.0)));
+
+            assert_eq!(
+                frustum.intersects_sphere(&Sphere3::new(vector!(0.0, 0.0, -1e6), 1.0)),
+                Containment::Inside
+            );
+            assert_eq!(
+                frustum.intersects_aabb(&Aabb3::new(
+                    vector!(-1.0, -1.0, -20.0),
+                    vector!(1.0, 1.0, -2.0)
+                )),
+                Containment::Inside
+            );
+            assert_eq!(
+                frustum.intersects_sphere(&Sphere3::new(vector!(0.0, 0.0, 1.0), 1.0)),
+                Containment::Outside
+            );
+        }
+
+        let corners = Frustum::from_view_projection(&gl, DepthRange::NegativeOneToOne).corners();
+        assert_close(corners[0], vector!(-1.0, -1.0, -1.0), 1e-9);
+        assert_close(corners[3], vector!(1.0, 1.0, -1.0), 1e-9);
+        assert_eq!(corners[4], vector!(-1.0, -1.0, -1.0) * f64::INFINITY);
+        assert_eq!(corners[7], vector!(1.0, 1.0, -1.0) * f64::INFINITY);
+    }
+}
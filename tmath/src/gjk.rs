@@ -0,0 +1,527 @@
+use num_traits::real::Real;
+
+use crate::{obb::Penetration, support_map::SupportMap, vector::Vector};
+
+const GJK_ITERATIONS: usize = 128;
+const EPA_ITERATIONS: usize = 128;
+
+// Closest points between two separated shapes
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Separation<const N: usize, T> {
+    pub distance: T,
+    pub point_a: Vector<N, T>,
+    pub point_b: Vector<N, T>,
+}
+
+// Point of the Minkowski difference a - b, with the shape points it came from
+#[derive(Debug, Copy, Clone)]
+struct SupportPoint<const N: usize, T> {
+    point: Vector<N, T>,
+    a: Vector<N, T>,
+    b: Vector<N, T>,
+}
+
+fn support_point<const N: usize, T, A, B>(
+    a: &A,
+    b: &B,
+    direction: &Vector<N, T>,
+) -> SupportPoint<N, T>
+where
+    T: Real,
+    A: SupportMap<N, T> + ?Sized,
+    B: SupportMap<N, T> + ?Sized,
+{
+    let on_a = a.support(direction);
+    let on_b = b.support(&-*direction);
+
+    SupportPoint {
+        point: on_a - on_b,
+        a: on_a,
+        b: on_b,
+    }
+}
+
+// Relative progress below which the iterations stop. GJK converges fast enough to go near machine
+// precision, EPA on curved shapes would need a huge polytope for that
+fn gjk_tolerance<T>() -> T
+where
+    T: Real,
+{
+    T::epsilon() * T::from(128.0).unwrap()
+}
+
+fn epa_tolerance<T>() -> T
+where
+    T: Real,
+{
+    T::epsilon().sqrt()
+}
+
+// Gaussian elimination with partial pivoting for the small systems below, None if singular
+fn solve<T>(mut rows: Vec<Vec<T>>, mut rhs: Vec<T>) -> Option<Vec<T>>
+where
+    T: Real,
+{
+    let n = rhs.len();
+
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&x, &y| {
+            rows[x][col]
+                .abs()
+                .partial_cmp(&rows[y][col].abs())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })?;
+
+        if rows[pivot][col].abs() <= T::epsilon() {
+            return None;
+        }
+
+        rows.swap(pivot, col);
+        rhs.swap(pivot, col);
+
+        for row in col + 1..n {
+            let factor = rows[row][col] / rows[col][col];
+
+            let pivot_row = rows[col].clone();
+            for (val, pivot_val) in rows[row][col..].iter_mut().zip(&pivot_row[col..]) {
+                *val = *val - factor * *pivot_val;
+            }
+            rhs[row] = rhs[row] - factor * rhs[col];
+        }
+    }
+
+    let mut res = vec![T::zero(); n];
+    for row in (0..n).rev() {
+        let sum = (row + 1..n).fold(rhs[row], |sum, k| sum - rows[row][k] * res[k]);
+        res[row] = sum / rows[row][row];
+    }
+
+    Some(res)
+}
+
+// Barycentric weights of the point closest to the origin in the affine hull of the points
+fn affine_weights<const N: usize, T>(points: &[Vector<N, T>]) -> Option<Vec<T>>
+where
+    T: Default + Real,
+{
+    let origin = points[0];
+    let edges: Vec<_> = points[1..].iter().map(|point| *point - origin).collect();
+
+    let gram = edges
+        .iter()
+        .map(|a| edges.iter().map(|b| a.dot(b)).collect())
+        .collect();
+    let rhs = edges.iter().map(|edge| -edge.dot(&origin)).collect();
+    let lambdas = solve(gram, rhs)?;
+
+    let first = lambdas.iter().fold(T::one(), |rest, &lambda| rest - lambda);
+
+    Some(std::iter::once(first).chain(lambdas).collect())
+}
+
+// Smallest face of the simplex holding the point closest to the origin, with its weights. Tries
+// every face, which is at most 15 in 3D
+fn closest_on_simplex<const N: usize, T>(
+    simplex: &[SupportPoint<N, T>],
+) -> (Vec<SupportPoint<N, T>>, Vec<T>)
+where
+    T: Default + Real,
+{
+    let mut best: Option<(T, Vec<SupportPoint<N, T>>, Vec<T>)> = None;
+
+    for mask in 1..1usize << simplex.len() {
+        let face: Vec<_> = (0..simplex.len())
+            .filter(|i| mask & (1 << i) != 0)
+            .map(|i| simplex[i])
+            .collect();
+        let points: Vec<_> = face.iter().map(|vertex| vertex.point).collect();
+
+        let Some(weights) = affine_weights(&points) else {
+            continue;
+        };
+        if weights.iter().any(|&weight| weight <= T::zero()) {
+            continue;
+        }
+
+        let distance = combine(&points, &weights).length_squared();
+        if best.as_ref().is_none_or(|(best, _, _)| distance < *best) {
+            best = Some((distance, face, weights));
+        }
+    }
+
+    // A single vertex is always a candidate
+    let (_, face, weights) = best.unwrap();
+
+    (face, weights)
+}
+
+fn combine<const N: usize, T>(points: &[Vector<N, T>], weights: &[T]) -> Vector<N, T>
+where
+    T: Real,
+{
+    points
+        .iter()
+        .zip(weights)
+        .fold(Vector::new_val(T::zero()), |sum, (point, &weight)| {
+            sum + *point * weight
+        })
+}
+
+// Separation when apart, otherwise the last simplex which surrounds the origin
+fn gjk<const N: usize, T, A, B>(a: &A, b: &B) -> Result<Separation<N, T>, Vec<SupportPoint<N, T>>>
+where
+    T: Default + Real,
+    A: SupportMap<N, T> + ?Sized,
+    B: SupportMap<N, T> + ?Sized,
+{
+    let mut direction = Vector::new_val(T::zero());
+    direction[0] = T::one();
+
+    let mut simplex = vec![support_point(a, b, &direction)];
+    let mut weights = vec![T::one()];
+    let mut closest = simplex[0].point;
+    let mut scale = closest.length_squared();
+
+    let separation = |simplex: &[SupportPoint<N, T>], weights: &[T]| {
+        let on_a: Vec<_> = simplex.iter().map(|vertex| vertex.a).collect();
+        let on_b: Vec<_> = simplex.iter().map(|vertex| vertex.b).collect();
+        let point_a = combine(&on_a, weights);
+        let point_b = combine(&on_b, weights);
+
+        Separation {
+            distance: (point_a - point_b).length(),
+            point_a,
+            point_b,
+        }
+    };
+
+    for _ in 0..GJK_ITERATIONS {
+        let len_sq = closest.length_squared();
+        if len_sq <= T::epsilon() * scale {
+            return Err(simplex);
+        }
+
+        let next = support_point(a, b, &-closest);
+        scale = scale.max(next.point.length_squared());
+
+        // Can't get any closer to the origin
+        if len_sq - closest.dot(&next.point) <= len_sq * gjk_tolerance::<T>()
+            || simplex.iter().any(|vertex| vertex.point == next.point)
+        {
+            return Ok(separation(&simplex, &weights));
+        }
+
+        simplex.push(next);
+        (simplex, weights) = closest_on_simplex(&simplex);
+
+        if simplex.len() == N + 1 {
+            return Err(simplex);
+        }
+
+        let points: Vec<_> = simplex.iter().map(|vertex| vertex.point).collect();
+        closest = combine(&points, &weights);
+    }
+
+    Ok(separation(&simplex, &weights))
+}
+
+// Closest points and distance between two convex shapes, None if they intersect
+pub fn gjk_distance<const N: usize, T, A, B>(a: &A, b: &B) -> Option<Separation<N, T>>
+where
+    T: Default + Real,
+    A: SupportMap<N, T> + ?Sized,
+    B: SupportMap<N, T> + ?Sized,
+{
+    gjk(a, b).ok()
+}
+
+pub fn gjk_intersects<const N: usize, T, A, B>(a: &A, b: &B) -> bool
+where
+    T: Default + Real,
+    A: SupportMap<N, T> + ?Sized,
+    B: SupportMap<N, T> + ?Sized,
+{
+    gjk(a, b).is_err()
+}
+
+// Part of v orthogonal to the orthonormal basis
+fn reject<const N: usize, T>(basis: &[Vector<N, T>], v: Vector<N, T>) -> Vector<N, T>
+where
+    T: Default + Real,
+{
+    basis.iter().fold(v, |v, axis| v - *axis * axis.dot(&v))
+}
+
+// Orthonormal basis of the directions spanned by the points
+fn span<const N: usize, T>(points: &[Vector<N, T>]) -> Vec<Vector<N, T>>
+where
+    T: Default + Real,
+{
+    let mut basis = Vec::new();
+
+    for point in &points[1..] {
+        let rejected = reject(&basis, *point - points[0]);
+        let len = rejected.length();
+
+        if len > T::epsilon() {
+            basis.push(rejected / len);
+        }
+    }
+
+    basis
+}
+
+struct Facet<const N: usize, T> {
+    vertices: Vec<usize>,
+    normal: Vector<N, T>,
+    distance: T,
+}
+
+// Normal pointing away from the interior point, no winding bookkeeping needed in any dimension
+fn facet<const N: usize, T>(
+    points: &[Vector<N, T>],
+    vertices: Vec<usize>,
+    interior: &Vector<N, T>,
+) -> Option<Facet<N, T>>
+where
+    T: Default + Real,
+{
+    let corners: Vec<_> = vertices.iter().map(|&i| points[i]).collect();
+    let normal = reject(&span(&corners), corners[0] - interior);
+    let len = normal.length();
+
+    if len <= T::epsilon() {
+        return None;
+    }
+
+    let normal = normal / len;
+
+    Some(Facet {
+        distance: normal.dot(&corners[0]),
+        vertices,
+        normal,
+    })
+}
+
+// Penetration depth and normal of two intersecting convex shapes by the expanding polytope
+// algorithm, None if they are apart. Moving b by normal * depth separates them
+pub fn epa_penetration<const N: usize, T, A, B>(a: &A, b: &B) -> Option<Penetration<N, T>>
+where
+    T: Default + Real,
+    A: SupportMap<N, T> + ?Sized,
+    B: SupportMap<N, T> + ?Sized,
+{
+    let simplex = gjk(a, b).err()?;
+    let mut points: Vec<_> = simplex.iter().map(|vertex| vertex.point).collect();
+
+    // GJK can stop early on a lower dimensional simplex, grow it to a full one
+    for axis in 0..N {
+        for sign in [T::one(), -T::one()] {
+            if points.len() == N + 1 {
+                break;
+            }
+
+            let mut direction = Vector::new_val(T::zero());
+            direction[axis] = sign;
+
+            let next = support_point(a, b, &direction).point;
+            let rejected = reject(&span(&points), next - points[0]);
+
+            if rejected.length() > epa_tolerance::<T>() * next.length().max(T::one()) {
+                points.push(next);
+            }
+        }
+    }
+
+    // The difference is flat, so the shapes only touch
+    if points.len() < N + 1 {
+        let basis = span(&points);
+        let normal = (0..N)
+            .map(|axis| {
+                let mut direction = Vector::new_val(T::zero());
+                direction[axis] = T::one();
+
+                reject(&basis, direction)
+            })
+            .fold(Vector::new_val(T::zero()), |best, v| {
+                if v.length_squared() > best.length_squared() {
+                    v
+                } else {
+                    best
+                }
+            });
+
+        return Some(Penetration {
+            normal: normal.normalized(),
+            depth: T::zero(),
+        });
+    }
+
+    let interior = points
+        .iter()
+        .fold(Vector::new_val(T::zero()), |sum, point| sum + point)
+        / T::from(N + 1).unwrap();
+
+    let mut facets: Vec<_> = (0..=N)
+        .filter_map(|skip| facet(&points, (0..=N).filter(|&i| i != skip).collect(), &interior))
+        .collect();
+    let mut best = None;
+
+    for _ in 0..EPA_ITERATIONS {
+        let Some(closest) = facets.iter().min_by(|x, y| {
+            x.distance
+                .partial_cmp(&y.distance)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        }) else {
+            break;
+        };
+
+        best = Some(Penetration {
+            normal: closest.normal,
+            depth: closest.distance.max(T::zero()),
+        });
+
+        let next = support_point(a, b, &closest.normal).point;
+        let scale = next.length().max(T::one());
+        if next.dot(&closest.normal) - closest.distance <= epa_tolerance::<T>() * scale {
+            break;
+        }
+
+        let index = points.len();
+        points.push(next);
+
+        // Facets the new point sees get replaced by a fan from the edge of the hole they leave
+        let (visible, kept): (Vec<_>, Vec<_>) = facets
+            .into_iter()
+            .partition(|facet| facet.normal.dot(&(next - points[facet.vertices[0]])) > T::zero());
+
+        let mut horizon: Vec<Vec<usize>> = Vec::new();
+        for facet in &visible {
+            for skip in 0..N {
+                let mut ridge: Vec<_> = facet
+                    .vertices
+                    .iter()
+                    .enumerate()
+                    .filter(|&(i, _)| i != skip)
+                    .map(|(_, &vertex)| vertex)
+                    .collect();
+                ridge.sort_unstable();
+
+                match horizon.iter().position(|other| *other == ridge) {
+                    Some(pos) => {
+                        horizon.swap_remove(pos);
+                    }
+                    None => horizon.push(ridge),
+                }
+            }
+        }
+
+        facets = kept;
+        facets.extend(horizon.into_iter().filter_map(|mut ridge| {
+            ridge.push(index);
+            facet(&points, ridge, &interior)
+        }));
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        aabb::Aabb,
+        obb::Obb3D,
+        shapes::{Capsule, Sphere},
+        support_map::MinkowskiSum,
+        test_util::assert_close,
+        vector,
+    };
+
+    #[test]
+    fn distance() {
+        let a = Sphere::new(vector!(0.0, 0.0, 0.0), 1.0);
+        let b = Sphere::new(vector!(3.0, 4.0, 0.0), 2.0);
+        let separation = gjk_distance(&a, &b).unwrap();
+
+        assert!((separation.distance - 2.0).abs() < 1e-6);
+        assert_close(separation.point_a, vector!(0.6, 0.8, 0.0), 1e-6);
+        assert_close(separation.point_b, vector!(1.8, 2.4, 0.0), 1e-6);
+
+        let boxes = (
+            Aabb::new(vector!(0.0, 0.0), vector!(1.0, 1.0)),
+            Aabb::new(vector!(3.0, -2.0), vector!(4.0, 0.5)),
+        );
+        let separation = gjk_distance(&boxes.0, &boxes.1).unwrap();
+        assert!((separation.distance - 2.0).abs() < 1e-9);
+        assert_eq!(separation.point_a.x(), 1.0);
+        assert_eq!(separation.point_b.x(), 3.0);
+
+        // Point against a tetrahedron given as a point cloud
+        let tetrahedron = [
+            vector!(0.0, 0.0, 0.0),
+            vector!(1.0, 0.0, 0.0),
+            vector!(0.0, 1.0, 0.0),
+            vector!(0.0, 0.0, 1.0),
+        ];
+        let separation = gjk_distance(&tetrahedron[..], &vector!(1.0, 1.0, 1.0)).unwrap();
+        assert!((separation.distance - 2.0 / 3.0_f64.sqrt()).abs() < 1e-9);
+        assert_close(separation.point_a, vector!(1.0, 1.0, 1.0) / 3.0, 1e-6);
+
+        assert!(gjk_distance(&tetrahedron[..], &vector!(0.1, 0.1, 0.1)).is_none());
+    }
+
+    #[test]
+    fn intersects() {
+        let capsule = Capsule::new(vector!(0.0, 0.0, 0.0), vector!(0.0, 4.0, 0.0), 1.0);
+        let obb = Obb3D::from_aabb(&Aabb::new(vector!(1.5, 3.0, -1.0), vector!(3.0, 5.0, 1.0)));
+
+        assert!(!gjk_intersects(&capsule, &obb));
+        assert!(gjk_intersects(
+            &capsule,
+            &Obb3D::new(
+                obb.center - vector!(0.6, 0.0, 0.0),
+                obb.half_extents,
+                obb.rotation
+            )
+        ));
+
+        // Rounded box through a Minkowski sum
+        let rounded = MinkowskiSum::new(
+            Aabb::new(vector!(-1.0, -1.0), vector!(1.0, 1.0)),
+            Sphere::new(vector!(0.0, 0.0), 0.5),
+        );
+        assert!(gjk_intersects(&rounded, &vector!(1.3, 1.3)));
+        assert!(!gjk_intersects(&rounded, &vector!(1.4, 1.4)));
+        assert!(gjk_intersects(&rounded, &vector!(1.45, 0.0)));
+    }
+
+    #[test]
+    fn penetration() {
+        let a = Aabb::new(vector!(0.0, 0.0), vector!(2.0, 2.0));
+        let b = Aabb::new(vector!(1.5, 0.5), vector!(3.0, 1.5));
+        let hit = epa_penetration(&a, &b).unwrap();
+
+        assert_close(hit.normal, vector!(1.0, 0.0), 1e-6);
+        assert!((hit.depth - 0.5).abs() < 1e-6);
+        assert!(epa_penetration(&a, &Aabb::new(vector!(2.5, 0.0), vector!(3.0, 1.0))).is_none());
+
+        let a = Sphere::new(vector!(0.0, 0.0, 0.0), 1.5);
+        let b = Sphere::new(vector!(0.0, 0.0, 2.0), 1.0);
+        let hit = epa_penetration(&a, &b).unwrap();
+        assert!((hit.normal - vector!(0.0, 0.0, 1.0)).length() < 1e-3);
+        assert!((hit.depth - 0.5).abs() < 1e-3);
+
+        let a = Aabb::new(vector!(-1.0, -1.0, -1.0), vector!(1.0, 1.0, 1.0));
+        let b = Aabb::new(vector!(-0.5, 0.7, -0.5), vector!(0.5, 3.0, 0.5));
+        let hit = epa_penetration(&a, &b).unwrap();
+        assert_close(hit.normal, vector!(0.0, 1.0, 0.0), 1e-6);
+        assert!((hit.depth - 0.3).abs() < 1e-6);
+
+        // Boxes sharing a face only touch
+        let b = Aabb::new(vector!(1.0, -1.0, -1.0), vector!(3.0, 1.0, 1.0));
+        let hit = epa_penetration(&a, &b).unwrap();
+        assert!(hit.depth.abs() < 1e-9);
+    }
+}
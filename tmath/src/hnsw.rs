@@ -0,0 +1,585 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, HashSet},
+    io::{self, Read, Write},
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::vector::Vector;
+
+const MAGIC: &[u8; 4] = b"HNSW";
+const VERSION: u32 = 1;
+const SEED: u64 = 0x5eed;
+
+// Smaller is closer for all of them: one minus the cosine similarity, the negated dot product, or
+// the straight line distance
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum HnswMetric {
+    #[default]
+    Cosine,
+    Dot,
+    Euclidean,
+}
+
+impl HnswMetric {
+    fn distance<const N: usize>(&self, a: &Vector<N, f32>, b: &Vector<N, f32>) -> f32 {
+        match self {
+            Self::Cosine => {
+                let norms = a.length() * b.length();
+                if norms == 0.0 {
+                    1.0
+                } else {
+                    1.0 - a.dot(b) / norms
+                }
+            }
+            Self::Dot => -a.dot(b),
+            Self::Euclidean => (*a - b).length(),
+        }
+    }
+
+    fn to_byte(self) -> u8 {
+        match self {
+            Self::Cosine => 0,
+            Self::Dot => 1,
+            Self::Euclidean => 2,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Self::Cosine),
+            1 => Some(Self::Dot),
+            2 => Some(Self::Euclidean),
+            _ => None,
+        }
+    }
+}
+
+// Ordered by distance, then id to keep the heaps deterministic
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Candidate {
+    distance: f32,
+    id: usize,
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance
+            .total_cmp(&other.distance)
+            .then(self.id.cmp(&other.id))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Node<const N: usize> {
+    vector: Vector<N, f32>,
+    // Neighbors on each layer the node is on, from the bottom up
+    links: Vec<Vec<usize>>,
+    deleted: bool,
+}
+
+// Hierarchical navigable small world graph (Malkov & Yashunin). Ids are handed out in insertion
+// order. Deleted items stay in the graph to route searches through but are never returned
+#[derive(Debug, Clone)]
+pub struct Hnsw<const N: usize> {
+    metric: HnswMetric,
+    // Links per node above the bottom layer, twice as many on it
+    m: usize,
+    ef_construction: usize,
+    nodes: Vec<Node<N>>,
+    entry: Option<usize>,
+    live: usize,
+    rng: StdRng,
+}
+
+impl<const N: usize> Hnsw<N> {
+    pub fn new(metric: HnswMetric, m: usize, ef_construction: usize) -> Self {
+        assert!(m >= 2, "HNSW needs at least two links per node");
+
+        Self {
+            metric,
+            m,
+            ef_construction: ef_construction.max(m),
+            nodes: Vec::new(),
+            entry: None,
+            live: 0,
+            rng: StdRng::seed_from_u64(SEED),
+        }
+    }
+
+    pub fn metric(&self) -> HnswMetric {
+        self.metric
+    }
+
+    // Items not deleted
+    pub fn len(&self) -> usize {
+        self.live
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.live == 0
+    }
+
+    pub fn get(&self, id: usize) -> Option<&Vector<N, f32>> {
+        self.nodes
+            .get(id)
+            .filter(|node| !node.deleted)
+            .map(|node| &node.vector)
+    }
+
+    fn distance(&self, query: &Vector<N, f32>, id: usize) -> f32 {
+        self.metric.distance(query, &self.nodes[id].vector)
+    }
+
+    fn max_links(&self, layer: usize) -> usize {
+        if layer == 0 {
+            2 * self.m
+        } else {
+            self.m
+        }
+    }
+
+    // Exponentially fewer nodes on each layer up
+    fn random_level(&mut self) -> usize {
+        let scale = 1.0 / (self.m as f64).ln();
+        let uniform: f64 = 1.0 - self.rng.gen::<f64>();
+
+        (-uniform.ln() * scale) as usize
+    }
+
+    pub fn insert(&mut self, vector: Vector<N, f32>) -> usize {
+        let id = self.nodes.len();
+        let level = self.random_level();
+
+        self.nodes.push(Node {
+            vector,
+            links: vec![Vec::new(); level + 1],
+            deleted: false,
+        });
+        self.live += 1;
+
+        let Some(entry) = self.entry else {
+            self.entry = Some(id);
+            return id;
+        };
+
+        let top = self.nodes[entry].links.len() - 1;
+        let mut nearest = vec![Candidate {
+            distance: self.distance(&vector, entry),
+            id: entry,
+        }];
+
+        for layer in (level + 1..=top).rev() {
+            nearest = self.search_layer(&vector, &nearest, 1, layer, false);
+        }
+
+        for layer in (0..=level.min(top)).rev() {
+            nearest = self.search_layer(&vector, &nearest, self.ef_construction, layer, false);
+
+            let neighbors = self.select_neighbors(&nearest, self.m);
+            self.nodes[id].links[layer] = neighbors.iter().map(|c| c.id).collect();
+
+            for neighbor in neighbors {
+                self.nodes[neighbor.id].links[layer].push(id);
+
+                if self.nodes[neighbor.id].links[layer].len() > self.max_links(layer) {
+                    self.shrink_links(neighbor.id, layer);
+                }
+            }
+        }
+
+        if level > top {
+            self.entry = Some(id);
+        }
+
+        id
+    }
+
+    // Deleted items keep routing searches, the slot is not reused
+    pub fn delete(&mut self, id: usize) -> bool {
+        match self.nodes.get_mut(id) {
+            Some(node) if !node.deleted => {
+                node.deleted = true;
+                self.live -= 1;
+
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn shrink_links(&mut self, id: usize, layer: usize) {
+        let vector = self.nodes[id].vector;
+        let mut candidates: Vec<_> = self.nodes[id].links[layer]
+            .iter()
+            .map(|&other| Candidate {
+                distance: self.distance(&vector, other),
+                id: other,
+            })
+            .collect();
+        candidates.sort();
+
+        self.nodes[id].links[layer] = self
+            .select_neighbors(&candidates, self.max_links(layer))
+            .into_iter()
+            .map(|c| c.id)
+            .collect();
+    }
+
+    // Keeps a candidate only if it's closer to the query than to any already kept, which spreads
+    // the links out in different directions. Candidates come sorted nearest first
+    fn select_neighbors(&self, candidates: &[Candidate], count: usize) -> Vec<Candidate> {
+        let mut res: Vec<Candidate> = Vec::with_capacity(count);
+
+        for candidate in candidates {
+            if res.len() >= count {
+                break;
+            }
+
+            let vector = &self.nodes[candidate.id].vector;
+            let diverse = res
+                .iter()
+                .all(|kept| self.distance(vector, kept.id) > candidate.distance);
+
+            if diverse {
+                res.push(*candidate);
+            }
+        }
+
+        res
+    }
+
+    // Best first search from the entry points, returning up to ef candidates nearest first
+    fn search_layer(
+        &self,
+        query: &Vector<N, f32>,
+        entries: &[Candidate],
+        ef: usize,
+        layer: usize,
+        skip_deleted: bool,
+    ) -> Vec<Candidate> {
+        let mut visited: HashSet<usize> = entries.iter().map(|c| c.id).collect();
+        let mut candidates: BinaryHeap<Reverse<Candidate>> =
+            entries.iter().copied().map(Reverse).collect();
+        let mut found: BinaryHeap<Candidate> = entries
+            .iter()
+            .copied()
+            .filter(|c| !skip_deleted || !self.nodes[c.id].deleted)
+            .collect();
+        while found.len() > ef {
+            found.pop();
+        }
+
+        while let Some(Reverse(current)) = candidates.pop() {
+            if found.len() >= ef
+                && found
+                    .peek()
+                    .is_some_and(|worst| current.distance > worst.distance)
+            {
+                break;
+            }
+
+            for &neighbor in &self.nodes[current.id].links[layer] {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+
+                let candidate = Candidate {
+                    distance: self.distance(query, neighbor),
+                    id: neighbor,
+                };
+                if found.len() >= ef && found.peek().is_some_and(|worst| candidate >= *worst) {
+                    continue;
+                }
+
+                candidates.push(Reverse(candidate));
+                if !skip_deleted || !self.nodes[neighbor].deleted {
+                    found.push(candidate);
+                    if found.len() > ef {
+                        found.pop();
+                    }
+                }
+            }
+        }
+
+        found.into_sorted_vec()
+    }
+
+    // Approximately the k nearest items as (id, distance), nearest first. A larger ef explores more
+    // of the graph for better recall, it's raised to k if lower
+    pub fn search(&self, query: &Vector<N, f32>, k: usize, ef: usize) -> Vec<(usize, f32)> {
+        let Some(entry) = self.entry else {
+            return Vec::new();
+        };
+
+        let mut nearest = vec![Candidate {
+            distance: self.distance(query, entry),
+            id: entry,
+        }];
+        for layer in (1..self.nodes[entry].links.len()).rev() {
+            nearest = self.search_layer(query, &nearest, 1, layer, false);
+        }
+
+        self.search_layer(query, &nearest, ef.max(k), 0, true)
+            .into_iter()
+            .take(k)
+            .map(|c| (c.id, c.distance))
+            .collect()
+    }
+
+    // Little endian binary, the vector size has to match when loading
+    pub fn save<W>(&self, mut writer: W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&(N as u64).to_le_bytes())?;
+        writer.write_all(&[self.metric.to_byte()])?;
+        writer.write_all(&(self.m as u64).to_le_bytes())?;
+        writer.write_all(&(self.ef_construction as u64).to_le_bytes())?;
+        writer.write_all(&self.entry.map_or(u64::MAX, |e| e as u64).to_le_bytes())?;
+        writer.write_all(&(self.nodes.len() as u64).to_le_bytes())?;
+
+        for node in &self.nodes {
+            writer.write_all(&[node.deleted as u8])?;
+            for val in node.vector.0 {
+                writer.write_all(&val.to_le_bytes())?;
+            }
+
+            writer.write_all(&(node.links.len() as u64).to_le_bytes())?;
+            for links in &node.links {
+                writer.write_all(&(links.len() as u64).to_le_bytes())?;
+                for &link in links {
+                    writer.write_all(&(link as u64).to_le_bytes())?;
+                }
+            }
+        }
+
+        writer.flush()
+    }
+
+    pub fn load<R>(mut reader: R) -> io::Result<Self>
+    where
+        R: Read,
+    {
+        fn invalid(message: &str) -> io::Error {
+            io::Error::new(io::ErrorKind::InvalidData, message)
+        }
+
+        fn read_bytes<R: Read, const B: usize>(reader: &mut R) -> io::Result<[u8; B]> {
+            let mut buf = [0; B];
+            reader.read_exact(&mut buf)?;
+
+            Ok(buf)
+        }
+
+        fn read_u64<R: Read>(reader: &mut R) -> io::Result<usize> {
+            usize::try_from(u64::from_le_bytes(read_bytes(reader)?))
+                .map_err(|_| invalid("index too large"))
+        }
+
+        if &read_bytes::<_, 4>(&mut reader)? != MAGIC {
+            return Err(invalid("not an HNSW index"));
+        }
+        if u32::from_le_bytes(read_bytes(&mut reader)?) != VERSION {
+            return Err(invalid("unsupported HNSW index version"));
+        }
+        if read_u64(&mut reader)? != N {
+            return Err(invalid("vector size doesn't match"));
+        }
+
+        let metric = HnswMetric::from_byte(read_bytes::<_, 1>(&mut reader)?[0])
+            .ok_or_else(|| invalid("unknown metric"))?;
+        let m = read_u64(&mut reader)?;
+        let ef_construction = read_u64(&mut reader)?;
+        let entry = u64::from_le_bytes(read_bytes(&mut reader)?);
+        let count = read_u64(&mut reader)?;
+
+        if m < 2 {
+            return Err(invalid("too few links per node"));
+        }
+
+        let mut index = Self::new(metric, m, ef_construction);
+
+        for _ in 0..count {
+            let deleted = read_bytes::<_, 1>(&mut reader)?[0] != 0;
+
+            let mut vector = Vector::new([0.0; N]);
+            for i in 0..N {
+                vector[i] = f32::from_le_bytes(read_bytes(&mut reader)?);
+            }
+
+            let layers = read_u64(&mut reader)?;
+            let links = (0..layers)
+                .map(|_| {
+                    let len = read_u64(&mut reader)?;
+
+                    (0..len).map(|_| read_u64(&mut reader)).collect()
+                })
+                .collect::<io::Result<Vec<Vec<usize>>>>()?;
+
+            index.nodes.push(Node {
+                vector,
+                links,
+                deleted,
+            });
+            index.live += !deleted as usize;
+        }
+
+        // Links and the entry point must stay within the graph and its layers
+        let valid = index.nodes.iter().all(|node| {
+            !node.links.is_empty()
+                && node.links.iter().enumerate().all(|(layer, links)| {
+                    links.iter().all(|&link| {
+                        index
+                            .nodes
+                            .get(link)
+                            .is_some_and(|other| other.links.len() > layer)
+                    })
+                })
+        });
+        if !valid {
+            return Err(invalid("corrupt HNSW links"));
+        }
+
+        index.entry = match entry {
+            u64::MAX if count == 0 => None,
+            entry if (entry as usize) < count => Some(entry as usize),
+            _ => return Err(invalid("corrupt HNSW entry point")),
+        };
+
+        Ok(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vectors<const N: usize>(count: usize, seed: u64) -> Vec<Vector<N, f32>> {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        (0..count)
+            .map(|_| Vector::new(std::array::from_fn(|_| rng.gen_range(-1.0..1.0))))
+            .collect()
+    }
+
+    fn exact<const N: usize>(
+        metric: HnswMetric,
+        data: &[Vector<N, f32>],
+        query: &Vector<N, f32>,
+        k: usize,
+        skip: &[usize],
+    ) -> Vec<usize> {
+        let mut all: Vec<_> = (0..data.len())
+            .filter(|i| !skip.contains(i))
+            .map(|i| (metric.distance(query, &data[i]), i))
+            .collect();
+        all.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        all.into_iter().take(k).map(|(_, i)| i).collect()
+    }
+
+    fn recall<const N: usize>(index: &Hnsw<N>, data: &[Vector<N, f32>], skip: &[usize]) -> f32 {
+        let queries = vectors::<N>(50, 99);
+        let mut hits = 0;
+
+        for query in &queries {
+            let expected = exact(index.metric(), data, query, 10, skip);
+            let found = index.search(query, 10, 64);
+            assert!(found.windows(2).all(|w| w[0].1 <= w[1].1));
+            assert!(found.iter().all(|(id, _)| !skip.contains(id)));
+
+            hits += found.iter().filter(|(id, _)| expected.contains(id)).count();
+        }
+
+        hits as f32 / (queries.len() * 10) as f32
+    }
+
+    #[test]
+    fn metrics() {
+        let data = vectors::<16>(1000, 1);
+
+        for metric in [HnswMetric::Cosine, HnswMetric::Dot, HnswMetric::Euclidean] {
+            let mut index = Hnsw::new(metric, 12, 100);
+            for vector in &data {
+                index.insert(*vector);
+            }
+
+            assert_eq!(index.len(), 1000);
+            assert!(recall(&index, &data, &[]) > 0.9, "{:?}", metric);
+        }
+    }
+
+    #[test]
+    fn exact_match() {
+        let data = vectors::<32>(300, 2);
+        let mut index = Hnsw::new(HnswMetric::Euclidean, 8, 64);
+        for vector in &data {
+            index.insert(*vector);
+        }
+
+        for (id, vector) in data.iter().enumerate().step_by(17) {
+            assert_eq!(index.search(vector, 1, 32)[0], (id, 0.0));
+        }
+
+        assert!(Hnsw::<4>::new(HnswMetric::Cosine, 4, 10)
+            .search(&Vector::new([1.0; 4]), 3, 10)
+            .is_empty());
+    }
+
+    #[test]
+    fn delete() {
+        let data = vectors::<8>(500, 3);
+        let mut index = Hnsw::new(HnswMetric::Euclidean, 8, 64);
+        for vector in &data {
+            index.insert(*vector);
+        }
+
+        let deleted: Vec<_> = (0..500).step_by(3).collect();
+        for &id in &deleted {
+            assert!(index.delete(id));
+        }
+        assert!(!index.delete(0));
+        assert!(!index.delete(1000));
+        assert_eq!(index.len(), 500 - deleted.len());
+        assert!(index.get(0).is_none());
+        assert_eq!(index.get(1), Some(&data[1]));
+
+        assert!(recall(&index, &data, &deleted) > 0.9);
+    }
+
+    #[test]
+    fn save_load() {
+        let data = vectors::<24>(400, 4);
+        let mut index = Hnsw::new(HnswMetric::Cosine, 10, 80);
+        for vector in &data {
+            index.insert(*vector);
+        }
+        index.delete(5);
+
+        let mut bytes = Vec::new();
+        index.save(&mut bytes).unwrap();
+        let loaded = Hnsw::<24>::load(bytes.as_slice()).unwrap();
+
+        assert_eq!(loaded.len(), index.len());
+        assert_eq!(loaded.metric(), HnswMetric::Cosine);
+        for query in vectors::<24>(20, 5) {
+            assert_eq!(loaded.search(&query, 5, 40), index.search(&query, 5, 40));
+        }
+
+        assert!(Hnsw::<16>::load(bytes.as_slice()).is_err());
+        assert!(Hnsw::<24>::load(&bytes[..bytes.len() / 2]).is_err());
+        assert!(Hnsw::<24>::load(&b"nope"[..]).is_err());
+    }
+}
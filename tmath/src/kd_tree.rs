@@ -0,0 +1,409 @@
+use std::cmp::Ordering;
+
+use num_traits::real::Real;
+
+use crate::vector::Vector;
+
+pub type KdTree2<T, V> = KdTree<2, T, V>;
+pub type KdTree3<T, V> = KdTree<3, T, V>;
+
+macro_rules! kd_tree_types {
+    ($($n:literal),*) => {
+        paste::paste! {
+            $(
+                pub type [< KdTree $n F >]<V> = [< KdTree $n >]<f32, V>;
+                pub type [< KdTree $n D >]<V> = [< KdTree $n >]<f64, V>;
+            )*
+        }
+    };
+}
+
+kd_tree_types!(2, 3);
+
+// Distance between points, with a lower bound for points apart by delta along a single axis used to
+// skip the far side of a split
+pub trait Metric<const N: usize, T> {
+    fn distance(&self, a: &Vector<N, T>, b: &Vector<N, T>) -> T;
+    fn axis_distance(&self, delta: T) -> T;
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Euclidean;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Manhattan;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Chebyshev;
+
+impl<const N: usize, T> Metric<N, T> for Euclidean
+where
+    T: Real,
+{
+    fn distance(&self, a: &Vector<N, T>, b: &Vector<N, T>) -> T {
+        (0..N)
+            .fold(T::zero(), |sum, i| sum + (a[i] - b[i]) * (a[i] - b[i]))
+            .sqrt()
+    }
+
+    fn axis_distance(&self, delta: T) -> T {
+        delta.abs()
+    }
+}
+
+impl<const N: usize, T> Metric<N, T> for Manhattan
+where
+    T: Real,
+{
+    fn distance(&self, a: &Vector<N, T>, b: &Vector<N, T>) -> T {
+        (0..N).fold(T::zero(), |sum, i| sum + (a[i] - b[i]).abs())
+    }
+
+    fn axis_distance(&self, delta: T) -> T {
+        delta.abs()
+    }
+}
+
+impl<const N: usize, T> Metric<N, T> for Chebyshev
+where
+    T: Real,
+{
+    fn distance(&self, a: &Vector<N, T>, b: &Vector<N, T>) -> T {
+        (0..N).fold(T::zero(), |max, i| max.max((a[i] - b[i]).abs()))
+    }
+
+    fn axis_distance(&self, delta: T) -> T {
+        delta.abs()
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Neighbor<'a, const N: usize, T, V> {
+    pub key: &'a Vector<N, T>,
+    pub value: &'a V,
+    pub distance: T,
+}
+
+// Balanced tree laid out implicitly: each range of items has its splitting item in the middle,
+// the smaller ones before it and the larger ones after
+#[derive(Debug, Clone, PartialEq)]
+pub struct KdTree<const N: usize, T, V> {
+    items: Vec<(Vector<N, T>, V)>,
+    axes: Vec<usize>,
+}
+
+impl<const N: usize, T, V> KdTree<N, T, V> {
+    pub fn build(items: Vec<(Vector<N, T>, V)>) -> Self
+    where
+        T: Real,
+    {
+        let mut tree = Self {
+            axes: vec![0; items.len()],
+            items,
+        };
+        tree.build_range(0, tree.items.len());
+
+        tree
+    }
+
+    // Splits along the axis the keys are most spread out on
+    fn build_range(&mut self, start: usize, end: usize)
+    where
+        T: Real,
+    {
+        if end - start < 2 {
+            return;
+        }
+
+        let items = &mut self.items[start..end];
+        let spread = |axis: usize| {
+            let (min, max) = items.iter().fold(
+                (items[0].0[axis], items[0].0[axis]),
+                |(min, max), (key, _)| (min.min(key[axis]), max.max(key[axis])),
+            );
+
+            max - min
+        };
+        let axis = (1..N).fold(0, |best, axis| {
+            if spread(axis) > spread(best) {
+                axis
+            } else {
+                best
+            }
+        });
+
+        let mid = items.len() / 2;
+        items.select_nth_unstable_by(mid, |a, b| {
+            a.0[axis].partial_cmp(&b.0[axis]).unwrap_or(Ordering::Equal)
+        });
+
+        self.axes[start + mid] = axis;
+        self.build_range(start, start + mid);
+        self.build_range(start + mid + 1, end);
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Vector<N, T>, &V)> {
+        self.items.iter().map(|(key, value)| (key, value))
+    }
+
+    fn neighbor(&self, (index, distance): (usize, T)) -> Neighbor<'_, N, T, V> {
+        let (key, value) = &self.items[index];
+
+        Neighbor {
+            key,
+            value,
+            distance,
+        }
+    }
+
+    pub fn nearest<M>(&self, point: &Vector<N, T>, metric: &M) -> Option<Neighbor<'_, N, T, V>>
+    where
+        T: Real,
+        M: Metric<N, T>,
+    {
+        self.nearest_k(point, 1, metric).into_iter().next()
+    }
+
+    // Up to k closest items, nearest first
+    pub fn nearest_k<M>(
+        &self,
+        point: &Vector<N, T>,
+        k: usize,
+        metric: &M,
+    ) -> Vec<Neighbor<'_, N, T, V>>
+    where
+        T: Real,
+        M: Metric<N, T>,
+    {
+        let mut best = Vec::with_capacity(k + 1);
+        if k > 0 {
+            self.nearest_range(0, self.items.len(), point, k, metric, &mut best);
+        }
+
+        best.into_iter().map(|found| self.neighbor(found)).collect()
+    }
+
+    fn nearest_range<M>(
+        &self,
+        start: usize,
+        end: usize,
+        point: &Vector<N, T>,
+        k: usize,
+        metric: &M,
+        best: &mut Vec<(usize, T)>,
+    ) where
+        T: Real,
+        M: Metric<N, T>,
+    {
+        if start >= end {
+            return;
+        }
+
+        let mid = start + (end - start) / 2;
+        let (key, _) = &self.items[mid];
+
+        let distance = metric.distance(point, key);
+        if best.len() < k || distance < best[best.len() - 1].1 {
+            let at = best.partition_point(|(_, d)| *d <= distance);
+            best.insert(at, (mid, distance));
+            best.truncate(k);
+        }
+
+        let axis = self.axes[mid];
+        let delta = point[axis] - key[axis];
+        let (near, far) = if delta <= T::zero() {
+            ((start, mid), (mid + 1, end))
+        } else {
+            ((mid + 1, end), (start, mid))
+        };
+
+        self.nearest_range(near.0, near.1, point, k, metric, best);
+        if best.len() < k || metric.axis_distance(delta) < best[best.len() - 1].1 {
+            self.nearest_range(far.0, far.1, point, k, metric, best);
+        }
+    }
+
+    // Every item within the radius, nearest first
+    pub fn within_radius<M>(
+        &self,
+        point: &Vector<N, T>,
+        radius: T,
+        metric: &M,
+    ) -> Vec<Neighbor<'_, N, T, V>>
+    where
+        T: Real,
+        M: Metric<N, T>,
+    {
+        let mut found = Vec::new();
+        self.radius_range(0, self.items.len(), point, radius, metric, &mut found);
+        found.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal));
+
+        found
+            .into_iter()
+            .map(|found| self.neighbor(found))
+            .collect()
+    }
+
+    fn radius_range<M>(
+        &self,
+        start: usize,
+        end: usize,
+        point: &Vector<N, T>,
+        radius: T,
+        metric: &M,
+        found: &mut Vec<(usize, T)>,
+    ) where
+        T: Real,
+        M: Metric<N, T>,
+    {
+        if start >= end {
+            return;
+        }
+
+        let mid = start + (end - start) / 2;
+        let (key, _) = &self.items[mid];
+
+        let distance = metric.distance(point, key);
+        if distance <= radius {
+            found.push((mid, distance));
+        }
+
+        let axis = self.axes[mid];
+        let delta = point[axis] - key[axis];
+        let reach = metric.axis_distance(delta) <= radius;
+
+        if delta <= T::zero() || reach {
+            self.radius_range(start, mid, point, radius, metric, found);
+        }
+        if delta >= T::zero() || reach {
+            self.radius_range(mid + 1, end, point, radius, metric, found);
+        }
+    }
+}
+
+impl<const N: usize, T, V> FromIterator<(Vector<N, T>, V)> for KdTree<N, T, V>
+where
+    T: Real,
+{
+    fn from_iter<I: IntoIterator<Item = (Vector<N, T>, V)>>(iter: I) -> Self {
+        Self::build(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn points<const N: usize>(count: usize, seed: u64) -> Vec<Vector<N, f64>> {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        (0..count)
+            .map(|_| Vector::new(std::array::from_fn(|_| rng.gen_range(-1.0..1.0))))
+            .collect()
+    }
+
+    fn brute_force<const N: usize, M: Metric<N, f64>>(
+        points: &[Vector<N, f64>],
+        query: &Vector<N, f64>,
+        metric: &M,
+    ) -> Vec<(usize, f64)> {
+        let mut all: Vec<_> = points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (i, metric.distance(query, p)))
+            .collect();
+        all.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+
+        all
+    }
+
+    fn check<const N: usize, M: Metric<N, f64>>(metric: &M) {
+        let keys = points::<N>(1000, 1);
+        let tree: KdTree<N, f64, usize> = keys.iter().copied().zip(0..).collect();
+        assert_eq!(tree.len(), 1000);
+
+        for query in points::<N>(50, 2) {
+            let expected = brute_force(&keys, &query, metric);
+
+            let nearest: Vec<_> = tree
+                .nearest_k(&query, 5, metric)
+                .iter()
+                .map(|n| (*n.value, n.distance))
+                .collect();
+            assert_eq!(nearest, expected[..5]);
+
+            let radius = expected[20].1;
+            let within: Vec<_> = tree
+                .within_radius(&query, radius, metric)
+                .iter()
+                .map(|n| *n.value)
+                .collect();
+            let expected: Vec<_> = expected
+                .iter()
+                .take_while(|(_, d)| *d <= radius)
+                .map(|(i, _)| *i)
+                .collect();
+            assert_eq!(within, expected);
+        }
+    }
+
+    #[test]
+    fn metrics() {
+        check::<2, _>(&Euclidean);
+        check::<3, _>(&Manhattan);
+        check::<3, _>(&Chebyshev);
+        check::<6, _>(&Euclidean);
+    }
+
+    #[test]
+    fn small() {
+        let empty = KdTree::<2, f64, ()>::build(Vec::new());
+        assert!(empty.is_empty());
+        assert!(empty
+            .nearest(&Vector::new([0.0, 0.0]), &Euclidean)
+            .is_none());
+
+        let tree = KdTree2D::build(vec![
+            (Vector::new([0.0, 0.0]), "origin"),
+            (Vector::new([3.0, 4.0]), "far"),
+            (Vector::new([1.0, 1.0]), "near"),
+        ]);
+
+        let nearest = tree.nearest(&Vector::new([0.9, 0.8]), &Euclidean).unwrap();
+        assert_eq!(*nearest.value, "near");
+        assert_eq!(*nearest.key, Vector::new([1.0, 1.0]));
+
+        assert_eq!(
+            tree.nearest_k(&Vector::new([0.0, 0.0]), 10, &Euclidean)
+                .len(),
+            3
+        );
+        assert_eq!(
+            tree.nearest_k(&Vector::new([0.0, 0.0]), 0, &Euclidean)
+                .len(),
+            0
+        );
+
+        let found: Vec<_> = tree
+            .within_radius(&Vector::new([0.0, 0.0]), 5.0, &Manhattan)
+            .iter()
+            .map(|n| *n.value)
+            .collect();
+        assert_eq!(found, ["origin", "near"]);
+        assert_eq!(
+            tree.within_radius(&Vector::new([0.0, 0.0]), 4.0, &Chebyshev)
+                .len(),
+            3
+        );
+    }
+}
@@ -1 +1,32 @@
+pub mod aabb;
+pub mod angle;
+pub mod bezier;
+pub mod bvh;
+pub mod convex_hull;
+pub mod curve;
+pub mod dual_quaternion;
+pub mod euler;
+pub mod frame;
+pub mod frustum;
+pub mod gjk;
+pub mod hnsw;
+pub mod kd_tree;
+pub mod manifold;
+pub mod matrix;
+pub mod nurbs;
+pub mod obb;
+pub mod orthtree;
+pub mod quaternion;
+pub mod ray;
+pub mod rotor;
+pub mod shapes;
+pub mod spatial_hash;
+pub mod spline;
+pub mod support_map;
+pub mod sweep;
+pub mod sweep_and_prune;
+#[cfg(test)]
+mod test_util;
+pub mod triangle;
 pub mod vector;
+pub mod viewport;
@@ -0,0 +1,1032 @@
+use std::collections::HashMap;
+
+use num_traits::real::Real;
+
+use crate::{
+    convex_hull::ConvexHull2,
+    obb::{Obb, Obb2, Obb3},
+    shapes::{closest_point_on_segment, closest_points_on_segments, Capsule, Plane},
+    triangle::Triangle,
+    vector::{Vector, Vector2, Vector3},
+};
+
+pub type Manifold2<T> = Manifold<2, T>;
+pub type Manifold3<T> = Manifold<3, T>;
+
+macro_rules! manifold_types {
+    ($($n:literal),*) => {
+        paste::paste! {
+            $(
+                pub type [< Manifold $n F >] = [< Manifold $n >]<f32>;
+                pub type [< Manifold $n D >] = [< Manifold $n >]<f64>;
+            )*
+        }
+    };
+}
+
+manifold_types!(2, 3);
+
+const MAX_CONTACTS: usize = 4;
+
+// A face only takes over as the reference when it's clearly better, so near ties don't flip the
+// reference from frame to frame
+const FACE_BIAS: f64 = 0.95;
+
+// Sine of the angle under which capsules count as parallel and touch along a line
+const PARALLEL_TOLERANCE: f64 = 1e-3;
+
+// Box vertices use the same bit order as Aabb3::corners, faces are 2 * axis plus one for the
+// positive side. Polygon and capsule features index their vertices in order
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Feature {
+    Vertex(usize),
+    Edge(usize, usize),
+    Face(usize),
+}
+
+// Features of the first and second shape a contact point came from, stays the same between frames
+// while the shapes touch the same way
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct FeatureId {
+    pub a: Feature,
+    pub b: Feature,
+}
+
+// Point halfway between the two surfaces, depth is how far they overlap there
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Contact<const N: usize, T> {
+    pub point: Vector<N, T>,
+    pub depth: T,
+    pub id: FeatureId,
+}
+
+// Normal points from the first shape towards the second
+#[derive(Debug, Clone, PartialEq)]
+pub struct Manifold<const N: usize, T> {
+    pub normal: Vector<N, T>,
+    pub contacts: Vec<Contact<N, T>>,
+}
+
+// Data kept for each contact point between frames, e.g. accumulated impulses for warm starting
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManifoldCache<D> {
+    entries: HashMap<FeatureId, D>,
+}
+
+impl<D> Default for ManifoldCache<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D> ManifoldCache<D> {
+    pub fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+
+    // Points that persist keep their data, new ones start from the default and the rest are dropped
+    pub fn update<const N: usize, T>(&mut self, manifold: &Manifold<N, T>)
+    where
+        D: Default,
+    {
+        let mut previous = std::mem::take(&mut self.entries);
+
+        self.entries = manifold
+            .contacts
+            .iter()
+            .map(|contact| {
+                let data = previous.remove(&contact.id).unwrap_or_default();
+
+                (contact.id, data)
+            })
+            .collect();
+    }
+
+    pub fn get(&self, id: &FeatureId) -> Option<&D> {
+        self.entries.get(id)
+    }
+
+    pub fn get_mut(&mut self, id: &FeatureId) -> Option<&mut D> {
+        self.entries.get_mut(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+fn edge(a: usize, b: usize) -> Feature {
+    Feature::Edge(a.min(b), a.max(b))
+}
+
+// Ids and normal as seen from the reference shape, turned around when that is the second one
+fn oriented<const N: usize, T>(
+    normal: Vector<N, T>,
+    contacts: Vec<Contact<N, T>>,
+    reference_is_a: bool,
+) -> Option<Manifold<N, T>>
+where
+    T: Default + Real,
+{
+    if contacts.is_empty() {
+        return None;
+    }
+
+    if reference_is_a {
+        return Some(Manifold { normal, contacts });
+    }
+
+    Some(Manifold {
+        normal: -normal,
+        contacts: contacts
+            .into_iter()
+            .map(|contact| Contact {
+                id: FeatureId {
+                    a: contact.id.b,
+                    b: contact.id.a,
+                },
+                ..contact
+            })
+            .collect(),
+    })
+}
+
+fn argmax<I, T>(values: I) -> usize
+where
+    I: IntoIterator<Item = T>,
+    T: PartialOrd,
+{
+    values
+        .into_iter()
+        .enumerate()
+        .fold(None, |best: Option<(usize, T)>, (i, value)| match best {
+            Some((_, ref best_value)) if *best_value >= value => best,
+            _ => Some((i, value)),
+        })
+        .map_or(0, |(i, _)| i)
+}
+
+// Deepest point, then each next one as far as possible from the ones picked so far
+fn reduce<const N: usize, T>(contacts: Vec<Contact<N, T>>) -> Vec<Contact<N, T>>
+where
+    T: Default + Real,
+{
+    if contacts.len() <= MAX_CONTACTS {
+        return contacts;
+    }
+
+    let points: Vec<_> = contacts.iter().map(|contact| contact.point).collect();
+
+    let first = argmax(contacts.iter().map(|contact| contact.depth));
+    let second = argmax(
+        points
+            .iter()
+            .map(|point| (*point - points[first]).length_squared()),
+    );
+    let third = argmax(points.iter().map(|point| {
+        (*point - closest_point_on_segment(&points[first], &points[second], point)).length_squared()
+    }));
+    let triangle = Triangle::new(points[first], points[second], points[third]);
+    let fourth = argmax(
+        points
+            .iter()
+            .map(|point| (*point - triangle.closest_point(point)).length_squared()),
+    );
+
+    let mut picked = vec![first, second, third, fourth];
+    picked.sort_unstable();
+    picked.dedup();
+
+    picked.into_iter().map(|i| contacts[i]).collect()
+}
+
+fn box_vertex<const N: usize, T>(obb: &Obb<N, T>, index: usize) -> Vector<N, T>
+where
+    T: Default + Real,
+{
+    (0..N).fold(obb.center, |point, axis| {
+        let offset = obb.axis(axis) * obb.half_extents[axis];
+
+        if index & (1 << axis) == 0 {
+            point - offset
+        } else {
+            point + offset
+        }
+    })
+}
+
+// Vertex of the box that is furthest along the direction, ties go to the positive side
+fn box_support_index<const N: usize, T>(obb: &Obb<N, T>, direction: &Vector<N, T>) -> usize
+where
+    T: Default + Real,
+{
+    (0..N)
+        .filter(|&axis| obb.axis(axis).dot(direction) >= T::zero())
+        .fold(0, |index, axis| index | (1 << axis))
+}
+
+// Polygon clipping against a plane, segments remember what they lie along to name the points
+// they get cut at
+#[derive(Debug, Copy, Clone)]
+enum Segment {
+    Incident(Feature),
+    Reference(usize),
+}
+
+#[derive(Debug, Copy, Clone)]
+struct ClipVertex<T> {
+    point: Vector3<T>,
+    id: FeatureId,
+    segment: Segment,
+}
+
+// Reference face of the first box, clipping the most antiparallel face of the second against its
+// sides. Ids are reference first
+fn box_face_contacts<T>(
+    reference: &Obb3<T>,
+    incident: &Obb3<T>,
+    axis: usize,
+) -> (Vector3<T>, Vec<Contact<3, T>>)
+where
+    T: Default + Real,
+{
+    let mut normal = reference.axis(axis);
+    let positive = normal.dot(&(incident.center - reference.center)) >= T::zero();
+    if !positive {
+        normal = -normal;
+    }
+    let face = 2 * axis + positive as usize;
+
+    let incident_axis = argmax((0..3).map(|i| incident.axis(i).dot(&normal).abs()));
+    let incident_face =
+        2 * incident_axis + (incident.axis(incident_axis).dot(&normal) < T::zero()) as usize;
+    let (u, v) = ((incident_axis + 1) % 3, (incident_axis + 2) % 3);
+    let base = (incident_face & 1) << incident_axis;
+    let vertices = [0, 1 << u, (1 << u) | (1 << v), 1 << v].map(|bits| base | bits);
+
+    let mut polygon: Vec<_> = (0..4)
+        .map(|i| ClipVertex {
+            point: reference.to_local(&box_vertex(incident, vertices[i])),
+            id: FeatureId {
+                a: Feature::Face(face),
+                b: Feature::Vertex(vertices[i]),
+            },
+            segment: Segment::Incident(edge(vertices[i], vertices[(i + 1) % 4])),
+        })
+        .collect();
+
+    // Side faces of the reference box, in its own frame
+    for side in (0..6).filter(|side| side / 2 != axis) {
+        let (side_axis, sign) = if side & 1 == 1 {
+            (side / 2, T::one())
+        } else {
+            (side / 2, -T::one())
+        };
+        let extent = reference.half_extents[side_axis];
+        let outside = |point: &Vector3<T>| point[side_axis] * sign - extent;
+
+        let mut clipped = Vec::with_capacity(polygon.len() + 1);
+        for (i, p) in polygon.iter().enumerate() {
+            let q = &polygon[(i + 1) % polygon.len()];
+            let (dp, dq) = (outside(&p.point), outside(&q.point));
+
+            if dp <= T::zero() {
+                clipped.push(*p);
+            }
+
+            if (dp <= T::zero()) != (dq <= T::zero()) {
+                let id = match p.segment {
+                    Segment::Incident(feature) => FeatureId {
+                        a: Feature::Face(side),
+                        b: feature,
+                    },
+                    // Corner of the reference box where the face and both sides meet
+                    Segment::Reference(other) => FeatureId {
+                        a: Feature::Vertex(
+                            [face, other, side]
+                                .into_iter()
+                                .fold(0, |bits, f| bits | ((f & 1) << (f / 2))),
+                        ),
+                        b: Feature::Face(incident_face),
+                    },
+                };
+
+                clipped.push(ClipVertex {
+                    point: p.point + (q.point - p.point) * (dp / (dp - dq)),
+                    id,
+                    segment: if dp <= T::zero() {
+                        Segment::Reference(side)
+                    } else {
+                        p.segment
+                    },
+                });
+            }
+        }
+
+        polygon = clipped;
+        if polygon.is_empty() {
+            break;
+        }
+    }
+
+    let sign = if positive { T::one() } else { -T::one() };
+    let half = T::one() / (T::one() + T::one());
+
+    let contacts = polygon
+        .into_iter()
+        .filter_map(|vertex| {
+            let depth = reference.half_extents[axis] - vertex.point[axis] * sign;
+            let point = reference.center + reference.rotation * vertex.point;
+
+            (depth >= T::zero()).then(|| Contact {
+                point: point + normal * (depth * half),
+                depth,
+                id: vertex.id,
+            })
+        })
+        .collect();
+
+    (normal, reduce(contacts))
+}
+
+// Outward normal of a counter-clockwise edge
+fn edge_normal<T>(p: &Vector2<T>, q: &Vector2<T>) -> Vector2<T>
+where
+    T: Default + Real,
+{
+    let dir = *q - p;
+
+    Vector2::new([dir.y(), -dir.x()]).normalized()
+}
+
+// Edge of the first polygon the second one is furthest in front of
+fn max_separation<T>(a: &[Vector2<T>], b: &[Vector2<T>]) -> Option<(usize, T)>
+where
+    T: Default + Real,
+{
+    if a.len() < 2 || b.is_empty() {
+        return None;
+    }
+
+    (0..a.len())
+        .filter(|&i| a[i] != a[(i + 1) % a.len()])
+        .map(|i| {
+            let normal = edge_normal(&a[i], &a[(i + 1) % a.len()]);
+            let separation = b
+                .iter()
+                .map(|point| normal.dot(&(*point - a[i])))
+                .fold(None, |min: Option<T>, d| {
+                    Some(min.map_or(d, |min| min.min(d)))
+                })
+                .unwrap();
+
+            (i, separation)
+        })
+        .fold(
+            None,
+            |best: Option<(usize, T)>, (i, separation)| match best {
+                Some((_, best_separation)) if best_separation >= separation => best,
+                _ => Some((i, separation)),
+            },
+        )
+}
+
+// Reference edge of the first polygon, clipping the most antiparallel edge of the second against
+// the sides. Ids are reference first
+fn polygon_edge_contacts<T>(
+    reference: &[Vector2<T>],
+    incident: &[Vector2<T>],
+    index: usize,
+) -> (Vector2<T>, Vec<Contact<2, T>>)
+where
+    T: Default + Real,
+{
+    let next = (index + 1) % reference.len();
+    let (p, q) = (reference[index], reference[next]);
+    let normal = edge_normal(&p, &q);
+    let tangent = (q - p).normalized();
+
+    let count = incident.len();
+    let first = argmax((0..count).map(|i| {
+        if count < 2 {
+            T::zero()
+        } else {
+            -edge_normal(&incident[i], &incident[(i + 1) % count]).dot(&normal)
+        }
+    }));
+    let second = (first + 1) % count;
+
+    let mut segment = vec![(incident[first], Feature::Vertex(first))];
+    if second != first {
+        segment.push((incident[second], Feature::Vertex(second)));
+    }
+
+    // Keep the part between the sides through the ends of the reference edge
+    for (side, direction, offset) in [
+        (index, -tangent, -tangent.dot(&p)),
+        (next, tangent, tangent.dot(&q)),
+    ] {
+        let outside: Vec<_> = segment
+            .iter()
+            .map(|(point, _)| direction.dot(point) - offset)
+            .collect();
+        let mut clipped: Vec<_> = segment
+            .iter()
+            .zip(&outside)
+            .filter(|(_, d)| **d <= T::zero())
+            .map(|(point, _)| *point)
+            .collect();
+
+        if segment.len() == 2 && (outside[0] <= T::zero()) != (outside[1] <= T::zero()) {
+            let (a, b) = (segment[0].0, segment[1].0);
+            let point = a + (b - a) * (outside[0] / (outside[0] - outside[1]));
+
+            clipped.push((point, Feature::Vertex(side)));
+        }
+
+        segment = clipped;
+    }
+
+    let half = T::one() / (T::one() + T::one());
+    let contacts = segment
+        .into_iter()
+        .filter_map(|(point, feature)| {
+            let depth = normal.dot(&(p - point));
+
+            let id = match feature {
+                Feature::Vertex(side) if side == index || side == next => FeatureId {
+                    a: Feature::Vertex(side),
+                    b: edge(first, second),
+                },
+                _ => FeatureId {
+                    a: edge(index, next),
+                    b: feature,
+                },
+            };
+
+            (depth >= T::zero()).then(|| Contact {
+                point: point + normal * (depth * half),
+                depth,
+                id,
+            })
+        })
+        .collect();
+
+    (normal, contacts)
+}
+
+// Both polygons counter-clockwise
+fn polygon_contacts<T>(a: &[Vector2<T>], b: &[Vector2<T>]) -> Option<Manifold<2, T>>
+where
+    T: Default + Real,
+{
+    let bias = T::from(FACE_BIAS).unwrap();
+
+    let (reference_is_a, index) = match (max_separation(a, b), max_separation(b, a)) {
+        (Some((_, sa)), _) if sa > T::zero() => return None,
+        (_, Some((_, sb))) if sb > T::zero() => return None,
+        (Some((ia, sa)), Some((ib, sb))) => {
+            if -sb >= -sa * bias {
+                (true, ia)
+            } else {
+                (false, ib)
+            }
+        }
+        (Some((ia, _)), None) => (true, ia),
+        (None, Some((ib, _))) => (false, ib),
+        (None, None) => return None,
+    };
+
+    let (normal, contacts) = if reference_is_a {
+        polygon_edge_contacts(a, b, index)
+    } else {
+        polygon_edge_contacts(b, a, index)
+    };
+
+    oriented(normal, contacts, reference_is_a)
+}
+
+impl<T> Obb2<T> {
+    fn polygon(&self) -> [Vector2<T>; 4]
+    where
+        T: Default + Real,
+    {
+        [0, 1, 3, 2].map(|i| box_vertex(self, i))
+    }
+
+    pub fn contact_obb(&self, other: &Self) -> Option<Manifold<2, T>>
+    where
+        T: Default + Real,
+    {
+        polygon_contacts(&self.polygon(), &other.polygon())
+    }
+}
+
+impl<T> ConvexHull2<T> {
+    pub fn contact_hull(&self, other: &Self) -> Option<Manifold<2, T>>
+    where
+        T: Default + Real,
+    {
+        polygon_contacts(&self.vertices, &other.vertices)
+    }
+}
+
+impl<T> Obb3<T> {
+    // Face of either box when one separates them the least, otherwise the closest points of the
+    // crossing edges
+    pub fn contact_obb(&self, other: &Self) -> Option<Manifold<3, T>>
+    where
+        T: Default + Real,
+    {
+        let offset = other.center - self.center;
+        let overlap = |axis: &Vector3<T>| {
+            let radius = |obb: &Obb3<T>| {
+                (0..3).fold(T::zero(), |sum, i| {
+                    sum + obb.half_extents[i] * obb.axis(i).dot(axis).abs()
+                })
+            };
+
+            radius(self) + radius(other) - offset.dot(axis).abs()
+        };
+
+        let mut faces = [(0, T::zero()); 2];
+        for (best, obb) in faces.iter_mut().zip([self, other]) {
+            let depths = [0, 1, 2].map(|i| overlap(&obb.axis(i)));
+            if depths.iter().any(|depth| *depth < T::zero()) {
+                return None;
+            }
+
+            let axis = argmax(depths.map(|depth| -depth));
+            *best = (axis, depths[axis]);
+        }
+
+        let mut edges: Option<(usize, usize, Vector3<T>, T)> = None;
+        for i in 0..3 {
+            for j in 0..3 {
+                let axis = self.axis(i).cross(&other.axis(j));
+                let len = axis.length();
+                if len <= T::epsilon().sqrt() {
+                    continue;
+                }
+
+                let axis = axis / len;
+                let depth = overlap(&axis);
+                if depth < T::zero() {
+                    return None;
+                }
+
+                if edges.is_none_or(|(_, _, _, best)| depth < best) {
+                    edges = Some((i, j, axis, depth));
+                }
+            }
+        }
+
+        let bias = T::from(FACE_BIAS).unwrap();
+        let [(axis_a, depth_a), (axis_b, depth_b)] = faces;
+        let reference_is_a = depth_b >= depth_a * bias;
+        let face_depth = if reference_is_a { depth_a } else { depth_b };
+
+        match edges {
+            Some((i, j, mut normal, depth)) if depth < face_depth * bias => {
+                if normal.dot(&offset) < T::zero() {
+                    normal = -normal;
+                }
+
+                // Edges along the crossed axes nearest the other box
+                let support_a = box_support_index(self, &normal) & !(1 << i);
+                let support_b = box_support_index(other, &-normal) & !(1 << j);
+                let (ends_a, ends_b) = (
+                    [support_a, support_a | (1 << i)],
+                    [support_b, support_b | (1 << j)],
+                );
+
+                let (point_a, point_b) = closest_points_on_segments(
+                    &box_vertex(self, ends_a[0]),
+                    &box_vertex(self, ends_a[1]),
+                    &box_vertex(other, ends_b[0]),
+                    &box_vertex(other, ends_b[1]),
+                );
+
+                Some(Manifold {
+                    normal,
+                    contacts: vec![Contact {
+                        point: (point_a + point_b) / (T::one() + T::one()),
+                        depth,
+                        id: FeatureId {
+                            a: edge(ends_a[0], ends_a[1]),
+                            b: edge(ends_b[0], ends_b[1]),
+                        },
+                    }],
+                })
+            }
+            _ if reference_is_a => {
+                let (normal, contacts) = box_face_contacts(self, other, axis_a);
+
+                oriented(normal, contacts, true)
+            }
+            _ => {
+                let (normal, contacts) = box_face_contacts(other, self, axis_b);
+
+                oriented(normal, contacts, false)
+            }
+        }
+    }
+
+    // Vertices behind the plane, the plane is the single face of the second shape
+    pub fn contact_plane(&self, plane: &Plane<T>) -> Option<Manifold<3, T>>
+    where
+        T: Default + Real,
+    {
+        let half = T::one() / (T::one() + T::one());
+
+        let contacts: Vec<_> = (0..8)
+            .filter_map(|vertex| {
+                let point = box_vertex(self, vertex);
+                let depth = -plane.signed_distance(&point);
+
+                (depth >= T::zero()).then(|| Contact {
+                    point: point + plane.normal * (depth * half),
+                    depth,
+                    id: FeatureId {
+                        a: Feature::Vertex(vertex),
+                        b: Feature::Face(0),
+                    },
+                })
+            })
+            .collect();
+
+        oriented(-plane.normal, reduce(contacts), true)
+    }
+}
+
+// Segment features of a capsule, its end points or the segment itself
+fn segment_feature<T>(point: &Vector3<T>, a: &Vector3<T>, b: &Vector3<T>) -> Feature
+where
+    T: Default + Real,
+{
+    if (*point - a).length_squared() <= T::epsilon() {
+        Feature::Vertex(0)
+    } else if (*point - b).length_squared() <= T::epsilon() {
+        Feature::Vertex(1)
+    } else {
+        Feature::Edge(0, 1)
+    }
+}
+
+impl<T> Capsule<T> {
+    // Two points where parallel segments overlap, one at the closest points otherwise
+    pub fn contact_capsule(&self, other: &Self) -> Option<Manifold<3, T>>
+    where
+        T: Default + Real,
+    {
+        let (closest_a, closest_b) =
+            closest_points_on_segments(&self.a, &self.b, &other.a, &other.b);
+        let radius = self.radius + other.radius;
+        let offset = closest_b - closest_a;
+        let distance = offset.length();
+        if distance > radius {
+            return None;
+        }
+
+        let dir_a = self.b - self.a;
+        let dir_b = other.b - other.a;
+
+        // Crossing axes have no side to tell apart, any direction across both will do
+        let normal = if distance > T::epsilon().sqrt() {
+            offset / distance
+        } else {
+            let x = Vector3::new([T::one(), T::zero(), T::zero()]);
+            let y = Vector3::new([T::zero(), T::one(), T::zero()]);
+
+            [dir_a.cross(&dir_b), dir_a.cross(&x), dir_a.cross(&y), y]
+                .into_iter()
+                .find(|axis| axis.length_squared() > T::epsilon())
+                .unwrap()
+                .normalized()
+        };
+
+        let contact = |point_a: Vector3<T>, point_b: Vector3<T>, id: FeatureId| {
+            let depth = radius - (point_b - point_a).dot(&normal);
+            let surface_a = point_a + normal * self.radius;
+            let surface_b = point_b - normal * other.radius;
+
+            (depth >= T::zero()).then(|| Contact {
+                point: (surface_a + surface_b) / (T::one() + T::one()),
+                depth,
+                id,
+            })
+        };
+
+        let len_a = dir_a.length();
+        let parallel = dir_a.normalized().cross(&dir_b.normalized()).length()
+            <= T::from(PARALLEL_TOLERANCE).unwrap();
+
+        if parallel && len_a > T::epsilon() {
+            let axis = dir_a / len_a;
+            let t0 = axis.dot(&(other.a - self.a));
+            let t1 = axis.dot(&(other.b - self.a));
+            let (near, far) = if t0 <= t1 { (0, 1) } else { (1, 0) };
+            let (start, end) = (t0.min(t1), t0.max(t1));
+
+            let low = start.max(T::zero());
+            let high = end.min(len_a);
+
+            if high - low > T::epsilon().sqrt() * len_a {
+                // Each end of the overlap is an end point of one of the two segments
+                let ends = [
+                    (
+                        low,
+                        if start <= T::zero() {
+                            FeatureId {
+                                a: Feature::Vertex(0),
+                                b: Feature::Edge(0, 1),
+                            }
+                        } else {
+                            FeatureId {
+                                a: Feature::Edge(0, 1),
+                                b: Feature::Vertex(near),
+                            }
+                        },
+                    ),
+                    (
+                        high,
+                        if end >= len_a {
+                            FeatureId {
+                                a: Feature::Vertex(1),
+                                b: Feature::Edge(0, 1),
+                            }
+                        } else {
+                            FeatureId {
+                                a: Feature::Edge(0, 1),
+                                b: Feature::Vertex(far),
+                            }
+                        },
+                    ),
+                ];
+
+                let contacts: Vec<_> = ends
+                    .into_iter()
+                    .filter_map(|(t, id)| {
+                        let point_a = self.a + axis * t;
+                        let point_b = closest_point_on_segment(&other.a, &other.b, &point_a);
+
+                        contact(point_a, point_b, id)
+                    })
+                    .collect();
+
+                return oriented(normal, contacts, true);
+            }
+        }
+
+        let id = FeatureId {
+            a: segment_feature(&closest_a, &self.a, &self.b),
+            b: segment_feature(&closest_b, &other.a, &other.b),
+        };
+
+        oriented(
+            normal,
+            contact(closest_a, closest_b, id).into_iter().collect(),
+            true,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{aabb::Aabb, angle::Rad, quaternion::Quaternion, test_util::assert_close, vector};
+
+    fn sorted<const N: usize>(manifold: &Manifold<N, f64>) -> Vec<Vector<N, f64>> {
+        let mut points: Vec<_> = manifold.contacts.iter().map(|c| c.point).collect();
+        points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        points
+    }
+
+    #[test]
+    fn box_box() {
+        let ground = Obb::from_aabb(&Aabb::new(
+            vector!(-5.0, -1.0, -5.0),
+            vector!(5.0, 0.0, 5.0),
+        ));
+        let crate_ = Obb::from_aabb(&Aabb::new(
+            vector!(-1.0, -0.1, -1.0),
+            vector!(1.0, 1.9, 1.0),
+        ));
+
+        // Resting face down gives the four bottom corners
+        let manifold = crate_.contact_obb(&ground).unwrap();
+        assert_close(manifold.normal, vector!(0.0, -1.0, 0.0), 1e-9);
+        assert_eq!(manifold.contacts.len(), 4);
+        for contact in &manifold.contacts {
+            assert!((contact.depth - 0.1).abs() < 1e-9);
+            assert!((contact.point.y() + 0.05).abs() < 1e-9);
+            assert!(matches!(contact.id.a, Feature::Vertex(_)));
+            assert_eq!(contact.id.b, Feature::Face(3));
+        }
+
+        let flipped = ground.contact_obb(&crate_).unwrap();
+        assert_close(flipped.normal, vector!(0.0, 1.0, 0.0), 1e-9);
+        assert_eq!(sorted(&flipped), sorted(&manifold));
+
+        // Hanging over the edge, clipped to the ground's top face
+        let shifted = Obb::from_aabb(&Aabb::new(vector!(4.0, -0.1, -1.0), vector!(6.0, 1.9, 1.0)));
+        let manifold = shifted.contact_obb(&ground).unwrap();
+        assert_eq!(manifold.contacts.len(), 4);
+        assert!(manifold.contacts.iter().all(|c| c.point.x() <= 5.0 + 1e-9));
+        assert!(manifold
+            .contacts
+            .iter()
+            .any(|c| matches!(c.id.b, Feature::Edge(..))));
+
+        // Twisted a quarter turn about x, balancing on an edge
+        let edge = Obb::from_quaternion(
+            vector!(0.0, 2.0f64.sqrt() - 0.1, 0.0),
+            vector!(1.0, 1.0, 1.0),
+            &Quaternion::from_axis_angle(vector!(1.0, 0.0, 0.0), std::f64::consts::FRAC_PI_4),
+        );
+        let manifold = edge.contact_obb(&ground).unwrap();
+        assert_close(manifold.normal, vector!(0.0, -1.0, 0.0), 1e-9);
+        assert_eq!(manifold.contacts.len(), 2);
+        for contact in &manifold.contacts {
+            assert!((contact.depth - 0.1).abs() < 1e-9);
+            assert!((contact.point.z()).abs() < 1e-9);
+        }
+
+        let apart = Obb::from_aabb(&Aabb::new(vector!(-1.0, 0.1, -1.0), vector!(1.0, 2.1, 1.0)));
+        assert!(apart.contact_obb(&ground).is_none());
+    }
+
+    #[test]
+    fn box_plane() {
+        let plane = Plane::new(vector!(0.0, 1.0, 0.0), 0.0);
+
+        let obb = Obb::from_aabb(&Aabb::new(
+            vector!(-1.0, -0.2, -1.0),
+            vector!(1.0, 1.8, 1.0),
+        ));
+        let manifold = obb.contact_plane(&plane).unwrap();
+        assert_close(manifold.normal, vector!(0.0, -1.0, 0.0), 1e-9);
+        assert_eq!(manifold.contacts.len(), 4);
+        assert!(manifold
+            .contacts
+            .iter()
+            .all(|c| (c.depth - 0.2).abs() < 1e-9 && (c.point.y() + 0.1).abs() < 1e-9));
+
+        // All eight corners under, reduced to four spread out ones
+        let sunk = Obb::from_aabb(&Aabb::new(
+            vector!(-1.0, -3.0, -1.0),
+            vector!(1.0, -1.0, 1.0),
+        ));
+        let manifold = sunk.contact_plane(&plane).unwrap();
+        assert_eq!(manifold.contacts.len(), 4);
+        assert!((manifold.contacts[0].depth - 3.0).abs() < 1e-9);
+
+        assert!(
+            Obb::from_aabb(&Aabb::new(vector!(-1.0, 0.1, -1.0), vector!(1.0, 1.0, 1.0)))
+                .contact_plane(&plane)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn polygons() {
+        let ground = Obb2::from_angle(vector!(0.0, -1.0), vector!(5.0, 1.0), Rad(0.0));
+        let square = Obb2::from_angle(vector!(0.0, 0.9), vector!(1.0, 1.0), Rad(0.0));
+
+        let manifold = square.contact_obb(&ground).unwrap();
+        assert_close(manifold.normal, vector!(0.0, -1.0), 1e-9);
+        let points = sorted(&manifold);
+        assert_eq!(points.len(), 2);
+        assert_close(points[0], vector!(-1.0, -0.05), 1e-9);
+        assert_close(points[1], vector!(1.0, -0.05), 1e-9);
+        assert!(manifold
+            .contacts
+            .iter()
+            .all(|c| (c.depth - 0.1).abs() < 1e-9));
+
+        // Triangle poking its tip in gives a single point
+        let hull =
+            ConvexHull2::from_points(&[vector!(0.0, -0.2), vector!(1.0, 2.0), vector!(-1.0, 2.0)])
+                .unwrap();
+        let floor = ConvexHull2::from_points(&[
+            vector!(-5.0, -2.0),
+            vector!(5.0, -2.0),
+            vector!(5.0, 0.0),
+            vector!(-5.0, 0.0),
+        ])
+        .unwrap();
+        let manifold = hull.contact_hull(&floor).unwrap();
+        assert_close(manifold.normal, vector!(0.0, -1.0), 1e-9);
+        assert_eq!(manifold.contacts.len(), 1);
+        assert!((manifold.contacts[0].depth - 0.2).abs() < 1e-9);
+        assert_close(manifold.contacts[0].point, vector!(0.0, -0.1), 1e-9);
+
+        let above = Obb2::from_angle(vector!(0.0, 1.5), vector!(1.0, 1.0), Rad(0.0));
+        assert!(above.contact_obb(&ground).is_none());
+    }
+
+    #[test]
+    fn capsules() {
+        let a = Capsule::new(vector!(-2.0, 0.0, 0.0), vector!(2.0, 0.0, 0.0), 0.5);
+
+        // Lying side by side, touching along the overlap
+        let b = Capsule::new(vector!(1.0, 0.9, 0.0), vector!(4.0, 0.9, 0.0), 0.5);
+        let manifold = a.contact_capsule(&b).unwrap();
+        assert_close(manifold.normal, vector!(0.0, 1.0, 0.0), 1e-9);
+        let points = sorted(&manifold);
+        assert_eq!(points.len(), 2);
+        assert_close(points[0], vector!(1.0, 0.45, 0.0), 1e-9);
+        assert_close(points[1], vector!(2.0, 0.45, 0.0), 1e-9);
+        assert_ne!(manifold.contacts[0].id, manifold.contacts[1].id);
+        assert!(manifold
+            .contacts
+            .iter()
+            .all(|c| (c.depth - 0.1).abs() < 1e-9));
+
+        // Crossing ones touch at a single point
+        let c = Capsule::new(vector!(0.0, 0.9, -2.0), vector!(0.0, 0.9, 2.0), 0.5);
+        let manifold = a.contact_capsule(&c).unwrap();
+        assert_eq!(manifold.contacts.len(), 1);
+        assert_eq!(
+            manifold.contacts[0].id,
+            FeatureId {
+                a: Feature::Edge(0, 1),
+                b: Feature::Edge(0, 1)
+            }
+        );
+        assert_close(manifold.contacts[0].point, vector!(0.0, 0.45, 0.0), 1e-9);
+
+        let d = Capsule::new(vector!(2.5, 0.0, 0.0), vector!(4.0, 0.0, 0.0), 0.5);
+        let manifold = a.contact_capsule(&d).unwrap();
+        assert_eq!(manifold.contacts[0].id.a, Feature::Vertex(1));
+        assert_eq!(manifold.contacts[0].id.b, Feature::Vertex(0));
+
+        assert!(a
+            .contact_capsule(&Capsule::new(
+                vector!(0.0, 2.0, 0.0),
+                vector!(1.0, 2.0, 0.0),
+                0.5
+            ))
+            .is_none());
+    }
+
+    #[test]
+    fn cache() {
+        let ground = Obb::from_aabb(&Aabb::new(
+            vector!(-5.0, -1.0, -5.0),
+            vector!(5.0, 0.0, 5.0),
+        ));
+        let mut obb = Obb::from_aabb(&Aabb::new(
+            vector!(-1.0, -0.1, -1.0),
+            vector!(1.0, 1.9, 1.0),
+        ));
+
+        let mut cache = ManifoldCache::<f64>::new();
+        let manifold = obb.contact_obb(&ground).unwrap();
+        cache.update(&manifold);
+        assert_eq!(cache.len(), 4);
+        for contact in &manifold.contacts {
+            *cache.get_mut(&contact.id).unwrap() = contact.depth;
+        }
+
+        // Same features next frame keep their data
+        obb.center += vector!(0.01, 0.05, 0.0);
+        let manifold = obb.contact_obb(&ground).unwrap();
+        cache.update(&manifold);
+        assert_eq!(cache.len(), 4);
+        assert!(manifold
+            .contacts
+            .iter()
+            .all(|c| (cache.get(&c.id).unwrap() - 0.1).abs() < 1e-9));
+
+        // Tipped onto one of the corners, that one stays and the others are gone
+        let manifold = Manifold {
+            normal: vector!(0.0, -1.0, 0.0),
+            contacts: vec![Contact {
+                point: vector!(1.0, -0.05, 1.0),
+                depth: 0.1,
+                id: FeatureId {
+                    a: Feature::Vertex(5),
+                    b: Feature::Face(3),
+                },
+            }],
+        };
+        cache.update(&manifold);
+        assert_eq!(cache.len(), 1);
+        assert!((cache.get(&manifold.contacts[0].id).unwrap() - 0.1).abs() < 1e-9);
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+}
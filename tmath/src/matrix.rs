@@ -0,0 +1,462 @@
+use std::ops::{Add, Mul};
+
+use num_traits::{real::Real, One, Zero};
+
+use crate::vector::Vector;
+
+#[macro_export]
+macro_rules! matrix {
+    ($([$($member:expr),*]),*) => {
+        $crate::matrix::Matrix::new([$([$($member),*]),*])
+    };
+}
+
+pub type Matrix2<T> = Matrix<2, 2, T>;
+pub type Matrix3<T> = Matrix<3, 3, T>;
+pub type Matrix4<T> = Matrix<4, 4, T>;
+
+macro_rules! mat_types {
+    ($($n:literal),*) => {
+        paste::paste! {
+            $(
+                pub type [< Matrix $n F >] = [< Matrix $n >]<f32>;
+                pub type [< Matrix $n D >] = [< Matrix $n >]<f64>;
+            )*
+        }
+    };
+}
+
+mat_types!(2, 3, 4);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Matrix<const R: usize, const C: usize, T>(pub(crate) [Vector<C, T>; R]);
+
+impl<const R: usize, const C: usize, T> Default for Matrix<R, C, T>
+where
+    T: Default + Copy,
+{
+    fn default() -> Self {
+        Self([Vector::default(); R])
+    }
+}
+
+impl<const R: usize, const C: usize, T> Matrix<R, C, T> {
+    pub const ROWS: usize = R;
+    pub const COLUMNS: usize = C;
+
+    pub fn new(rows: [[T; C]; R]) -> Self {
+        Self(rows.map(Vector::new))
+    }
+
+    pub const fn from_rows(rows: [Vector<C, T>; R]) -> Self {
+        Self(rows)
+    }
+
+    pub fn from_columns(columns: [Vector<R, T>; C]) -> Self
+    where
+        T: Copy,
+    {
+        Matrix::<C, R, T>(columns).transpose()
+    }
+
+    pub fn row(&self, index: usize) -> Vector<C, T>
+    where
+        T: Copy,
+    {
+        self.0[index]
+    }
+
+    pub fn column(&self, index: usize) -> Vector<R, T>
+    where
+        T: Copy,
+    {
+        Vector::new(self.0.map(|row| row[index]))
+    }
+
+    pub fn transpose(&self) -> Matrix<C, R, T>
+    where
+        T: Copy,
+    {
+        Matrix(std::array::from_fn(|c| self.column(c)))
+    }
+}
+
+impl<const N: usize, T> Matrix<N, N, T> {
+    pub fn identity() -> Self
+    where
+        T: Copy + Zero + One,
+    {
+        Self(std::array::from_fn(|r| {
+            Vector::new(std::array::from_fn(|c| {
+                if r == c {
+                    T::one()
+                } else {
+                    T::zero()
+                }
+            }))
+        }))
+    }
+
+    pub fn diagonal(&self) -> Vector<N, T>
+    where
+        T: Copy,
+    {
+        Vector::new(std::array::from_fn(|i| self[i][i]))
+    }
+
+    pub fn determinant(&self) -> T
+    where
+        T: Default + Real,
+    {
+        let mut rows = self.0;
+        let mut det = T::one();
+        let tolerances = self.pivot_tolerances();
+
+        for col in 0..N {
+            let Some(pivot) = Self::pivot_row(&rows, col, tolerances[col]) else {
+                return T::zero();
+            };
+
+            if pivot != col {
+                rows.swap(pivot, col);
+                det = -det;
+            }
+
+            let pivot_val = rows[col][col];
+            det = det * pivot_val;
+
+            for row in col + 1..N {
+                let factor = rows[row][col] / pivot_val;
+                rows[row] = rows[row] - rows[col] * factor;
+            }
+        }
+
+        det
+    }
+
+    pub fn inverse(&self) -> Option<Self>
+    where
+        T: Default + Real,
+    {
+        let mut rows = self.0;
+        let mut inv = Self::identity().0;
+        let tolerances = self.pivot_tolerances();
+
+        for col in 0..N {
+            let pivot = Self::pivot_row(&rows, col, tolerances[col])?;
+
+            rows.swap(pivot, col);
+            inv.swap(pivot, col);
+
+            let pivot_val = rows[col][col];
+            rows[col] = rows[col] / pivot_val;
+            inv[col] = inv[col] / pivot_val;
+
+            for row in 0..N {
+                if row == col {
+                    continue;
+                }
+
+                let factor = rows[row][col];
+                rows[row] = rows[row] - rows[col] * factor;
+                inv[row] = inv[row] - inv[col] * factor;
+            }
+        }
+
+        Some(Self(inv))
+    }
+
+    // Eigenvalues and eigenvectors (as columns) of a symmetric matrix, by cyclic Jacobi rotations
+    pub fn symmetric_eigen(&self) -> (Vector<N, T>, Self)
+    where
+        T: Default + Real,
+    {
+        let mut a = self.0;
+        let mut vectors = Self::identity().0;
+        let scale = a
+            .iter()
+            .fold(T::zero(), |sum, row| sum + row.length_squared());
+
+        for _ in 0..64 {
+            let off_diagonal = (0..N).fold(T::zero(), |sum, p| {
+                (p + 1..N).fold(sum, |sum, q| sum + a[p][q] * a[p][q])
+            });
+
+            if off_diagonal <= scale * T::epsilon() * T::epsilon() {
+                break;
+            }
+
+            for p in 0..N {
+                for q in p + 1..N {
+                    if a[p][q] == T::zero() {
+                        continue;
+                    }
+
+                    let theta = (a[q][q] - a[p][p]) / (a[p][q] + a[p][q]);
+                    let t = theta.signum() / (theta.abs() + (theta * theta + T::one()).sqrt());
+                    let c = T::one() / (t * t + T::one()).sqrt();
+                    let s = t * c;
+
+                    for row in a.iter_mut() {
+                        let (kp, kq) = (row[p], row[q]);
+                        row[p] = c * kp - s * kq;
+                        row[q] = s * kp + c * kq;
+                    }
+
+                    let (row_p, row_q) = (a[p], a[q]);
+                    a[p] = row_p * c - row_q * s;
+                    a[q] = row_p * s + row_q * c;
+
+                    for row in vectors.iter_mut() {
+                        let (kp, kq) = (row[p], row[q]);
+                        row[p] = c * kp - s * kq;
+                        row[q] = s * kp + c * kq;
+                    }
+                }
+            }
+        }
+
+        (Self(a).diagonal(), Self(vectors))
+    }
+
+    // Pivots this much smaller than the largest entry of their column are taken as zero. Scaling
+    // a column scales everything eliminated in it the same, so the cutoff follows along and
+    // matrices with tiny but independent columns (e.g. projections over huge extents) still invert
+    fn pivot_tolerances(&self) -> Vector<N, T>
+    where
+        T: Real,
+    {
+        let scale = T::epsilon() * T::from(N).unwrap();
+
+        Vector::new(std::array::from_fn(|col| {
+            self.0
+                .iter()
+                .fold(T::zero(), |largest, row| largest.max(row[col].abs()))
+                * scale
+        }))
+    }
+
+    fn pivot_row(rows: &[Vector<N, T>; N], col: usize, tolerance: T) -> Option<usize>
+    where
+        T: Real,
+    {
+        (col..N)
+            .map(|row| (row, rows[row][col].abs()))
+            .filter(|(_, val)| *val > tolerance)
+            .fold(None, |best: Option<(usize, T)>, (row, val)| match best {
+                Some((_, best_val)) if best_val >= val => best,
+                _ => Some((row, val)),
+            })
+            .map(|(row, _)| row)
+    }
+}
+
+impl<T> Matrix3<T> {
+    pub fn rotation_x(angle: T) -> Self
+    where
+        T: Real,
+    {
+        let (sin, cos) = angle.sin_cos();
+        let (zero, one) = (T::zero(), T::one());
+
+        Self::new([[one, zero, zero], [zero, cos, -sin], [zero, sin, cos]])
+    }
+
+    pub fn rotation_y(angle: T) -> Self
+    where
+        T: Real,
+    {
+        let (sin, cos) = angle.sin_cos();
+        let (zero, one) = (T::zero(), T::one());
+
+        Self::new([[cos, zero, sin], [zero, one, zero], [-sin, zero, cos]])
+    }
+
+    pub fn rotation_z(angle: T) -> Self
+    where
+        T: Real,
+    {
+        let (sin, cos) = angle.sin_cos();
+        let (zero, one) = (T::zero(), T::one());
+
+        Self::new([[cos, -sin, zero], [sin, cos, zero], [zero, zero, one]])
+    }
+}
+
+mod indexing {
+    use super::*;
+
+    use std::ops::{Index, IndexMut};
+
+    impl<const R: usize, const C: usize, T> Index<usize> for Matrix<R, C, T> {
+        type Output = Vector<C, T>;
+
+        fn index(&self, index: usize) -> &Self::Output {
+            &self.0[index]
+        }
+    }
+
+    impl<const R: usize, const C: usize, T> IndexMut<usize> for Matrix<R, C, T> {
+        fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+            &mut self.0[index]
+        }
+    }
+}
+
+mod ops {
+    use super::*;
+
+    impl<const R: usize, const C: usize, T> Mul<Vector<C, T>> for Matrix<R, C, T>
+    where
+        T: Default + Copy + Add<Output = T> + Mul<Output = T>,
+    {
+        type Output = Vector<R, T>;
+
+        fn mul(self, rhs: Vector<C, T>) -> Self::Output {
+            Vector::new(self.0.map(|row| row.dot(&rhs)))
+        }
+    }
+
+    impl<const R: usize, const C: usize, T> Mul<Vector<C, T>> for &Matrix<R, C, T>
+    where
+        T: Default + Copy + Add<Output = T> + Mul<Output = T>,
+    {
+        type Output = Vector<R, T>;
+
+        fn mul(self, rhs: Vector<C, T>) -> Self::Output {
+            (*self).mul(rhs)
+        }
+    }
+
+    impl<const R: usize, const C: usize, const K: usize, T> Mul<Matrix<C, K, T>> for Matrix<R, C, T>
+    where
+        T: Default + Copy + Add<Output = T> + Mul<Output = T>,
+    {
+        type Output = Matrix<R, K, T>;
+
+        fn mul(self, rhs: Matrix<C, K, T>) -> Self::Output {
+            let columns = rhs.transpose();
+
+            Matrix(
+                self.0
+                    .map(|row| Vector::new(columns.0.map(|col| row.dot(&col)))),
+            )
+        }
+    }
+
+    impl<'b, const R: usize, const C: usize, const K: usize, T> Mul<&'b Matrix<C, K, T>>
+        for &Matrix<R, C, T>
+    where
+        T: Default + Copy + Add<Output = T> + Mul<Output = T>,
+    {
+        type Output = Matrix<R, K, T>;
+
+        fn mul(self, rhs: &'b Matrix<C, K, T>) -> Self::Output {
+            (*self).mul(*rhs)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::vector;
+
+    #[test]
+    fn matrix_macro() {
+        let m: Matrix<2, 3, i32> = matrix!([1, 2, 3], [4, 5, 6]);
+
+        assert_eq!(m[0], vector!(1, 2, 3));
+        assert_eq!(m[1], vector!(4, 5, 6));
+        assert_eq!(m.column(1), vector!(2, 5));
+    }
+
+    #[test]
+    fn transpose() {
+        let m: Matrix<2, 3, i32> = matrix!([1, 2, 3], [4, 5, 6]);
+
+        assert_eq!(m.transpose(), matrix!([1, 4], [2, 5], [3, 6]));
+        assert_eq!(
+            Matrix::from_columns([vector!(1, 4), vector!(2, 5), vector!(3, 6)]),
+            m
+        );
+    }
+
+    #[test]
+    fn multiplication() {
+        let a: Matrix<2, 3, i32> = matrix!([1, 2, 3], [4, 5, 6]);
+        let b: Matrix<3, 2, i32> = matrix!([7, 8], [9, 10], [11, 12]);
+
+        assert_eq!(a * b, matrix!([58, 64], [139, 154]));
+        assert_eq!(a * vector!(1, 0, -1), vector!(-2, -2));
+        assert_eq!(Matrix3::identity() * b, b);
+    }
+
+    #[test]
+    fn determinant() {
+        let m: Matrix3D = matrix!([2.0, 0.0, 1.0], [1.0, 3.0, 2.0], [1.0, 1.0, 2.0]);
+
+        assert!((m.determinant() - 6.0).abs() < 1e-9);
+        assert_eq!(Matrix2D::default().determinant(), 0.0);
+
+        // Small but far from singular
+        let small: Matrix3D = matrix!([1e-8, 0.0, 0.0], [0.0, 1e-8, 0.0], [0.0, 0.0, 1e-8]);
+        assert!((small.determinant() - 1e-24).abs() < 1e-33);
+    }
+
+    #[test]
+    fn inverse() {
+        let m: Matrix4D = matrix!(
+            [4.0, 7.0, 2.0, 3.0],
+            [0.0, 5.0, 0.0, 1.0],
+            [1.0, 0.0, 3.0, 0.0],
+            [2.0, 1.0, 0.0, 6.0]
+        );
+        let identity = m * m.inverse().unwrap();
+
+        for r in 0..4 {
+            for c in 0..4 {
+                let target = if r == c { 1.0 } else { 0.0 };
+                assert!((identity[r][c] - target).abs() < 1e-9);
+            }
+        }
+
+        assert!(Matrix2D::new([[1.0, 2.0], [2.0, 4.0]]).inverse().is_none());
+        assert!(Matrix2D::new([[1e-8, 2e-8], [2e-8, 4e-8]])
+            .inverse()
+            .is_none());
+
+        // Orthographic projection over a 1e8 wide area, with the translation dwarfing the scale
+        let ortho: Matrix4<f32> = matrix!(
+            [2e-8, 0.0, 0.0, -0.5],
+            [0.0, 2e-8, 0.0, 0.25],
+            [0.0, 0.0, -1e-3, -1.0],
+            [0.0, 0.0, 0.0, 1.0]
+        );
+        let identity = ortho * ortho.inverse().unwrap();
+
+        for r in 0..4 {
+            for c in 0..4 {
+                let target = if r == c { 1.0 } else { 0.0 };
+                assert!((identity[r][c] - target).abs() < 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn symmetric_eigen() {
+        let m: Matrix3D = matrix!([4.0, 1.0, -2.0], [1.0, 2.0, 0.0], [-2.0, 0.0, 3.0]);
+        let (values, vectors) = m.symmetric_eigen();
+
+        for i in 0..3 {
+            let v = vectors.column(i);
+
+            assert!((v.length() - 1.0).abs() < 1e-9);
+            assert!((m * v - v * values[i]).length() < 1e-9);
+        }
+
+        let trace = values[0] + values[1] + values[2];
+        assert!((trace - 9.0).abs() < 1e-9);
+    }
+}
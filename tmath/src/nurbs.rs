@@ -0,0 +1,742 @@
+use num_traits::real::Real;
+
+use crate::{
+    curve::Curve,
+    vector::{Vector, Vector3},
+};
+
+pub type NurbsCurve2<T> = NurbsCurve<2, T>;
+pub type NurbsCurve3<T> = NurbsCurve<3, T>;
+
+macro_rules! nurbs_types {
+    ($($n:literal),*) => {
+        paste::paste! {
+            $(
+                pub type [< NurbsCurve $n F >] = [< NurbsCurve $n >]<f32>;
+                pub type [< NurbsCurve $n D >] = [< NurbsCurve $n >]<f64>;
+            )*
+        }
+    };
+}
+
+nurbs_types!(2, 3);
+
+pub type NurbsSurfaceF = NurbsSurface<f32>;
+pub type NurbsSurfaceD = NurbsSurface<f64>;
+
+// Knots are nondecreasing, one more than there are points plus the degree, and every weight is
+// positive
+fn valid<T>(degree: usize, count: usize, knots: &[T], weights: &[T]) -> bool
+where
+    T: Real,
+{
+    degree >= 1
+        && count > degree
+        && weights.len() == count
+        && weights.iter().all(|&w| w > T::zero())
+        && knots.len() == count + degree + 1
+        && knots.windows(2).all(|w| w[0] <= w[1])
+        && knots[degree] < knots[count]
+}
+
+// Index of the nonempty knot span holding t, clamped to the domain
+fn find_span<T>(knots: &[T], degree: usize, count: usize, t: T) -> usize
+where
+    T: Real,
+{
+    let t = t.max(knots[degree]).min(knots[count]);
+
+    (knots.partition_point(|&knot| knot <= t) - 1).clamp(degree, count - 1)
+}
+
+// Values of the degree + 1 basis functions nonzero on the span and their derivatives up to order,
+// ders[k][j] being the k-th derivative of function span - degree + j (The NURBS Book, A2.3)
+fn basis_derivatives<T>(knots: &[T], degree: usize, span: usize, t: T, order: usize) -> Vec<Vec<T>>
+where
+    T: Real,
+{
+    let p = degree;
+    let mut ndu = vec![vec![T::zero(); p + 1]; p + 1];
+    let mut left = vec![T::zero(); p + 1];
+    let mut right = vec![T::zero(); p + 1];
+    ndu[0][0] = T::one();
+
+    for j in 1..=p {
+        left[j] = t - knots[span + 1 - j];
+        right[j] = knots[span + j] - t;
+
+        let mut saved = T::zero();
+        for r in 0..j {
+            // Lower triangle holds the knot differences, upper the basis values
+            ndu[j][r] = right[r + 1] + left[j - r];
+            let temp = ndu[r][j - 1] / ndu[j][r];
+
+            ndu[r][j] = saved + right[r + 1] * temp;
+            saved = left[j - r] * temp;
+        }
+        ndu[j][j] = saved;
+    }
+
+    let mut ders = vec![vec![T::zero(); p + 1]; order + 1];
+    for j in 0..=p {
+        ders[0][j] = ndu[j][p];
+    }
+
+    // Past the degree every derivative is zero
+    let order = order.min(p);
+    let mut a = [vec![T::zero(); p + 1], vec![T::zero(); p + 1]];
+
+    for r in 0..=p {
+        let (mut s1, mut s2) = (0, 1);
+        a[0][0] = T::one();
+
+        for k in 1..=order {
+            let mut d = T::zero();
+            let rk = r as isize - k as isize;
+            let pk = p - k;
+
+            if r >= k {
+                a[s2][0] = a[s1][0] / ndu[pk + 1][rk as usize];
+                d = a[s2][0] * ndu[rk as usize][pk];
+            }
+
+            let j1 = if rk >= -1 { 1 } else { (-rk) as usize };
+            let j2 = if r <= pk + 1 { k - 1 } else { p - r };
+            for j in j1..=j2 {
+                let index = (rk + j as isize) as usize;
+                a[s2][j] = (a[s1][j] - a[s1][j - 1]) / ndu[pk + 1][index];
+                d = d + a[s2][j] * ndu[index][pk];
+            }
+
+            if r <= pk {
+                a[s2][k] = -a[s1][k - 1] / ndu[pk + 1][r];
+                d = d + a[s2][k] * ndu[r][pk];
+            }
+
+            ders[k][r] = d;
+            std::mem::swap(&mut s1, &mut s2);
+        }
+    }
+
+    let mut factor = T::from(p).unwrap();
+    for (k, row) in ders.iter_mut().enumerate().skip(1).take(order) {
+        for value in row.iter_mut() {
+            *value = *value * factor;
+        }
+        factor = factor * T::from(p - k).unwrap();
+    }
+
+    ders
+}
+
+// Point multiplied by its weight, along with the weight
+type Homogeneous<const N: usize, T> = (Vector<N, T>, T);
+
+// Boehm's algorithm. None if t is outside of the domain or already there degree times
+fn insert_knot<const N: usize, T>(
+    degree: usize,
+    knots: &[T],
+    points: &[Homogeneous<N, T>],
+    t: T,
+) -> Option<(Vec<T>, Vec<Homogeneous<N, T>>)>
+where
+    T: Real,
+{
+    let (p, count) = (degree, points.len());
+    if !(knots[p] < t && t < knots[count]) {
+        return None;
+    }
+
+    let k = knots.partition_point(|&knot| knot <= t) - 1;
+    let multiplicity = knots.iter().filter(|&&knot| knot == t).count();
+    if multiplicity >= p {
+        return None;
+    }
+
+    let mut inserted = Vec::with_capacity(count + 1);
+    inserted.extend_from_slice(&points[..=k - p]);
+    for j in k - p + 1..=k - multiplicity {
+        let alpha = (t - knots[j]) / (knots[j + p] - knots[j]);
+        let ((a, wa), (b, wb)) = (points[j - 1], points[j]);
+
+        inserted.push((
+            a * (T::one() - alpha) + b * alpha,
+            wa * (T::one() - alpha) + wb * alpha,
+        ));
+    }
+    inserted.extend_from_slice(&points[k - multiplicity..]);
+
+    let mut new_knots = knots.to_vec();
+    new_knots.insert(k + 1, t);
+
+    Some((new_knots, inserted))
+}
+
+// Parameters spread evenly over the domain, count of them including both ends
+fn samples<T>((start, end): (T, T), count: usize) -> impl Iterator<Item = T>
+where
+    T: Real,
+{
+    let last = T::from(count.saturating_sub(1).max(1)).unwrap();
+
+    (0..count).map(move |i| start + (end - start) * T::from(i).unwrap() / last)
+}
+
+// Rational B-spline, each point pulling the curve towards it by its weight. Runs from
+// knots[degree] to knots[points.len()]
+#[derive(Debug, Clone, PartialEq)]
+pub struct NurbsCurve<const N: usize, T> {
+    degree: usize,
+    points: Vec<Vector<N, T>>,
+    weights: Vec<T>,
+    knots: Vec<T>,
+}
+
+impl<const N: usize, T> NurbsCurve<N, T> {
+    // None if the knots or weights don't fit the points or there aren't more points than the degree
+    pub fn new(
+        degree: usize,
+        points: Vec<Vector<N, T>>,
+        weights: Vec<T>,
+        knots: Vec<T>,
+    ) -> Option<Self>
+    where
+        T: Real,
+    {
+        valid(degree, points.len(), &knots, &weights).then_some(Self {
+            degree,
+            points,
+            weights,
+            knots,
+        })
+    }
+
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    pub fn points(&self) -> &[Vector<N, T>] {
+        &self.points
+    }
+
+    pub fn weights(&self) -> &[T] {
+        &self.weights
+    }
+
+    pub fn knots(&self) -> &[T] {
+        &self.knots
+    }
+
+    // The point and its derivatives up to order
+    pub fn derivatives(&self, t: T, order: usize) -> Vec<Vector<N, T>>
+    where
+        T: Real,
+    {
+        let (p, count) = (self.degree, self.points.len());
+        let span = find_span(&self.knots, p, count, t);
+        let t = t.max(self.knots[p]).min(self.knots[count]);
+        let basis = basis_derivatives(&self.knots, p, span, t, order);
+
+        // Derivatives of the weighted sum of points and of the weights
+        let (a, w): (Vec<_>, Vec<_>) = basis
+            .iter()
+            .map(|row| {
+                row.iter().enumerate().fold(
+                    (Vector::new_val(T::zero()), T::zero()),
+                    |(a, w), (j, &n)| {
+                        let i = span - p + j;
+                        let weight = n * self.weights[i];
+
+                        (a + self.points[i] * weight, w + weight)
+                    },
+                )
+            })
+            .unzip();
+
+        // Quotient rule: C(k) = (A(k) - sum over i of binomial(k, i) w(i) C(k - i)) / w
+        let mut ders: Vec<Vector<N, T>> = Vec::with_capacity(order + 1);
+        for k in 0..=order {
+            let mut binomial = T::one();
+            let mut v = a[k];
+
+            for i in 1..=k {
+                binomial = binomial * T::from(k - i + 1).unwrap() / T::from(i).unwrap();
+                v = v - ders[k - i] * (binomial * w[i]);
+            }
+
+            ders.push(v / w[0]);
+        }
+
+        ders
+    }
+
+    // Same curve with t added to the knots and one more point. False if t is outside of the domain
+    // or already there as many times as the degree
+    pub fn insert_knot(&mut self, t: T) -> bool
+    where
+        T: Real,
+    {
+        let homogeneous: Vec<_> = self
+            .points
+            .iter()
+            .zip(&self.weights)
+            .map(|(&p, &w)| (p * w, w))
+            .collect();
+
+        let Some((knots, inserted)) = insert_knot(self.degree, &self.knots, &homogeneous, t) else {
+            return false;
+        };
+
+        self.knots = knots;
+        (self.points, self.weights) = inserted.into_iter().map(|(p, w)| (p / w, w)).unzip();
+
+        true
+    }
+
+    // Points at count parameters spread evenly over the domain
+    pub fn tessellate(&self, count: usize) -> Vec<Vector<N, T>>
+    where
+        T: Real,
+    {
+        samples(self.domain(), count)
+            .map(|t| self.derivatives(t, 0)[0])
+            .collect()
+    }
+}
+
+impl<const N: usize, T> Curve<N, T> for NurbsCurve<N, T>
+where
+    T: Real,
+{
+    fn domain(&self) -> (T, T) {
+        (self.knots[self.degree], self.knots[self.points.len()])
+    }
+
+    fn point(&self, t: T) -> Vector<N, T> {
+        self.derivatives(t, 0)[0]
+    }
+
+    fn derivative(&self, t: T) -> Vector<N, T> {
+        self.derivatives(t, 1)[1]
+    }
+
+    fn second_derivative(&self, t: T) -> Vector<N, T> {
+        self.derivatives(t, 2)[2]
+    }
+
+    fn third_derivative(&self, t: T) -> Vector<N, T> {
+        self.derivatives(t, 3)[3]
+    }
+}
+
+// Tensor product of two NURBS curves, points[i][j] being the i-th along u and j-th along v
+#[derive(Debug, Clone, PartialEq)]
+pub struct NurbsSurface<T> {
+    degree: (usize, usize),
+    points: Vec<Vec<Vector3<T>>>,
+    weights: Vec<Vec<T>>,
+    knots: (Vec<T>, Vec<T>),
+}
+
+impl<T> NurbsSurface<T> {
+    // None if the grid isn't rectangular or the knots or weights don't fit it
+    pub fn new(
+        degree: (usize, usize),
+        points: Vec<Vec<Vector3<T>>>,
+        weights: Vec<Vec<T>>,
+        knots: (Vec<T>, Vec<T>),
+    ) -> Option<Self>
+    where
+        T: Real,
+    {
+        let count_v = points.first().map_or(0, |row| row.len());
+        let flat: Vec<_> = weights.iter().flatten().copied().collect();
+
+        let rectangular = weights.len() == points.len()
+            && points.iter().all(|row| row.len() == count_v)
+            && weights.iter().all(|row| row.len() == count_v);
+
+        let valid = rectangular
+            && valid(
+                degree.0,
+                points.len(),
+                &knots.0,
+                &vec![T::one(); points.len()],
+            )
+            && valid(degree.1, count_v, &knots.1, &flat[..count_v])
+            && flat.iter().all(|&w| w > T::zero());
+
+        valid.then_some(Self {
+            degree,
+            points,
+            weights,
+            knots,
+        })
+    }
+
+    pub fn degree(&self) -> (usize, usize) {
+        self.degree
+    }
+
+    pub fn points(&self) -> &[Vec<Vector3<T>>] {
+        &self.points
+    }
+
+    pub fn weights(&self) -> &[Vec<T>] {
+        &self.weights
+    }
+
+    pub fn knots(&self) -> (&[T], &[T]) {
+        (&self.knots.0, &self.knots.1)
+    }
+
+    pub fn domain_u(&self) -> (T, T)
+    where
+        T: Copy,
+    {
+        (self.knots.0[self.degree.0], self.knots.0[self.points.len()])
+    }
+
+    pub fn domain_v(&self) -> (T, T)
+    where
+        T: Copy,
+    {
+        (
+            self.knots.1[self.degree.1],
+            self.knots.1[self.points[0].len()],
+        )
+    }
+
+    // The point and its first derivatives along u and along v
+    pub fn derivatives(&self, u: T, v: T) -> [Vector3<T>; 3]
+    where
+        T: Real,
+    {
+        let (pu, pv) = self.degree;
+        let (count_u, count_v) = (self.points.len(), self.points[0].len());
+
+        let (span_u, span_v) = (
+            find_span(&self.knots.0, pu, count_u, u),
+            find_span(&self.knots.1, pv, count_v, v),
+        );
+        let u = u.max(self.knots.0[pu]).min(self.knots.0[count_u]);
+        let v = v.max(self.knots.1[pv]).min(self.knots.1[count_v]);
+        let (nu, nv) = (
+            basis_derivatives(&self.knots.0, pu, span_u, u, 1),
+            basis_derivatives(&self.knots.1, pv, span_v, v, 1),
+        );
+
+        // Weighted sums for the point, d/du and d/dv
+        let mut a = [Vector::new_val(T::zero()); 3];
+        let mut w = [T::zero(); 3];
+
+        for (k, (du, dv)) in [(0, 0), (1, 0), (0, 1)].into_iter().enumerate() {
+            for (i, &bu) in nu[du].iter().enumerate() {
+                for (j, &bv) in nv[dv].iter().enumerate() {
+                    let (row, column) = (span_u - pu + i, span_v - pv + j);
+                    let weight = bu * bv * self.weights[row][column];
+
+                    a[k] = a[k] + self.points[row][column] * weight;
+                    w[k] = w[k] + weight;
+                }
+            }
+        }
+
+        let point = a[0] / w[0];
+
+        [
+            point,
+            (a[1] - point * w[1]) / w[0],
+            (a[2] - point * w[2]) / w[0],
+        ]
+    }
+
+    pub fn point(&self, u: T, v: T) -> Vector3<T>
+    where
+        T: Real,
+    {
+        self.derivatives(u, v)[0]
+    }
+
+    // Unit normal along du × dv, zero where the surface is degenerate
+    pub fn normal(&self, u: T, v: T) -> Vector3<T>
+    where
+        T: Default + Real,
+    {
+        let [_, du, dv] = self.derivatives(u, v);
+        let normal = du.cross(&dv);
+        let length = normal.length();
+
+        if length > T::zero() {
+            normal / length
+        } else {
+            normal
+        }
+    }
+
+    fn homogeneous(&self) -> Vec<Vec<Homogeneous<3, T>>>
+    where
+        T: Real,
+    {
+        self.points
+            .iter()
+            .zip(&self.weights)
+            .map(|(points, weights)| {
+                points
+                    .iter()
+                    .zip(weights)
+                    .map(|(&p, &w)| (p * w, w))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn set_homogeneous(&mut self, grid: Vec<Vec<Homogeneous<3, T>>>)
+    where
+        T: Real,
+    {
+        (self.points, self.weights) = grid
+            .into_iter()
+            .map(|row| row.into_iter().map(|(p, w)| (p / w, w)).unzip())
+            .unzip();
+    }
+
+    // Adds a knot along u, a row of points more. False if it's outside of the domain or already
+    // there as many times as the degree
+    pub fn insert_knot_u(&mut self, u: T) -> bool
+    where
+        T: Real,
+    {
+        let grid = self.homogeneous();
+        let count_v = grid[0].len();
+
+        let mut knots = None;
+        let mut columns = Vec::with_capacity(count_v);
+        for j in 0..count_v {
+            let column: Vec<_> = grid.iter().map(|row| row[j]).collect();
+            let Some((new_knots, inserted)) = insert_knot(self.degree.0, &self.knots.0, &column, u)
+            else {
+                return false;
+            };
+
+            knots = Some(new_knots);
+            columns.push(inserted);
+        }
+
+        let rows = (0..columns[0].len())
+            .map(|i| columns.iter().map(|column| column[i]).collect())
+            .collect();
+
+        self.knots.0 = knots.unwrap_or_default();
+        self.set_homogeneous(rows);
+
+        true
+    }
+
+    // Adds a knot along v, a column of points more
+    pub fn insert_knot_v(&mut self, v: T) -> bool
+    where
+        T: Real,
+    {
+        let mut knots = None;
+        let mut rows = Vec::with_capacity(self.points.len());
+        for row in self.homogeneous() {
+            let Some((new_knots, inserted)) = insert_knot(self.degree.1, &self.knots.1, &row, v)
+            else {
+                return false;
+            };
+
+            knots = Some(new_knots);
+            rows.push(inserted);
+        }
+
+        self.knots.1 = knots.unwrap_or_default();
+        self.set_homogeneous(rows);
+
+        true
+    }
+
+    // Grid of points at parameters spread evenly over the domain, [i][j] at the i-th u and j-th v
+    pub fn tessellate(&self, count_u: usize, count_v: usize) -> Vec<Vec<Vector3<T>>>
+    where
+        T: Real,
+    {
+        samples(self.domain_u(), count_u)
+            .map(|u| {
+                samples(self.domain_v(), count_v)
+                    .map(|v| self.point(u, v))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{test_util::assert_close, vector};
+
+    // Unit circle from 9 points, each quarter a rational quadratic
+    fn circle() -> NurbsCurve2D {
+        let s = std::f64::consts::FRAC_1_SQRT_2;
+
+        NurbsCurve::new(
+            2,
+            vec![
+                vector!(1.0, 0.0),
+                vector!(1.0, 1.0),
+                vector!(0.0, 1.0),
+                vector!(-1.0, 1.0),
+                vector!(-1.0, 0.0),
+                vector!(-1.0, -1.0),
+                vector!(0.0, -1.0),
+                vector!(1.0, -1.0),
+                vector!(1.0, 0.0),
+            ],
+            vec![1.0, s, 1.0, s, 1.0, s, 1.0, s, 1.0],
+            vec![0.0, 0.0, 0.0, 1.0, 1.0, 2.0, 2.0, 3.0, 3.0, 4.0, 4.0, 4.0],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn curve() {
+        assert!(
+            NurbsCurve2D::new(2, vec![vector!(0.0, 0.0); 3], vec![1.0; 3], vec![0.0; 5]).is_none()
+        );
+        assert!(NurbsCurve2D::new(
+            1,
+            vec![vector!(0.0, 0.0); 2],
+            vec![1.0, 0.0],
+            vec![0.0, 0.0, 1.0, 1.0]
+        )
+        .is_none());
+
+        let circle = circle();
+        assert_eq!(circle.domain(), (0.0, 4.0));
+
+        let points = circle.tessellate(41);
+        assert_eq!(points.len(), 41);
+        for p in &points {
+            assert!((p.length() - 1.0).abs() < 1e-12);
+        }
+        assert_close(circle.point(1.0), vector!(0.0, 1.0), 1e-6);
+        assert_close(circle.point(2.0), vector!(-1.0, 0.0), 1e-6);
+
+        // Against central differences
+        let h = 1e-5;
+        for t in [0.3, 1.2, 2.5, 3.9] {
+            let d1 = (circle.point(t + h) - circle.point(t - h)) / (2.0 * h);
+            let d2 = (circle.derivative(t + h) - circle.derivative(t - h)) / (2.0 * h);
+            assert!((circle.derivative(t) - d1).length() < 1e-5);
+            assert!((circle.second_derivative(t) - d2).length() < 1e-5);
+
+            // Tangent to the circle
+            assert!(circle.derivative(t).dot(&circle.point(t)).abs() < 1e-9);
+        }
+
+        let derivatives = circle.derivatives(0.5, 4);
+        assert_eq!(derivatives.len(), 5);
+        assert_close(derivatives[0], circle.point(0.5), 1e-6);
+    }
+
+    #[test]
+    fn curve_knot_insertion() {
+        let original = circle();
+        let mut circle = original.clone();
+
+        assert!(circle.insert_knot(0.5));
+        assert!(circle.insert_knot(2.25));
+        assert!(circle.insert_knot(2.25));
+        assert_eq!(circle.points().len(), 12);
+        assert_eq!(circle.knots().len(), 15);
+
+        // Knots already there as often as the degree, or outside of the curve, can't be added
+        assert!(!circle.insert_knot(1.0));
+        assert!(!circle.insert_knot(2.25));
+        assert!(!circle.insert_knot(4.0));
+        assert!(!circle.insert_knot(-1.0));
+
+        for t in [0.1, 0.5, 0.7, 1.6, 2.25, 3.3] {
+            assert_close(circle.point(t), original.point(t), 1e-6);
+            assert_close(circle.derivative(t), original.derivative(t), 1e-6);
+        }
+    }
+
+    // Quarter of a cylinder of radius 1 and height 2, along the arc in u and up the axis in v
+    fn cylinder() -> NurbsSurfaceD {
+        let s = std::f64::consts::FRAC_1_SQRT_2;
+        let arc = [vector!(1.0, 0.0), vector!(1.0, 1.0), vector!(0.0, 1.0)];
+
+        NurbsSurface::new(
+            (2, 1),
+            arc.iter()
+                .map(|p| vec![vector!(p[0], p[1], 0.0), vector!(p[0], p[1], 2.0)])
+                .collect(),
+            vec![vec![1.0, 1.0], vec![s, s], vec![1.0, 1.0]],
+            (vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0], vec![0.0, 0.0, 1.0, 1.0]),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn surface() {
+        let cylinder = cylinder();
+        assert_eq!(cylinder.domain_u(), (0.0, 1.0));
+        assert_eq!(cylinder.domain_v(), (0.0, 1.0));
+
+        let grid = cylinder.tessellate(9, 5);
+        assert_eq!(grid.len(), 9);
+        for (i, row) in grid.iter().enumerate() {
+            assert_eq!(row.len(), 5);
+            for (j, p) in row.iter().enumerate() {
+                assert!((vector!(p[0], p[1]).length() - 1.0).abs() < 1e-12);
+                assert!((p[2] - j as f64 / 2.0).abs() < 1e-12);
+                if j > 0 {
+                    assert_close(*p - vector!(0.0, 0.0, p[2]), grid[i][0], 1e-6);
+                }
+            }
+        }
+
+        // Normals point out of the cylinder
+        for (u, v) in [(0.0, 0.0), (0.3, 0.5), (1.0, 1.0)] {
+            let p = cylinder.point(u, v);
+            assert_close(cylinder.normal(u, v), vector!(p[0], p[1], 0.0), 1e-6);
+        }
+
+        let h = 1e-6;
+        let [_, du, dv] = cylinder.derivatives(0.4, 0.6);
+        assert_close(
+            du,
+            (cylinder.point(0.4 + h, 0.6) - cylinder.point(0.4 - h, 0.6)) / (2.0 * h),
+            1e-6,
+        );
+        assert_close(
+            dv,
+            (cylinder.point(0.4, 0.6 + h) - cylinder.point(0.4, 0.6 - h)) / (2.0 * h),
+            1e-6,
+        );
+
+        let mut refined = cylinder.clone();
+        assert!(refined.insert_knot_u(0.3));
+        assert!(refined.insert_knot_v(0.5));
+        assert!(!refined.insert_knot_v(0.5));
+        assert_eq!(refined.points().len(), 4);
+        assert!(refined.points().iter().all(|row| row.len() == 3));
+        for (u, v) in [(0.1, 0.2), (0.3, 0.5), (0.8, 0.9)] {
+            assert_close(refined.point(u, v), cylinder.point(u, v), 1e-6);
+        }
+
+        assert!(NurbsSurfaceD::new(
+            (1, 1),
+            vec![
+                vec![vector!(0.0, 0.0, 0.0); 2],
+                vec![vector!(0.0, 0.0, 0.0)]
+            ],
+            vec![vec![1.0; 2]; 2],
+            (vec![0.0, 0.0, 1.0, 1.0], vec![0.0, 0.0, 1.0, 1.0]),
+        )
+        .is_none());
+    }
+}
@@ -0,0 +1,469 @@
+use num_traits::real::Real;
+
+use crate::{
+    aabb::{Aabb, Aabb2, Aabb3},
+    angle::Rad,
+    matrix::Matrix,
+    quaternion::Quaternion,
+    triangle::{Triangle2, Triangle3},
+    vector::{Vector, Vector2, Vector3},
+};
+
+pub type Obb2<T> = Obb<2, T>;
+pub type Obb3<T> = Obb<3, T>;
+
+macro_rules! obb_types {
+    ($($n:literal),*) => {
+        paste::paste! {
+            $(
+                pub type [< Obb $n F >] = [< Obb $n >]<f32>;
+                pub type [< Obb $n D >] = [< Obb $n >]<f64>;
+            )*
+        }
+    };
+}
+
+obb_types!(2, 3);
+
+// Normal is the axis of least overlap, pointing from the first shape towards the second
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Penetration<const N: usize, T> {
+    pub normal: Vector<N, T>,
+    pub depth: T,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Obb<const N: usize, T> {
+    pub center: Vector<N, T>,
+    pub half_extents: Vector<N, T>,
+    // Columns are the box's local axes in world space
+    pub rotation: Matrix<N, N, T>,
+}
+
+// Separating axis test over the candidate axes, each shape gives its interval along an axis.
+// Expects unit length inputs to the axes, near zero ones come from parallel edges and are skipped
+fn separating_axes<const N: usize, T, I, A, B>(
+    axes: I,
+    interval_a: A,
+    interval_b: B,
+) -> Option<Penetration<N, T>>
+where
+    T: Default + Real,
+    I: IntoIterator<Item = Vector<N, T>>,
+    A: Fn(&Vector<N, T>) -> (T, T),
+    B: Fn(&Vector<N, T>) -> (T, T),
+{
+    let mut best: Option<Penetration<N, T>> = None;
+
+    for axis in axes {
+        let len = axis.length();
+        if len <= T::epsilon().sqrt() {
+            continue;
+        }
+
+        let axis = axis / len;
+        let (min_a, max_a) = interval_a(&axis);
+        let (min_b, max_b) = interval_b(&axis);
+        let depth = (max_a - min_b).min(max_b - min_a);
+
+        if depth < T::zero() {
+            return None;
+        }
+
+        if best.is_none_or(|best| depth < best.depth) {
+            let normal = if min_b + max_b < min_a + max_a {
+                -axis
+            } else {
+                axis
+            };
+
+            best = Some(Penetration { normal, depth });
+        }
+    }
+
+    best
+}
+
+fn vertices_interval<const N: usize, T>(vertices: &[Vector<N, T>; 3], axis: &Vector<N, T>) -> (T, T)
+where
+    T: Default + Real,
+{
+    let first = vertices[0].dot(axis);
+
+    vertices[1..]
+        .iter()
+        .fold((first, first), |(min, max), vertex| {
+            let val = vertex.dot(axis);
+
+            (min.min(val), max.max(val))
+        })
+}
+
+impl<const N: usize, T> Obb<N, T> {
+    pub const fn new(
+        center: Vector<N, T>,
+        half_extents: Vector<N, T>,
+        rotation: Matrix<N, N, T>,
+    ) -> Self {
+        Self {
+            center,
+            half_extents,
+            rotation,
+        }
+    }
+
+    pub fn from_aabb(aabb: &Aabb<N, T>) -> Self
+    where
+        T: Real,
+    {
+        Self::new(aabb.center(), aabb.half_extents(), Matrix::identity())
+    }
+
+    // Axes along the principal components of the points, None if there are none
+    pub fn from_points(points: &[Vector<N, T>]) -> Option<Self>
+    where
+        T: Default + Real,
+    {
+        if points.is_empty() {
+            return None;
+        }
+
+        let count = T::from(points.len()).unwrap();
+        let mean = points
+            .iter()
+            .fold(Vector::new_val(T::zero()), |sum, point| sum + point)
+            / count;
+
+        let mut covariance = Matrix::<N, N, T>::default();
+        for point in points {
+            let offset = *point - mean;
+
+            for (i, row) in covariance.0.iter_mut().enumerate() {
+                *row = *row + offset * offset[i];
+            }
+        }
+
+        // Keep it a rotation rather than a reflection
+        let (_, mut rotation) = covariance.symmetric_eigen();
+        if rotation.determinant() < T::zero() {
+            for row in rotation.0.iter_mut() {
+                row[N - 1] = -row[N - 1];
+            }
+        }
+
+        let inverse = rotation.transpose();
+        let local = Aabb::from_points(points.iter().map(|point| inverse * *point))?;
+
+        Some(Self::new(
+            rotation * local.center(),
+            local.half_extents(),
+            rotation,
+        ))
+    }
+
+    pub fn axis(&self, index: usize) -> Vector<N, T>
+    where
+        T: Copy,
+    {
+        self.rotation.column(index)
+    }
+
+    // Point in the box's frame, with the center at the origin
+    pub fn to_local(&self, point: &Vector<N, T>) -> Vector<N, T>
+    where
+        T: Default + Real,
+    {
+        self.rotation.transpose() * (*point - self.center)
+    }
+
+    pub fn contains_point(&self, point: &Vector<N, T>) -> bool
+    where
+        T: Default + Real,
+    {
+        let local = self.to_local(point);
+
+        (0..N).all(|i| local[i].abs() <= self.half_extents[i])
+    }
+
+    pub fn closest_point(&self, point: &Vector<N, T>) -> Vector<N, T>
+    where
+        T: Default + Real,
+    {
+        let local = self.to_local(point);
+        let clamped = local.max(&-self.half_extents).min(&self.half_extents);
+
+        self.center + self.rotation * clamped
+    }
+
+    pub fn bounding_box(&self) -> Aabb<N, T>
+    where
+        T: Default + Real,
+    {
+        let abs = Matrix(
+            self.rotation
+                .0
+                .map(|row| Vector::new(row.0.map(|val| val.abs()))),
+        );
+
+        Aabb::from_center_half_extents(self.center, abs * self.half_extents)
+    }
+
+    // Projection of the box onto a unit axis
+    fn interval(&self, axis: &Vector<N, T>) -> (T, T)
+    where
+        T: Default + Real,
+    {
+        let center = self.center.dot(axis);
+        let radius = (0..N).fold(T::zero(), |sum, i| {
+            sum + self.half_extents[i] * self.axis(i).dot(axis).abs()
+        });
+
+        (center - radius, center + radius)
+    }
+}
+
+impl<T> Obb2<T> {
+    pub fn from_angle<A>(center: Vector2<T>, half_extents: Vector2<T>, angle: A) -> Self
+    where
+        A: Into<Rad<T>>,
+        T: Real,
+    {
+        let (s, c) = angle.into().sin_cos();
+
+        Self::new(center, half_extents, Matrix::new([[c, -s], [s, c]]))
+    }
+
+    pub fn intersect_obb(&self, other: &Self) -> Option<Penetration<2, T>>
+    where
+        T: Default + Real,
+    {
+        let axes = [self.axis(0), self.axis(1), other.axis(0), other.axis(1)];
+
+        separating_axes(
+            axes,
+            |axis| self.interval(axis),
+            |axis| other.interval(axis),
+        )
+    }
+
+    pub fn intersect_aabb(&self, aabb: &Aabb2<T>) -> Option<Penetration<2, T>>
+    where
+        T: Default + Real,
+    {
+        self.intersect_obb(&Obb::from_aabb(aabb))
+    }
+
+    pub fn intersect_triangle(&self, triangle: &Triangle2<T>) -> Option<Penetration<2, T>>
+    where
+        T: Default + Real,
+    {
+        let vertices = triangle.vertices();
+        let edge_normals = [0, 1, 2].map(|i| {
+            let edge = (vertices[(i + 1) % 3] - vertices[i]).normalized();
+
+            Vector2::new([-edge.y(), edge.x()])
+        });
+        let axes = [self.axis(0), self.axis(1)].into_iter().chain(edge_normals);
+
+        separating_axes(
+            axes,
+            |axis| self.interval(axis),
+            |axis| vertices_interval(&vertices, axis),
+        )
+    }
+}
+
+impl<T> Obb3<T> {
+    pub fn from_quaternion(
+        center: Vector3<T>,
+        half_extents: Vector3<T>,
+        rotation: &Quaternion<T>,
+    ) -> Self
+    where
+        T: Real,
+    {
+        Self::new(center, half_extents, rotation.to_rotation_matrix())
+    }
+
+    pub fn intersect_obb(&self, other: &Self) -> Option<Penetration<3, T>>
+    where
+        T: Default + Real,
+    {
+        let own = [0, 1, 2].map(|i| self.axis(i));
+        let others = [0, 1, 2].map(|i| other.axis(i));
+        let edges = own.into_iter().flat_map(|a| others.map(|b| a.cross(&b)));
+
+        separating_axes(
+            own.into_iter().chain(others).chain(edges),
+            |axis| self.interval(axis),
+            |axis| other.interval(axis),
+        )
+    }
+
+    pub fn intersect_aabb(&self, aabb: &Aabb3<T>) -> Option<Penetration<3, T>>
+    where
+        T: Default + Real,
+    {
+        self.intersect_obb(&Obb::from_aabb(aabb))
+    }
+
+    pub fn intersect_triangle(&self, triangle: &Triangle3<T>) -> Option<Penetration<3, T>>
+    where
+        T: Default + Real,
+    {
+        let vertices = triangle.vertices();
+        let own = [0, 1, 2].map(|i| self.axis(i));
+        let edges = [0, 1, 2].map(|i| (vertices[(i + 1) % 3] - vertices[i]).normalized());
+        let crosses = own
+            .into_iter()
+            .flat_map(|a| edges.map(|edge| a.cross(&edge)));
+        let axes = own.into_iter().chain([triangle.normal()]).chain(crosses);
+
+        separating_axes(
+            axes,
+            |axis| self.interval(axis),
+            |axis| vertices_interval(&vertices, axis),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::f64::consts::FRAC_PI_4;
+
+    use crate::{angle::DegD, test_util::assert_close, vector};
+
+    #[test]
+    fn queries() {
+        let obb = Obb2D::from_angle(vector!(1.0, 1.0), vector!(2.0, 1.0), DegD::new(90.0));
+
+        assert_close(obb.axis(0), vector!(0.0, 1.0), 1e-9);
+        assert!(obb.contains_point(&vector!(1.5, 2.5)));
+        assert!(!obb.contains_point(&vector!(2.5, 1.0)));
+        assert_close(
+            obb.closest_point(&vector!(5.0, 1.0)),
+            vector!(2.0, 1.0),
+            1e-9,
+        );
+        assert_close(
+            obb.closest_point(&vector!(5.0, 5.0)),
+            vector!(2.0, 3.0),
+            1e-9,
+        );
+        assert_close(obb.to_local(&vector!(1.0, 3.0)), vector!(2.0, 0.0), 1e-9);
+
+        let aabb = obb.bounding_box();
+        assert_close(aabb.min, vector!(0.0, -1.0), 1e-9);
+        assert_close(aabb.max, vector!(2.0, 3.0), 1e-9);
+    }
+
+    #[test]
+    fn from_points() {
+        let obb = Obb3D::from_quaternion(
+            vector!(1.0, 2.0, 3.0),
+            vector!(4.0, 2.0, 1.0),
+            &Quaternion::from_axis_angle(vector!(1.0, 1.0, 0.0).normalized(), 0.7),
+        );
+        let corners = Aabb::new(-obb.half_extents, obb.half_extents)
+            .corners()
+            .map(|corner| obb.center + obb.rotation * corner);
+
+        let fitted = Obb::from_points(&corners).unwrap();
+        assert_close(fitted.center, obb.center, 1e-9);
+        assert!(fitted.rotation.determinant() > 0.0);
+        for corner in corners {
+            assert!(Aabb::new(-fitted.half_extents, fitted.half_extents)
+                .expand(1e-9)
+                .contains_point(&fitted.to_local(&corner)));
+        }
+
+        let volume = |obb: &Obb3D| obb.half_extents.0.iter().product::<f64>();
+        assert!((volume(&fitted) - volume(&obb)).abs() < 1e-6);
+
+        assert!(Obb3D::from_points(&[]).is_none());
+    }
+
+    #[test]
+    fn obb_obb() {
+        let a = Obb2D::from_aabb(&Aabb::new(vector!(-1.0, -1.0), vector!(1.0, 1.0)));
+
+        // Diamond whose left tip pokes 0.1 into the square
+        let half = std::f64::consts::SQRT_2 / 2.0;
+        let b = Obb2D::from_angle(vector!(1.9, 0.0), vector!(half, half), Rad(FRAC_PI_4));
+        let hit = a.intersect_obb(&b).unwrap();
+        assert!((hit.depth - 0.1).abs() < 1e-9);
+        assert_close(hit.normal, vector!(1.0, 0.0), 1e-9);
+
+        let apart = Obb2D::new(vector!(2.1, 0.0), b.half_extents, b.rotation);
+        assert!(a.intersect_obb(&apart).is_none());
+
+        // Boxes only separable along an edge-edge cross product
+        let x = Obb3D::new(
+            vector!(0.0, 0.0, 0.0),
+            vector!(3.0, 0.1, 0.1),
+            Matrix::rotation_x(FRAC_PI_4),
+        );
+        let z = Obb3D::new(
+            vector!(0.0, 0.3, 0.0),
+            vector!(0.1, 0.1, 3.0),
+            Matrix::rotation_z(FRAC_PI_4),
+        );
+        assert!(x.intersect_obb(&z).is_none());
+
+        let z = Obb3D::new(vector!(0.0, 0.2, 0.0), z.half_extents, z.rotation);
+        let hit = x.intersect_obb(&z).unwrap();
+        assert!(hit.normal.y() > 0.0);
+
+        let aabb = Aabb::new(vector!(2.5, -1.0, -1.0), vector!(4.0, 1.0, 1.0));
+        let hit = x.intersect_aabb(&aabb).unwrap();
+        assert_close(hit.normal, vector!(1.0, 0.0, 0.0), 1e-9);
+        assert!((hit.depth - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn obb_triangle() {
+        let obb = Obb3D::from_aabb(&Aabb::new(
+            vector!(-1.0, -1.0, -1.0),
+            vector!(1.0, 1.0, 1.0),
+        ));
+
+        let triangle = Triangle3::new(
+            vector!(-5.0, 0.8, -5.0),
+            vector!(5.0, 0.8, -5.0),
+            vector!(0.0, 0.8, 5.0),
+        );
+        let hit = obb.intersect_triangle(&triangle).unwrap();
+        assert_close(hit.normal, vector!(0.0, 1.0, 0.0), 1e-9);
+        assert!((hit.depth - 0.2).abs() < 1e-9);
+
+        let triangle = Triangle3::new(
+            vector!(2.0, 0.0, 0.0),
+            vector!(0.0, 2.0, 0.0),
+            vector!(0.0, 0.0, 2.0),
+        );
+        let hit = obb.intersect_triangle(&triangle).unwrap();
+        assert_close(hit.normal, vector!(1.0, 1.0, 1.0).normalized(), 1e-9);
+        assert!((hit.depth - 1.0 / 3.0_f64.sqrt()).abs() < 1e-9);
+
+        let triangle = Triangle3::new(
+            vector!(4.0, 0.0, 0.0),
+            vector!(0.0, 4.0, 0.0),
+            vector!(0.0, 0.0, 4.0),
+        );
+        assert!(obb.intersect_triangle(&triangle).is_none());
+
+        let square = Obb2D::from_angle(vector!(0.0, 0.0), vector!(1.0, 1.0), Rad(FRAC_PI_4));
+        let triangle = Triangle2::new(vector!(1.2, 0.0), vector!(3.0, -1.0), vector!(3.0, 1.0));
+        let hit = square.intersect_triangle(&triangle).unwrap();
+        assert!(hit.normal.x() > 0.0 && hit.depth > 0.0);
+        assert!(square
+            .intersect_triangle(&Triangle2::new(
+                vector!(1.5, 0.0),
+                vector!(3.0, -1.0),
+                vector!(3.0, 1.0)
+            ))
+            .is_none());
+    }
+}
@@ -0,0 +1,587 @@
+use num_traits::real::Real;
+
+use crate::{aabb::Aabb, bvh::slab_entry, ray::Ray};
+
+pub type Quadtree<T, V> = Orthtree<2, T, V>;
+pub type Octree<T, V> = Orthtree<3, T, V>;
+
+macro_rules! orthtree_types {
+    ($($name:ident),*) => {
+        paste::paste! {
+            $(
+                pub type [< $name F >]<V> = $name<f32, V>;
+                pub type [< $name D >]<V> = $name<f64, V>;
+            )*
+        }
+    };
+}
+
+orthtree_types!(Quadtree, Octree);
+
+#[derive(Debug, Clone)]
+struct Node<const N: usize, T> {
+    bounds: Aabb<N, T>,
+    depth: usize,
+    parent: Option<usize>,
+    // First of the 2^N consecutive children
+    children: Option<usize>,
+    items: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+struct Item<const N: usize, T, V> {
+    bounds: Aabb<N, T>,
+    value: V,
+    node: usize,
+}
+
+// Quadtree in 2D, octree in 3D. Each item sits in the deepest node whose loose bounds, the node's
+// cell scaled up by the looseness around its center, hold it whole. A looseness of one gives the
+// classic tree, larger ones let moving items stay put for longer. Items reaching outside the root
+// are kept at the root
+#[derive(Debug, Clone)]
+pub struct Orthtree<const N: usize, T, V> {
+    nodes: Vec<Node<N, T>>,
+    free_nodes: Vec<usize>,
+    items: Vec<Option<Item<N, T, V>>>,
+    free: Vec<usize>,
+    max_depth: usize,
+    bucket_size: usize,
+    looseness: T,
+}
+
+impl<const N: usize, T, V> Orthtree<N, T, V> {
+    // Leaves holding more than bucket_size items are split until max_depth is reached
+    pub fn new(bounds: Aabb<N, T>, max_depth: usize, bucket_size: usize) -> Self
+    where
+        T: Real,
+    {
+        Self::loose(bounds, max_depth, bucket_size, T::one())
+    }
+
+    pub fn loose(bounds: Aabb<N, T>, max_depth: usize, bucket_size: usize, looseness: T) -> Self
+    where
+        T: Real,
+    {
+        assert!(looseness >= T::one(), "looseness can't be below one");
+
+        Self {
+            nodes: vec![Node {
+                bounds,
+                depth: 0,
+                parent: None,
+                children: None,
+                items: Vec::new(),
+            }],
+            free_nodes: Vec::new(),
+            items: Vec::new(),
+            free: Vec::new(),
+            max_depth,
+            bucket_size,
+            looseness,
+        }
+    }
+
+    pub fn bounds(&self) -> Aabb<N, T>
+    where
+        T: Copy,
+    {
+        self.nodes[0].bounds
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len() - self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, id: usize) -> Option<(&Aabb<N, T>, &V)> {
+        self.items
+            .get(id)?
+            .as_ref()
+            .map(|item| (&item.bounds, &item.value))
+    }
+
+    pub fn get_mut(&mut self, id: usize) -> Option<&mut V> {
+        self.items.get_mut(id)?.as_mut().map(|item| &mut item.value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &Aabb<N, T>, &V)> {
+        self.items
+            .iter()
+            .enumerate()
+            .filter_map(|(id, item)| item.as_ref().map(|item| (id, &item.bounds, &item.value)))
+    }
+
+    fn loose_bounds(&self, node: usize) -> Aabb<N, T>
+    where
+        T: Real,
+    {
+        let bounds = self.nodes[node].bounds;
+
+        if self.looseness == T::one() {
+            bounds
+        } else {
+            Aabb::from_center_half_extents(bounds.center(), bounds.half_extents() * self.looseness)
+        }
+    }
+
+    // Child of an interior node the item would move down into, if it fits there
+    fn child_for(&self, node: usize, bounds: &Aabb<N, T>) -> Option<usize>
+    where
+        T: Real,
+    {
+        let first = self.nodes[node].children?;
+        let (center, item_center) = (self.nodes[node].bounds.center(), bounds.center());
+        let child = first
+            + (0..N)
+                .filter(|&i| item_center[i] >= center[i])
+                .fold(0, |index, i| index | 1 << i);
+
+        self.loose_bounds(child).contains(bounds).then_some(child)
+    }
+
+    fn allocate_children(&mut self, node: usize) -> usize
+    where
+        T: Real,
+    {
+        let (bounds, depth) = (self.nodes[node].bounds, self.nodes[node].depth);
+        let center = bounds.center();
+
+        let children = (0..1 << N).map(|index| {
+            let mut child = bounds;
+            for i in 0..N {
+                if index & 1 << i != 0 {
+                    child.min[i] = center[i];
+                } else {
+                    child.max[i] = center[i];
+                }
+            }
+
+            Node {
+                bounds: child,
+                depth: depth + 1,
+                parent: Some(node),
+                children: None,
+                items: Vec::new(),
+            }
+        });
+
+        match self.free_nodes.pop() {
+            Some(first) => {
+                for (i, child) in children.enumerate() {
+                    self.nodes[first + i] = child;
+                }
+                first
+            }
+            None => {
+                let first = self.nodes.len();
+                self.nodes.extend(children);
+                first
+            }
+        }
+    }
+
+    // Splits an overfull leaf, pushing down whatever fits into the children
+    fn split(&mut self, node: usize)
+    where
+        T: Real,
+    {
+        let leaf = &self.nodes[node];
+        if leaf.children.is_some()
+            || leaf.items.len() <= self.bucket_size
+            || leaf.depth >= self.max_depth
+        {
+            return;
+        }
+
+        let first = self.allocate_children(node);
+        self.nodes[node].children = Some(first);
+
+        for id in std::mem::take(&mut self.nodes[node].items) {
+            let bounds = self.items[id].as_ref().map(|item| item.bounds);
+            let target = bounds
+                .and_then(|bounds| self.child_for(node, &bounds))
+                .unwrap_or(node);
+
+            self.link(id, target);
+        }
+
+        for child in first..first + (1 << N) {
+            self.split(child);
+        }
+    }
+
+    fn link(&mut self, id: usize, node: usize) {
+        self.nodes[node].items.push(id);
+        if let Some(item) = &mut self.items[id] {
+            item.node = node;
+        }
+    }
+
+    // Deepest node from start down the item fits in, splitting the leaf it lands in if needed
+    fn place(&mut self, id: usize, bounds: &Aabb<N, T>, start: usize)
+    where
+        T: Real,
+    {
+        let mut node = start;
+        while let Some(child) = self.child_for(node, bounds) {
+            node = child;
+        }
+
+        self.link(id, node);
+        self.split(node);
+    }
+
+    pub fn insert(&mut self, bounds: Aabb<N, T>, value: V) -> usize
+    where
+        T: Real,
+    {
+        let item = Some(Item {
+            bounds,
+            value,
+            node: 0,
+        });
+
+        let id = match self.free.pop() {
+            Some(id) => {
+                self.items[id] = item;
+                id
+            }
+            None => {
+                self.items.push(item);
+                self.items.len() - 1
+            }
+        };
+
+        self.place(id, &bounds, 0);
+
+        id
+    }
+
+    // Folds the children back into their parent once they're all leaves holding no more than a
+    // bucket together, walking up from node
+    fn collapse(&mut self, node: usize) {
+        let mut current = match self.nodes[node].children {
+            Some(_) => Some(node),
+            None => self.nodes[node].parent,
+        };
+
+        while let Some(node) = current {
+            let Some(first) = self.nodes[node].children else {
+                break;
+            };
+
+            let children = first..first + (1 << N);
+            let count = children
+                .clone()
+                .map(|child| self.nodes[child].items.len())
+                .sum::<usize>()
+                + self.nodes[node].items.len();
+            if count > self.bucket_size
+                || children
+                    .clone()
+                    .any(|child| self.nodes[child].children.is_some())
+            {
+                break;
+            }
+
+            for child in children {
+                for id in std::mem::take(&mut self.nodes[child].items) {
+                    self.link(id, node);
+                }
+            }
+
+            self.nodes[node].children = None;
+            self.free_nodes.push(first);
+            current = self.nodes[node].parent;
+        }
+    }
+
+    fn unlink(&mut self, id: usize, node: usize) {
+        self.nodes[node].items.retain(|&other| other != id);
+    }
+
+    pub fn remove(&mut self, id: usize) -> Option<(Aabb<N, T>, V)> {
+        let item = self.items.get_mut(id)?.take()?;
+
+        self.unlink(id, item.node);
+        self.collapse(item.node);
+        self.free.push(id);
+
+        Some((item.bounds, item.value))
+    }
+
+    // Stays in the same node while it still fits and can't move further down, which happens a lot
+    // more in loose trees. False if there's no such item
+    pub fn update(&mut self, id: usize, bounds: Aabb<N, T>) -> bool
+    where
+        T: Real,
+    {
+        let Some(Some(item)) = self.items.get_mut(id) else {
+            return false;
+        };
+
+        item.bounds = bounds;
+        let node = item.node;
+
+        let fits = node == 0 || self.loose_bounds(node).contains(&bounds);
+        if fits && self.child_for(node, &bounds).is_none() {
+            return true;
+        }
+
+        self.unlink(id, node);
+
+        // Climb back up to the first node that holds it, or the root
+        let mut start = node;
+        while let Some(parent) = self.nodes[start].parent {
+            start = parent;
+            if self.loose_bounds(start).contains(&bounds) {
+                break;
+            }
+        }
+
+        self.place(id, &bounds, start);
+        self.collapse(node);
+
+        true
+    }
+
+    // Ids of the items with bounds overlapping the region
+    pub fn query_aabb(&self, region: &Aabb<N, T>) -> Vec<usize>
+    where
+        T: Real,
+    {
+        let mut res = Vec::new();
+        let mut stack = vec![0];
+
+        while let Some(node) = stack.pop() {
+            // Anything outside the root bounds is at the root, which is always visited
+            if node != 0 && !self.loose_bounds(node).intersects(region) {
+                continue;
+            }
+
+            res.extend(self.nodes[node].items.iter().copied().filter(|&id| {
+                self.items[id]
+                    .as_ref()
+                    .is_some_and(|item| item.bounds.intersects(region))
+            }));
+
+            if let Some(first) = self.nodes[node].children {
+                stack.extend(first..first + (1 << N));
+            }
+        }
+
+        res
+    }
+
+    // Ids of the items with bounds the ray passes through within max_distance, paired with where it
+    // enters them and nearest first
+    pub fn query_ray(&self, ray: &Ray<N, T>, max_distance: T) -> Vec<(usize, T)>
+    where
+        T: Real,
+    {
+        let mut res = Vec::new();
+        let mut stack = vec![0];
+
+        while let Some(node) = stack.pop() {
+            if node != 0 && slab_entry(ray, &self.loose_bounds(node), max_distance).is_none() {
+                continue;
+            }
+
+            res.extend(self.nodes[node].items.iter().filter_map(|&id| {
+                let item = self.items[id].as_ref()?;
+                slab_entry(ray, &item.bounds, max_distance).map(|t| (id, t))
+            }));
+
+            if let Some(first) = self.nodes[node].children {
+                stack.extend(first..first + (1 << N));
+            }
+        }
+
+        res.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        res
+    }
+
+    // Closest item the ray hits within max_distance, intersect gives the distance to an item along
+    // the ray if it's hit
+    pub fn cast_ray<F>(
+        &self,
+        ray: &Ray<N, T>,
+        max_distance: T,
+        mut intersect: F,
+    ) -> Option<(usize, T)>
+    where
+        T: Real,
+        F: FnMut(usize) -> Option<T>,
+    {
+        let mut best: Option<(usize, T)> = None;
+        let mut stack = vec![(0, T::zero())];
+
+        while let Some((node, entry)) = stack.pop() {
+            let limit = best.map_or(max_distance, |(_, t)| t);
+            if entry > limit {
+                continue;
+            }
+
+            for &id in &self.nodes[node].items {
+                let limit = best.map_or(max_distance, |(_, t)| t);
+                let Some(item) = &self.items[id] else {
+                    continue;
+                };
+                if slab_entry(ray, &item.bounds, limit).is_none() {
+                    continue;
+                }
+
+                if let Some(t) = intersect(id).filter(|&t| t >= T::zero() && t <= limit) {
+                    best = Some((id, t));
+                }
+            }
+
+            if let Some(first) = self.nodes[node].children {
+                let limit = best.map_or(max_distance, |(_, t)| t);
+
+                // Nearest child on top of the stack
+                let mut children: Vec<_> = (first..first + (1 << N))
+                    .filter_map(|child| {
+                        slab_entry(ray, &self.loose_bounds(child), limit).map(|t| (child, t))
+                    })
+                    .collect();
+                children.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+                stack.extend(children);
+            }
+        }
+
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::{vector, vector::Vector};
+
+    fn boxes(count: usize, seed: u64) -> Vec<Aabb<3, f64>> {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        (0..count)
+            .map(|_| {
+                let center = Vector::new(std::array::from_fn(|_| rng.gen_range(-10.0..10.0)));
+                let half = Vector::new(std::array::from_fn(|_| rng.gen_range(0.01..1.0)));
+
+                Aabb::from_center_half_extents(center, half)
+            })
+            .collect()
+    }
+
+    fn sorted(mut ids: Vec<usize>) -> Vec<usize> {
+        ids.sort_unstable();
+        ids
+    }
+
+    fn check(mut tree: OctreeD<usize>) {
+        let mut bounds = boxes(500, 1);
+        let ids: Vec<_> = bounds
+            .iter()
+            .enumerate()
+            .map(|(i, b)| tree.insert(*b, i))
+            .collect();
+        assert_eq!(ids, (0..500).collect::<Vec<_>>());
+        assert!(tree.nodes.len() > 1);
+
+        // Shuffle half of them around, some outside the root, and drop a few
+        let mut rng = StdRng::seed_from_u64(2);
+        for id in (0..500).step_by(2) {
+            let offset = Vector::new(std::array::from_fn(|_| rng.gen_range(-3.0..3.0)));
+            bounds[id] = Aabb::new(bounds[id].min + offset, bounds[id].max + offset);
+            assert!(tree.update(id, bounds[id]));
+        }
+        for id in (0..500).step_by(7) {
+            assert_eq!(tree.remove(id), Some((bounds[id], id)));
+        }
+        assert_eq!(tree.len(), 500 - 72);
+
+        let alive = |id: &usize| !id.is_multiple_of(7);
+
+        for region in boxes(30, 3).iter().map(|b| b.expand(2.0)) {
+            let expected: Vec<_> = (0..500)
+                .filter(|id| alive(id) && bounds[*id].intersects(&region))
+                .collect();
+            assert_eq!(sorted(tree.query_aabb(&region)), expected);
+        }
+
+        for (from, to) in boxes(30, 4).iter().zip(boxes(30, 5).iter()) {
+            let ray = Ray::through(from.min * 1.5, to.max * 1.5);
+
+            let hits = tree.query_ray(&ray, 20.0);
+            let expected: Vec<_> = (0..500)
+                .filter(|id| alive(id) && slab_entry(&ray, &bounds[*id], 20.0).is_some())
+                .collect();
+            assert_eq!(sorted(hits.iter().map(|(id, _)| *id).collect()), expected);
+            assert!(hits.windows(2).all(|w| w[0].1 <= w[1].1));
+
+            // Treating the boxes as the shapes, the closest is the first entered
+            let closest = tree.cast_ray(&ray, 20.0, |id| slab_entry(&ray, &bounds[id], 20.0));
+            assert_eq!(closest.map(|(_, t)| t), hits.first().map(|(_, t)| *t));
+        }
+
+        for id in 0..500 {
+            tree.remove(id);
+        }
+        assert!(tree.is_empty());
+        assert_eq!(tree.nodes.len() - tree.free_nodes.len() * 8, 1);
+        assert!(tree.nodes[0].children.is_none());
+    }
+
+    #[test]
+    fn octree() {
+        let bounds = Aabb::new(vector!(-10.0, -10.0, -10.0), vector!(10.0, 10.0, 10.0));
+
+        check(Octree::new(bounds, 6, 4));
+        check(Octree::loose(bounds, 6, 4, 2.0));
+    }
+
+    #[test]
+    fn quadtree() {
+        let mut tree = QuadtreeF::new(Aabb::new(vector!(0.0, 0.0), vector!(8.0, 8.0)), 3, 1);
+
+        let a = tree.insert(Aabb::new(vector!(1.0, 1.0), vector!(1.5, 1.5)), "a");
+        let b = tree.insert(Aabb::new(vector!(5.0, 5.0), vector!(5.5, 5.5)), "b");
+        // Straddles the middle, so stays at the root
+        let c = tree.insert(Aabb::new(vector!(3.0, 3.0), vector!(5.0, 5.0)), "c");
+        assert_eq!(tree.items[c].as_ref().unwrap().node, 0);
+        assert_eq!(tree.nodes[tree.items[a].as_ref().unwrap().node].depth, 1);
+
+        let region = Aabb::new(vector!(0.0, 0.0), vector!(2.0, 2.0));
+        assert_eq!(tree.query_aabb(&region), [a]);
+
+        let ray = Ray::new(vector!(0.0, 0.0), vector!(1.0, 1.0).normalized());
+        let hits: Vec<_> = tree
+            .query_ray(&ray, 100.0)
+            .iter()
+            .map(|(id, _)| *id)
+            .collect();
+        assert_eq!(hits, [a, c, b]);
+        assert_eq!(
+            tree.cast_ray(&ray, 100.0, |id| (id != a).then_some(1.0)),
+            Some((c, 1.0))
+        );
+
+        // Moving a small item over the edge of its cell in a loose tree doesn't relocate it
+        let mut loose =
+            QuadtreeF::loose(Aabb::new(vector!(0.0, 0.0), vector!(8.0, 8.0)), 3, 0, 2.0);
+        let d = loose.insert(Aabb::new(vector!(3.0, 3.0), vector!(3.5, 3.5)), "d");
+        let node = loose.items[d].as_ref().unwrap().node;
+        assert_ne!(node, 0);
+        assert!(loose.update(d, Aabb::new(vector!(3.9, 3.0), vector!(4.4, 3.5))));
+        assert!(loose.nodes[node].items.contains(&d));
+        assert_eq!(loose.get(d).unwrap().0.min, vector!(3.9, 3.0));
+        assert!(!loose.update(7, Aabb::new(vector!(0.0, 0.0), vector!(1.0, 1.0))));
+    }
+}
@@ -0,0 +1,394 @@
+use num_traits::{real::Real, One, Zero};
+
+use crate::{
+    matrix::Matrix3,
+    vector::{Vector3, Vector4},
+};
+
+pub type QuaternionF = Quaternion<f32>;
+pub type QuaternionD = Quaternion<f64>;
+
+// Stored as [x, y, z, w] where w is the scalar part
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Quaternion<T>(pub(crate) Vector4<T>);
+
+impl<T> Default for Quaternion<T>
+where
+    T: Copy + Zero + One,
+{
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl<T> Quaternion<T> {
+    pub const fn new(x: T, y: T, z: T, w: T) -> Self {
+        Self(Vector4::new([x, y, z, w]))
+    }
+
+    pub fn from_parts(vector: Vector3<T>, scalar: T) -> Self
+    where
+        T: Copy,
+    {
+        Self(Vector4::from((vector, scalar)))
+    }
+
+    pub fn identity() -> Self
+    where
+        T: Copy + Zero + One,
+    {
+        Self::new(T::zero(), T::zero(), T::zero(), T::one())
+    }
+
+    pub fn x(&self) -> T
+    where
+        T: Copy,
+    {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> T
+    where
+        T: Copy,
+    {
+        self.0[1]
+    }
+
+    pub fn z(&self) -> T
+    where
+        T: Copy,
+    {
+        self.0[2]
+    }
+
+    pub fn w(&self) -> T
+    where
+        T: Copy,
+    {
+        self.0[3]
+    }
+
+    pub fn vector(&self) -> Vector3<T>
+    where
+        T: Copy,
+    {
+        Vector3::from(self.0)
+    }
+
+    pub fn scalar(&self) -> T
+    where
+        T: Copy,
+    {
+        self.w()
+    }
+
+    pub fn as_vector(&self) -> &Vector4<T> {
+        &self.0
+    }
+
+    pub fn from_axis_angle(axis: Vector3<T>, angle: T) -> Self
+    where
+        T: Default + Real,
+    {
+        let half = angle / (T::one() + T::one());
+
+        Self::from_parts(axis.normalized() * half.sin(), half.cos())
+    }
+
+    pub fn to_axis_angle(&self) -> (Vector3<T>, T)
+    where
+        T: Default + Real,
+    {
+        let q = if self.w() < T::zero() { -*self } else { *self };
+        let sin_half = q.vector().length();
+
+        if sin_half <= T::epsilon() {
+            return (Vector3::new([T::one(), T::zero(), T::zero()]), T::zero());
+        }
+
+        let angle = (T::one() + T::one()) * sin_half.atan2(q.w());
+
+        (q.vector() / sin_half, angle)
+    }
+
+    pub fn dot(&self, other: &Self) -> T
+    where
+        T: Default + Real,
+    {
+        self.0.dot(&other.0)
+    }
+
+    pub fn length_squared(&self) -> T
+    where
+        T: Real,
+    {
+        self.0.length_squared()
+    }
+
+    pub fn length(&self) -> T
+    where
+        T: Real,
+    {
+        self.0.length()
+    }
+
+    pub fn normalize(&mut self)
+    where
+        T: Default + Real,
+    {
+        *self = self.normalized();
+    }
+
+    pub fn normalized(&self) -> Self
+    where
+        T: Default + Real,
+    {
+        Self(self.0.normalized())
+    }
+
+    pub fn conjugate(&self) -> Self
+    where
+        T: Copy + std::ops::Neg<Output = T>,
+    {
+        Self::new(-self.x(), -self.y(), -self.z(), self.w())
+    }
+
+    pub fn inverse(&self) -> Option<Self>
+    where
+        T: Real,
+    {
+        let len_sq = self.length_squared();
+
+        if len_sq <= T::epsilon() {
+            return None;
+        }
+
+        Some(Self(self.conjugate().0 / len_sq))
+    }
+
+    pub fn rotate(&self, v: Vector3<T>) -> Vector3<T>
+    where
+        T: Real,
+    {
+        let two = T::one() + T::one();
+        let u = self.vector();
+        let t = u.cross(&v) * two;
+
+        v + t * self.w() + u.cross(&t)
+    }
+
+    pub fn from_rotation_matrix(m: &Matrix3<T>) -> Self
+    where
+        T: Default + Real,
+    {
+        let one = T::one();
+        let two = one + one;
+        let quarter = one / (two * two);
+        let trace = m[0][0] + m[1][1] + m[2][2];
+
+        let q = if trace > T::zero() {
+            let s = (trace + one).sqrt() * two;
+
+            Self::new(
+                (m[2][1] - m[1][2]) / s,
+                (m[0][2] - m[2][0]) / s,
+                (m[1][0] - m[0][1]) / s,
+                quarter * s,
+            )
+        } else if m[0][0] > m[1][1] && m[0][0] > m[2][2] {
+            let s = (one + m[0][0] - m[1][1] - m[2][2]).sqrt() * two;
+
+            Self::new(
+                quarter * s,
+                (m[0][1] + m[1][0]) / s,
+                (m[0][2] + m[2][0]) / s,
+                (m[2][1] - m[1][2]) / s,
+            )
+        } else if m[1][1] > m[2][2] {
+            let s = (one + m[1][1] - m[0][0] - m[2][2]).sqrt() * two;
+
+            Self::new(
+                (m[0][1] + m[1][0]) / s,
+                quarter * s,
+                (m[1][2] + m[2][1]) / s,
+                (m[0][2] - m[2][0]) / s,
+            )
+        } else {
+            let s = (one + m[2][2] - m[0][0] - m[1][1]).sqrt() * two;
+
+            Self::new(
+                (m[0][2] + m[2][0]) / s,
+                (m[1][2] + m[2][1]) / s,
+                quarter * s,
+                (m[1][0] - m[0][1]) / s,
+            )
+        };
+
+        q.normalized()
+    }
+
+    pub fn to_rotation_matrix(&self) -> Matrix3<T>
+    where
+        T: Real,
+    {
+        let two = T::one() + T::one();
+        let (x, y, z, w) = (self.x(), self.y(), self.z(), self.w());
+
+        Matrix3::new([
+            [
+                T::one() - two * (y * y + z * z),
+                two * (x * y - z * w),
+                two * (x * z + y * w),
+            ],
+            [
+                two * (x * y + z * w),
+                T::one() - two * (x * x + z * z),
+                two * (y * z - x * w),
+            ],
+            [
+                two * (x * z - y * w),
+                two * (y * z + x * w),
+                T::one() - two * (x * x + y * y),
+            ],
+        ])
+    }
+}
+
+mod ops {
+    use super::*;
+
+    use std::ops::{Add, Mul, Neg, Sub};
+
+    impl<T> Mul<Quaternion<T>> for Quaternion<T>
+    where
+        T: Real,
+    {
+        type Output = Quaternion<T>;
+
+        fn mul(self, rhs: Quaternion<T>) -> Self::Output {
+            let (a, b) = (self.vector(), rhs.vector());
+
+            Quaternion::from_parts(
+                b * self.w() + a * rhs.w() + a.cross(&b),
+                self.w() * rhs.w() - (a.x() * b.x() + a.y() * b.y() + a.z() * b.z()),
+            )
+        }
+    }
+
+    impl<'b, T> Mul<&'b Quaternion<T>> for &Quaternion<T>
+    where
+        T: Real,
+    {
+        type Output = Quaternion<T>;
+
+        fn mul(self, rhs: &'b Quaternion<T>) -> Self::Output {
+            (*self).mul(*rhs)
+        }
+    }
+
+    impl<T> Mul<Vector3<T>> for Quaternion<T>
+    where
+        T: Real,
+    {
+        type Output = Vector3<T>;
+
+        fn mul(self, rhs: Vector3<T>) -> Self::Output {
+            self.rotate(rhs)
+        }
+    }
+
+    impl<T> Mul<T> for Quaternion<T>
+    where
+        T: Copy + Mul<Output = T>,
+    {
+        type Output = Quaternion<T>;
+
+        fn mul(self, rhs: T) -> Self::Output {
+            Quaternion(self.0 * rhs)
+        }
+    }
+
+    impl<T> Add for Quaternion<T>
+    where
+        T: Copy + Add<Output = T>,
+    {
+        type Output = Quaternion<T>;
+
+        fn add(self, rhs: Quaternion<T>) -> Self::Output {
+            Quaternion(self.0 + rhs.0)
+        }
+    }
+
+    impl<T> Sub for Quaternion<T>
+    where
+        T: Copy + Sub<Output = T>,
+    {
+        type Output = Quaternion<T>;
+
+        fn sub(self, rhs: Quaternion<T>) -> Self::Output {
+            Quaternion(self.0 - rhs.0)
+        }
+    }
+
+    impl<T> Neg for Quaternion<T>
+    where
+        T: Neg<Output = T>,
+    {
+        type Output = Quaternion<T>;
+
+        fn neg(self) -> Self::Output {
+            Quaternion(-self.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{test_util::assert_close, vector};
+
+    #[test]
+    fn rotate() {
+        let q = QuaternionD::from_axis_angle(vector!(0.0, 0.0, 1.0), std::f64::consts::FRAC_PI_2);
+
+        assert_close(
+            q.rotate(vector!(1.0, 0.0, 0.0)),
+            vector!(0.0, 1.0, 0.0),
+            1e-9,
+        );
+        assert_close(q * vector!(0.0, 1.0, 0.0), vector!(-1.0, 0.0, 0.0), 1e-9);
+    }
+
+    #[test]
+    fn composition() {
+        let a = QuaternionD::from_axis_angle(vector!(1.0, 0.0, 0.0), 0.3);
+        let b = QuaternionD::from_axis_angle(vector!(0.0, 1.0, 0.0), -1.1);
+        let v = vector!(0.2, -1.0, 3.0);
+
+        assert_close((a * b).rotate(v), a.rotate(b.rotate(v)), 1e-9);
+        assert_close((a * a.inverse().unwrap()).rotate(v), v, 1e-9);
+        assert_close((a * a.conjugate()).rotate(v), v, 1e-9);
+    }
+
+    #[test]
+    fn axis_angle() {
+        let axis = vector!(1.0, 2.0, -2.0).normalized();
+        let (out_axis, angle) = QuaternionD::from_axis_angle(axis, 2.5).to_axis_angle();
+
+        assert_close(out_axis, axis, 1e-9);
+        assert!((angle - 2.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rotation_matrix() {
+        let q = QuaternionD::from_axis_angle(vector!(-1.0, 0.5, 2.0), 2.9);
+        let m = q.to_rotation_matrix();
+        let v = vector!(3.0, -2.0, 1.0);
+
+        assert_close(m * v, q.rotate(v), 1e-9);
+
+        let back = QuaternionD::from_rotation_matrix(&m);
+        assert!((back.dot(&q).abs() - 1.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,494 @@
+use std::ops::{Add, Mul};
+
+use num_traits::real::Real;
+
+use crate::{
+    aabb::Aabb,
+    shapes::closest_point_on_segment,
+    vector::{Vector, Vector3},
+};
+
+pub type Ray2<T> = Ray<2, T>;
+pub type Ray3<T> = Ray<3, T>;
+
+macro_rules! ray_types {
+    ($($n:literal),*) => {
+        paste::paste! {
+            $(
+                pub type [< Ray $n F >] = [< Ray $n >]<f32>;
+                pub type [< Ray $n D >] = [< Ray $n >]<f64>;
+            )*
+        }
+    };
+}
+
+ray_types!(2, 3);
+
+// Distance is measured in multiples of the ray direction, so it's the actual distance only for
+// normalized directions
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RayHit<const N: usize, T> {
+    pub distance: T,
+    pub point: Vector<N, T>,
+    pub normal: Vector<N, T>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TriangleHit<T> {
+    pub distance: T,
+    pub point: Vector3<T>,
+    pub normal: Vector3<T>,
+    // Weights of the triangle's a, b and c vertices
+    pub barycentric: Vector3<T>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Ray<const N: usize, T> {
+    pub origin: Vector<N, T>,
+    pub direction: Vector<N, T>,
+}
+
+impl<const N: usize, T> Ray<N, T> {
+    pub const fn new(origin: Vector<N, T>, direction: Vector<N, T>) -> Self {
+        Self { origin, direction }
+    }
+
+    pub fn through(from: Vector<N, T>, to: Vector<N, T>) -> Self
+    where
+        T: Default + Real,
+    {
+        Self::new(from, (to - from).normalized())
+    }
+
+    pub fn at(&self, t: T) -> Vector<N, T>
+    where
+        T: Copy + Add<Output = T> + Mul<Output = T>,
+    {
+        self.origin + self.direction * t
+    }
+
+    fn hit(&self, distance: T, normal: Vector<N, T>) -> RayHit<N, T>
+    where
+        T: Copy + Add<Output = T> + Mul<Output = T>,
+    {
+        RayHit {
+            distance,
+            point: self.at(distance),
+            normal,
+        }
+    }
+
+    // Normal faces against the ray, for two sided surfaces
+    fn facing(&self, normal: Vector<N, T>) -> Vector<N, T>
+    where
+        T: Default + Real,
+    {
+        if normal.dot(&self.direction) > T::zero() {
+            -normal
+        } else {
+            normal
+        }
+    }
+
+    // Both parameters where the line of the ray crosses the sphere, smallest first
+    fn sphere_roots(&self, center: &Vector<N, T>, radius: T) -> Option<(T, T)>
+    where
+        T: Default + Real,
+    {
+        let oc = self.origin - center;
+        let a = self.direction.length_squared();
+        let b = oc.dot(&self.direction);
+        let c = oc.length_squared() - radius * radius;
+        let discriminant = b * b - a * c;
+
+        if a <= T::zero() || discriminant < T::zero() {
+            return None;
+        }
+
+        let sqrt = discriminant.sqrt();
+
+        Some(((-b - sqrt) / a, (-b + sqrt) / a))
+    }
+
+    // Nearest hit in front of the origin, the exit point when starting inside
+    pub fn intersect_sphere(&self, center: &Vector<N, T>, radius: T) -> Option<RayHit<N, T>>
+    where
+        T: Default + Real,
+    {
+        let (near, far) = self.sphere_roots(center, radius)?;
+        let t = if near >= T::zero() { near } else { far };
+
+        (t >= T::zero()).then(|| self.hit(t, (self.at(t) - center) / radius))
+    }
+
+    // Plane of points x where normal · x = distance
+    pub fn intersect_plane(&self, normal: &Vector<N, T>, distance: T) -> Option<RayHit<N, T>>
+    where
+        T: Default + Real,
+    {
+        let denom = normal.dot(&self.direction);
+
+        if denom.abs() <= T::epsilon() {
+            return None;
+        }
+
+        let t = (distance - normal.dot(&self.origin)) / denom;
+
+        (t >= T::zero()).then(|| self.hit(t, self.facing(*normal)))
+    }
+
+    // Slab method, the exit point is returned when starting inside
+    pub fn intersect_aabb(&self, aabb: &Aabb<N, T>) -> Option<RayHit<N, T>>
+    where
+        T: Default + Real,
+    {
+        // (t, axis) where the ray enters and leaves the last of the slabs, None while unbounded
+        let mut near: Option<(T, usize)> = None;
+        let mut far: Option<(T, usize)> = None;
+
+        for axis in 0..N {
+            let (origin, dir) = (self.origin[axis], self.direction[axis]);
+
+            if dir == T::zero() {
+                if origin < aabb.min[axis] || origin > aabb.max[axis] {
+                    return None;
+                }
+
+                continue;
+            }
+
+            let t1 = (aabb.min[axis] - origin) / dir;
+            let t2 = (aabb.max[axis] - origin) / dir;
+            let (t1, t2) = if t1 <= t2 { (t1, t2) } else { (t2, t1) };
+
+            if near.is_none_or(|(t, _)| t1 > t) {
+                near = Some((t1, axis));
+            }
+            if far.is_none_or(|(t, _)| t2 < t) {
+                far = Some((t2, axis));
+            }
+
+            if let (Some((near, _)), Some((far, _))) = (near, far) {
+                if near > far {
+                    return None;
+                }
+            }
+        }
+
+        // Both are None only for a zero direction starting inside
+        let ((near_t, near_axis), (far_t, far_axis)) = (near?, far?);
+
+        let (t, axis, entering) = if near_t >= T::zero() {
+            (near_t, near_axis, true)
+        } else if far_t >= T::zero() {
+            (far_t, far_axis, false)
+        } else {
+            return None;
+        };
+
+        let mut normal = Vector::new_val(T::zero());
+        normal[axis] = if (self.direction[axis] > T::zero()) == entering {
+            -T::one()
+        } else {
+            T::one()
+        };
+
+        Some(self.hit(t, normal))
+    }
+
+    // Capsule around the segment a-b, the exit point is returned when starting inside
+    pub fn intersect_capsule(
+        &self,
+        a: &Vector<N, T>,
+        b: &Vector<N, T>,
+        radius: T,
+    ) -> Option<RayHit<N, T>>
+    where
+        T: Default + Real,
+    {
+        let ab = *b - a;
+        let ab_len_sq = ab.length_squared();
+        let axis_param = |t: T| (self.at(t) - a).dot(&ab);
+
+        let mut candidates = [None; 6];
+
+        // Side of the infinite cylinder, only where it's between the caps
+        let ao = self.origin - a;
+        let dir_ab = self.direction.dot(&ab);
+        let ao_ab = ao.dot(&ab);
+        let qa = self.direction.length_squared() * ab_len_sq - dir_ab * dir_ab;
+        let qb = ab_len_sq * self.direction.dot(&ao) - ao_ab * dir_ab;
+        let qc = ab_len_sq * (ao.length_squared() - radius * radius) - ao_ab * ao_ab;
+        let discriminant = qb * qb - qa * qc;
+
+        if qa > T::epsilon() && discriminant >= T::zero() {
+            let sqrt = discriminant.sqrt();
+
+            for (slot, t) in candidates[..2]
+                .iter_mut()
+                .zip([(-qb - sqrt) / qa, (-qb + sqrt) / qa])
+            {
+                let y = axis_param(t);
+                *slot = (y > T::zero() && y < ab_len_sq).then_some(t);
+            }
+        }
+
+        // Hemispherical caps
+        if let Some((near, far)) = self.sphere_roots(a, radius) {
+            candidates[2] = (axis_param(near) <= T::zero()).then_some(near);
+            candidates[3] = (axis_param(far) <= T::zero()).then_some(far);
+        }
+        if let Some((near, far)) = self.sphere_roots(b, radius) {
+            candidates[4] = (axis_param(near) >= ab_len_sq).then_some(near);
+            candidates[5] = (axis_param(far) >= ab_len_sq).then_some(far);
+        }
+
+        let t = candidates
+            .into_iter()
+            .flatten()
+            .filter(|&t| t >= T::zero())
+            .fold(None, |best: Option<T>, t| match best {
+                Some(best) if best <= t => Some(best),
+                _ => Some(t),
+            })?;
+
+        let point = self.at(t);
+        let normal = (point - closest_point_on_segment(a, b, &point)) / radius;
+
+        Some(self.hit(t, normal))
+    }
+}
+
+impl<T> Ray3<T> {
+    // Möller–Trumbore, hits from both sides, the normal follows the a -> b -> c winding
+    pub fn intersect_triangle(
+        &self,
+        a: &Vector3<T>,
+        b: &Vector3<T>,
+        c: &Vector3<T>,
+    ) -> Option<TriangleHit<T>>
+    where
+        T: Default + Real,
+    {
+        let ab = *b - a;
+        let ac = *c - a;
+        let p = self.direction.cross(&ac);
+        let det = ab.dot(&p);
+
+        if det.abs() <= T::epsilon() {
+            return None;
+        }
+
+        let inv_det = T::one() / det;
+        let ao = self.origin - a;
+
+        let u = ao.dot(&p) * inv_det;
+        if u < T::zero() || u > T::one() {
+            return None;
+        }
+
+        let q = ao.cross(&ab);
+        let v = self.direction.dot(&q) * inv_det;
+        if v < T::zero() || u + v > T::one() {
+            return None;
+        }
+
+        let t = ac.dot(&q) * inv_det;
+        if t < T::zero() {
+            return None;
+        }
+
+        Some(TriangleHit {
+            distance: t,
+            point: self.at(t),
+            normal: ab.cross(&ac).normalized(),
+            barycentric: Vector3::new([T::one() - u - v, u, v]),
+        })
+    }
+
+    pub fn intersect_disk(
+        &self,
+        center: &Vector3<T>,
+        normal: &Vector3<T>,
+        radius: T,
+    ) -> Option<RayHit<3, T>>
+    where
+        T: Default + Real,
+    {
+        let normal = normal.normalized();
+        let hit = self.intersect_plane(&normal, normal.dot(center))?;
+
+        ((hit.point - center).length_squared() <= radius * radius).then_some(hit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{test_util::assert_close, vector};
+
+    #[test]
+    fn at() {
+        let ray: Ray3D = Ray::new(vector!(1.0, 2.0, 3.0), vector!(0.0, 0.0, -1.0));
+
+        assert_eq!(ray.at(0.0), ray.origin);
+        assert_eq!(ray.at(2.5), vector!(1.0, 2.0, 0.5));
+    }
+
+    #[test]
+    fn through() {
+        let ray: Ray2D = Ray::through(vector!(1.0, 1.0), vector!(1.0, 5.0));
+
+        assert_eq!(ray.direction, vector!(0.0, 1.0));
+        assert_eq!(ray.at(4.0), vector!(1.0, 5.0));
+    }
+
+    #[test]
+    fn sphere() {
+        let ray: Ray3D = Ray::new(vector!(0.0, 0.0, -5.0), vector!(0.0, 0.0, 1.0));
+        let hit = ray.intersect_sphere(&vector!(0.0, 0.0, 0.0), 2.0).unwrap();
+
+        assert_eq!(hit.distance, 3.0);
+        assert_close(hit.normal, vector!(0.0, 0.0, -1.0), 1e-9);
+
+        let inside = Ray::new(vector!(0.0, 0.0, 0.0), vector!(0.0, 0.0, 1.0));
+        let hit = inside
+            .intersect_sphere(&vector!(0.0, 0.0, 0.0), 2.0)
+            .unwrap();
+        assert_eq!(hit.distance, 2.0);
+        assert_close(hit.normal, vector!(0.0, 0.0, 1.0), 1e-9);
+
+        assert!(ray.intersect_sphere(&vector!(0.0, 3.0, 0.0), 2.0).is_none());
+        assert!(ray
+            .intersect_sphere(&vector!(0.0, 0.0, -10.0), 2.0)
+            .is_none());
+
+        // Works in any dimension
+        let ray2: Ray2D = Ray::new(vector!(-4.0, 1.0), vector!(1.0, 0.0));
+        let hit = ray2.intersect_sphere(&vector!(0.0, 0.0), 2.0).unwrap();
+        assert!((hit.distance - (4.0 - 3.0_f64.sqrt())).abs() < 1e-9);
+    }
+
+    #[test]
+    fn plane() {
+        let ray: Ray3D = Ray::new(vector!(1.0, 5.0, 2.0), vector!(0.0, -1.0, 0.0));
+        let hit = ray.intersect_plane(&vector!(0.0, 1.0, 0.0), 2.0).unwrap();
+
+        assert_eq!(hit.distance, 3.0);
+        assert_eq!(hit.point, vector!(1.0, 2.0, 2.0));
+        assert_eq!(hit.normal, vector!(0.0, 1.0, 0.0));
+
+        // Seen from below the normal is flipped towards the ray
+        let below = Ray::new(vector!(0.0, 0.0, 0.0), vector!(0.0, 1.0, 0.0));
+        assert_eq!(
+            below
+                .intersect_plane(&vector!(0.0, 1.0, 0.0), 2.0)
+                .unwrap()
+                .normal,
+            vector!(0.0, -1.0, 0.0)
+        );
+
+        assert!(ray.intersect_plane(&vector!(0.0, 1.0, 0.0), 6.0).is_none());
+        assert!(ray.intersect_plane(&vector!(1.0, 0.0, 0.0), 0.0).is_none());
+    }
+
+    #[test]
+    fn aabb() {
+        let aabb = Aabb::new(vector!(-1.0, -1.0, -1.0), vector!(1.0, 1.0, 1.0));
+
+        let ray: Ray3D = Ray::new(vector!(-5.0, 0.5, 0.0), vector!(1.0, 0.0, 0.0));
+        let hit = ray.intersect_aabb(&aabb).unwrap();
+        assert_eq!(hit.distance, 4.0);
+        assert_eq!(hit.normal, vector!(-1.0, 0.0, 0.0));
+
+        let diagonal = Ray::new(vector!(1.5, 3.0, 0.5), vector!(-1.0, -2.0, 0.0));
+        let hit = diagonal.intersect_aabb(&aabb).unwrap();
+        assert_eq!(hit.distance, 1.0);
+        assert_eq!(hit.normal, vector!(0.0, 1.0, 0.0));
+
+        let inside = Ray::new(vector!(0.0, 0.0, 0.0), vector!(0.0, 0.0, -1.0));
+        let hit = inside.intersect_aabb(&aabb).unwrap();
+        assert_eq!(hit.distance, 1.0);
+        assert_eq!(hit.normal, vector!(0.0, 0.0, -1.0));
+
+        assert!(Ray::new(vector!(-5.0, 2.0, 0.0), vector!(1.0, 0.0, 0.0))
+            .intersect_aabb(&aabb)
+            .is_none());
+        assert!(Ray::new(vector!(5.0, 0.0, 0.0), vector!(1.0, 0.0, 0.0))
+            .intersect_aabb(&aabb)
+            .is_none());
+    }
+
+    #[test]
+    fn triangle() {
+        let (a, b, c) = (
+            vector!(0.0, 0.0, 0.0),
+            vector!(4.0, 0.0, 0.0),
+            vector!(0.0, 4.0, 0.0),
+        );
+
+        let ray: Ray3D = Ray::new(vector!(1.0, 2.0, 3.0), vector!(0.0, 0.0, -1.0));
+        let hit = ray.intersect_triangle(&a, &b, &c).unwrap();
+
+        assert_eq!(hit.distance, 3.0);
+        assert_eq!(hit.normal, vector!(0.0, 0.0, 1.0));
+        assert_close(hit.barycentric, vector!(0.25, 0.25, 0.5), 1e-9);
+        assert_close(
+            a * hit.barycentric.x() + b * hit.barycentric.y() + c * hit.barycentric.z(),
+            hit.point,
+            1e-9,
+        );
+
+        assert!(Ray::new(vector!(3.0, 3.0, 3.0), vector!(0.0, 0.0, -1.0))
+            .intersect_triangle(&a, &b, &c)
+            .is_none());
+        assert!(Ray::new(vector!(1.0, 1.0, -3.0), vector!(0.0, 0.0, -1.0))
+            .intersect_triangle(&a, &b, &c)
+            .is_none());
+    }
+
+    #[test]
+    fn disk() {
+        let ray: Ray3D = Ray::new(vector!(0.5, 0.5, 2.0), vector!(0.0, 0.0, -2.0));
+        let hit = ray
+            .intersect_disk(&vector!(0.0, 0.0, 0.0), &vector!(0.0, 0.0, 3.0), 1.0)
+            .unwrap();
+
+        assert_eq!(hit.distance, 1.0);
+        assert_eq!(hit.normal, vector!(0.0, 0.0, 1.0));
+        assert!(ray
+            .intersect_disk(&vector!(0.0, 0.0, 0.0), &vector!(0.0, 0.0, 1.0), 0.5)
+            .is_none());
+    }
+
+    #[test]
+    fn capsule() {
+        let (a, b) = (vector!(0.0, 0.0, 0.0), vector!(0.0, 4.0, 0.0));
+
+        // Side
+        let ray: Ray3D = Ray::new(vector!(-5.0, 2.0, 0.0), vector!(1.0, 0.0, 0.0));
+        let hit = ray.intersect_capsule(&a, &b, 1.0).unwrap();
+        assert!((hit.distance - 4.0).abs() < 1e-9);
+        assert_close(hit.normal, vector!(-1.0, 0.0, 0.0), 1e-9);
+
+        // Cap
+        let ray = Ray::new(vector!(0.0, 10.0, 0.0), vector!(0.0, -1.0, 0.0));
+        let hit = ray.intersect_capsule(&a, &b, 1.0).unwrap();
+        assert!((hit.distance - 5.0).abs() < 1e-9);
+        assert_close(hit.normal, vector!(0.0, 1.0, 0.0), 1e-9);
+
+        // Inside, leaving through the bottom cap
+        let ray = Ray::new(vector!(0.0, 1.0, 0.0), vector!(0.0, -1.0, 0.0));
+        let hit = ray.intersect_capsule(&a, &b, 1.0).unwrap();
+        assert!((hit.distance - 2.0).abs() < 1e-9);
+        assert_close(hit.normal, vector!(0.0, -1.0, 0.0), 1e-9);
+
+        assert!(Ray::new(vector!(-5.0, 2.0, 1.5), vector!(1.0, 0.0, 0.0))
+            .intersect_capsule(&a, &b, 1.0)
+            .is_none());
+        assert!(Ray::new(vector!(-5.0, 5.5, 0.0), vector!(1.0, 0.0, 0.0))
+            .intersect_capsule(&a, &b, 1.0)
+            .is_none());
+    }
+}
@@ -0,0 +1,653 @@
+use std::collections::BTreeMap;
+
+use num_traits::real::Real;
+
+use crate::{angle::Rad, matrix::Matrix, vector::Vector};
+
+// Grade 2 part of the geometric algebra over N dimensions, stored as the antisymmetric
+// matrix B[i][j] = -B[j][i] holding the coefficient of e_i ∧ e_j
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Bivector<const N: usize, T>(pub(crate) Matrix<N, N, T>);
+
+impl<const N: usize, T> Bivector<N, T> {
+    pub fn zero() -> Self
+    where
+        T: Real,
+    {
+        Self(Matrix::from_rows([Vector::new_val(T::zero()); N]))
+    }
+
+    // Unit bivector e_i ∧ e_j
+    pub fn basis(i: usize, j: usize) -> Self
+    where
+        T: Real,
+    {
+        let mut res = Self::zero();
+        res.0[i][j] = T::one();
+        res.0[j][i] = -T::one();
+
+        res
+    }
+
+    pub fn component(&self, i: usize, j: usize) -> T
+    where
+        T: Copy,
+    {
+        self.0[i][j]
+    }
+
+    pub fn as_matrix(&self) -> &Matrix<N, N, T> {
+        &self.0
+    }
+
+    pub fn magnitude_squared(&self) -> T
+    where
+        T: Real,
+    {
+        let rows = &self.0 .0;
+
+        rows.iter()
+            .enumerate()
+            .flat_map(|(i, row)| row.0[i + 1..].iter())
+            .fold(T::zero(), |sum, &val| sum + val * val)
+    }
+
+    pub fn magnitude(&self) -> T
+    where
+        T: Real,
+    {
+        self.magnitude_squared().sqrt()
+    }
+
+    pub fn normalized(&self) -> Self
+    where
+        T: Real,
+    {
+        let len = self.magnitude();
+
+        if len > T::zero() {
+            *self * (T::one() / len)
+        } else {
+            *self
+        }
+    }
+}
+
+impl<const N: usize, T> Vector<N, T> {
+    pub fn wedge(&self, other: &Self) -> Bivector<N, T>
+    where
+        T: Real,
+    {
+        Bivector(Matrix::from_rows(std::array::from_fn(|i| {
+            Vector::new(std::array::from_fn(|j| {
+                self[i] * other[j] - self[j] * other[i]
+            }))
+        })))
+    }
+}
+
+// Even grade multivector holding only its nonzero blades as (bitmask, coefficient) sorted by
+// bitmask, where the bitmask marks the basis vectors forming the blade (e.g. 0b101 is e_0 ∧ e_2).
+// A rotor in a single plane has at most N * (N - 1) / 2 + 1 blades, and as bitmasks are usize, N
+// has to be below usize::BITS
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rotor<const N: usize, T>(pub(crate) Vec<(usize, T)>);
+
+impl<const N: usize, T> Rotor<N, T> {
+    // Sums up the coefficients of repeated blades and drops the zero ones
+    fn from_blades<I>(blades: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, T)>,
+        T: Real,
+    {
+        const { assert!(N < usize::BITS as usize, "too many dimensions for a Rotor") };
+
+        let mut sums = BTreeMap::new();
+        for (blade, val) in blades {
+            let sum = sums.entry(blade).or_insert_with(T::zero);
+            *sum = *sum + val;
+        }
+
+        Self(sums.into_iter().filter(|(_, val)| !val.is_zero()).collect())
+    }
+
+    pub fn identity() -> Self
+    where
+        T: Real,
+    {
+        Self::from_blades([(0, T::one())])
+    }
+
+    // Rotation by angle in the plane, turning the plane's first vector towards its second one
+    pub fn from_plane<A>(plane: &Bivector<N, T>, angle: A) -> Self
+    where
+        A: Into<Rad<T>>,
+        T: Real,
+    {
+        let plane = plane.normalized();
+        let (sin, cos) = (angle.into() / (T::one() + T::one())).sin_cos();
+
+        Self::from_blades(
+            std::iter::once((0, cos)).chain(
+                plane_blades::<N>().map(|(blade, i, j)| (blade, -sin * plane.component(i, j))),
+            ),
+        )
+    }
+
+    // Rotation in the plane of both vectors taking the direction of from to the direction of to,
+    // None when they point in opposite directions and the plane is ambiguous
+    pub fn from_vectors(from: &Vector<N, T>, to: &Vector<N, T>) -> Option<Self>
+    where
+        T: Default + Real,
+    {
+        let (from, to) = (from.normalized(), to.normalized());
+        let cos = from.dot(&to);
+
+        if cos + T::one() <= T::epsilon().sqrt() {
+            return None;
+        }
+
+        let plane = from.wedge(&to);
+        let res = Self::from_blades(
+            std::iter::once((0, T::one() + cos))
+                .chain(plane_blades::<N>().map(|(blade, i, j)| (blade, -plane.component(i, j)))),
+        );
+
+        Some(res.normalized())
+    }
+
+    pub fn scalar(&self) -> T
+    where
+        T: Real,
+    {
+        self.component(0)
+    }
+
+    pub fn bivector(&self) -> Bivector<N, T>
+    where
+        T: Real,
+    {
+        let mut res = Bivector::zero();
+
+        for (blade, i, j) in plane_blades::<N>() {
+            let val = self.component(blade);
+
+            res.0[i][j] = val;
+            res.0[j][i] = -val;
+        }
+
+        res
+    }
+
+    // Coefficient of the blade with the given bitmask
+    pub fn component(&self, blade: usize) -> T
+    where
+        T: Real,
+    {
+        self.0
+            .binary_search_by_key(&blade, |&(blade, _)| blade)
+            .map_or_else(|_| T::zero(), |i| self.0[i].1)
+    }
+
+    // A simple rotor rotates in a single plane and has no grade 4 or higher parts
+    pub fn is_simple(&self) -> bool
+    where
+        T: Real,
+    {
+        let eps = T::epsilon().sqrt();
+
+        self.0
+            .iter()
+            .all(|(blade, val)| blade.count_ones() <= 2 || val.abs() <= eps)
+    }
+
+    pub fn reverse(&self) -> Self
+    where
+        T: Real,
+    {
+        Self(
+            self.0
+                .iter()
+                .map(|&(blade, val)| match blade.count_ones() % 4 {
+                    2 | 3 => (blade, -val),
+                    _ => (blade, val),
+                })
+                .collect(),
+        )
+    }
+
+    pub fn magnitude_squared(&self) -> T
+    where
+        T: Real,
+    {
+        self.0
+            .iter()
+            .fold(T::zero(), |sum, &(_, val)| sum + val * val)
+    }
+
+    pub fn magnitude(&self) -> T
+    where
+        T: Real,
+    {
+        self.magnitude_squared().sqrt()
+    }
+
+    pub fn normalized(&self) -> Self
+    where
+        T: Real,
+    {
+        let len = self.magnitude();
+
+        if len > T::zero() {
+            self.scaled(T::one() / len)
+        } else {
+            self.clone()
+        }
+    }
+
+    pub fn inverse(&self) -> Option<Self>
+    where
+        T: Real,
+    {
+        let len_sq = self.magnitude_squared();
+
+        if len_sq <= T::epsilon() {
+            return None;
+        }
+
+        Some(self.reverse().scaled(T::one() / len_sq))
+    }
+
+    pub fn rotate(&self, v: &Vector<N, T>) -> Vector<N, T>
+    where
+        T: Real,
+    {
+        let embedded: Vec<_> = (0..N)
+            .map(|i| (1 << i, v[i]))
+            .filter(|(_, val)| !val.is_zero())
+            .collect();
+        let left = geometric_product(&self.0, &embedded);
+        let reverse = self.reverse();
+
+        // Only the vector part of left * reverse is kept, so for each blade of left only the
+        // blades of reverse leaving a single basis vector have to be looked up
+        Vector::new(std::array::from_fn(|k| {
+            left.iter().fold(T::zero(), |sum, &(blade, x)| {
+                let other = blade ^ (1 << k);
+                let val = x * reverse.component(other);
+
+                if reorder_is_odd(blade, other) {
+                    sum - val
+                } else {
+                    sum + val
+                }
+            })
+        }))
+    }
+
+    // Columns are the rotated basis vectors
+    pub fn to_matrix(&self) -> Matrix<N, N, T>
+    where
+        T: Real,
+    {
+        Matrix::from_columns(std::array::from_fn(|j| {
+            let mut basis = Vector::new_val(T::zero());
+            basis[j] = T::one();
+
+            self.rotate(&basis)
+        }))
+    }
+
+    // Raises a simple rotor to a power, scaling its rotation angle, None for non simple rotors
+    pub fn pow(&self, exponent: T) -> Option<Self>
+    where
+        T: Real,
+    {
+        if !self.is_simple() {
+            return None;
+        }
+
+        let rotor = self.normalized();
+        let plane = rotor.bivector();
+        let sin_half = plane.magnitude();
+
+        if sin_half <= T::epsilon() {
+            return Some(Self::identity());
+        }
+
+        let angle = (T::one() + T::one()) * sin_half.atan2(rotor.scalar());
+
+        Some(Self::from_plane(&-plane, Rad(angle * exponent)))
+    }
+
+    pub fn nlerp(&self, other: &Self, t: T) -> Self
+    where
+        T: Real,
+    {
+        let other = self.hemisphere(other);
+
+        Self::from_blades(
+            self.scaled(T::one() - t)
+                .0
+                .into_iter()
+                .chain(other.scaled(t).0),
+        )
+        .normalized()
+    }
+
+    // Follows the geodesic when the relative rotation happens in a single plane (always the
+    // case up to 3D), otherwise falls back to nlerp
+    pub fn slerp(&self, other: &Self, t: T) -> Self
+    where
+        T: Real,
+    {
+        let other = self.hemisphere(other);
+
+        match self.reverse().product(&other).pow(t) {
+            Some(delta) => self.product(&delta).normalized(),
+            None => self.nlerp(&other, t),
+        }
+    }
+
+    fn hemisphere(&self, other: &Self) -> Self
+    where
+        T: Real,
+    {
+        let dot = self.0.iter().fold(T::zero(), |sum, &(blade, val)| {
+            sum + val * other.component(blade)
+        });
+
+        if dot < T::zero() {
+            other.scaled(-T::one())
+        } else {
+            other.clone()
+        }
+    }
+
+    fn scaled(&self, factor: T) -> Self
+    where
+        T: Real,
+    {
+        Self::from_blades(self.0.iter().map(|&(blade, val)| (blade, val * factor)))
+    }
+
+    fn product(&self, other: &Self) -> Self
+    where
+        T: Real,
+    {
+        Self(geometric_product(&self.0, &other.0))
+    }
+}
+
+// (bitmask of e_i ∧ e_j, i, j) for every i < j
+fn plane_blades<const N: usize>() -> impl Iterator<Item = (usize, usize, usize)> {
+    (0..N).flat_map(|i| (i + 1..N).map(move |j| ((1 << i) | (1 << j), i, j)))
+}
+
+// Whether reordering blade a * blade b into canonical order takes an odd number of swaps
+fn reorder_is_odd(a: usize, b: usize) -> bool {
+    let mut a = a >> 1;
+    let mut swaps = 0;
+
+    while a != 0 {
+        swaps += (a & b).count_ones();
+        a >>= 1;
+    }
+
+    swaps % 2 == 1
+}
+
+// Geometric product of two sparse multivectors with a euclidean metric, same layout as Rotor
+fn geometric_product<T>(a: &[(usize, T)], b: &[(usize, T)]) -> Vec<(usize, T)>
+where
+    T: Real,
+{
+    let mut res = BTreeMap::new();
+
+    for &(i, x) in a {
+        for &(j, y) in b {
+            let val = x * y;
+            let sum = res.entry(i ^ j).or_insert_with(T::zero);
+
+            *sum = if reorder_is_odd(i, j) {
+                *sum - val
+            } else {
+                *sum + val
+            };
+        }
+    }
+
+    res.into_iter().filter(|(_, val)| !val.is_zero()).collect()
+}
+
+mod ops {
+    use super::*;
+
+    use std::ops::{Add, Mul, Neg, Sub};
+
+    impl<const N: usize, T> Add for Bivector<N, T>
+    where
+        T: Copy + Add<Output = T>,
+    {
+        type Output = Bivector<N, T>;
+
+        fn add(self, rhs: Bivector<N, T>) -> Self::Output {
+            let mut res = self;
+
+            for (row, rhs_row) in res.0 .0.iter_mut().zip(rhs.0 .0) {
+                *row = *row + rhs_row;
+            }
+
+            res
+        }
+    }
+
+    impl<const N: usize, T> Sub for Bivector<N, T>
+    where
+        T: Copy + Sub<Output = T>,
+    {
+        type Output = Bivector<N, T>;
+
+        fn sub(self, rhs: Bivector<N, T>) -> Self::Output {
+            let mut res = self;
+
+            for (row, rhs_row) in res.0 .0.iter_mut().zip(rhs.0 .0) {
+                *row = *row - rhs_row;
+            }
+
+            res
+        }
+    }
+
+    impl<const N: usize, T> Mul<T> for Bivector<N, T>
+    where
+        T: Copy + Mul<Output = T>,
+    {
+        type Output = Bivector<N, T>;
+
+        fn mul(self, rhs: T) -> Self::Output {
+            Bivector(Matrix(self.0 .0.map(|row| row * rhs)))
+        }
+    }
+
+    impl<const N: usize, T> Neg for Bivector<N, T>
+    where
+        T: Neg<Output = T>,
+    {
+        type Output = Bivector<N, T>;
+
+        fn neg(self) -> Self::Output {
+            Bivector(Matrix(self.0 .0.map(|row| -row)))
+        }
+    }
+
+    impl<const N: usize, T> Neg for &Bivector<N, T>
+    where
+        T: Copy + Neg<Output = T>,
+    {
+        type Output = Bivector<N, T>;
+
+        fn neg(self) -> Self::Output {
+            -*self
+        }
+    }
+
+    // Composition, lhs * rhs applies rhs first
+    impl<const N: usize, T> Mul for Rotor<N, T>
+    where
+        T: Real,
+    {
+        type Output = Rotor<N, T>;
+
+        fn mul(self, rhs: Rotor<N, T>) -> Self::Output {
+            self.product(&rhs)
+        }
+    }
+
+    impl<'b, const N: usize, T> Mul<&'b Rotor<N, T>> for &Rotor<N, T>
+    where
+        T: Real,
+    {
+        type Output = Rotor<N, T>;
+
+        fn mul(self, rhs: &'b Rotor<N, T>) -> Self::Output {
+            self.product(rhs)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    use crate::{
+        quaternion::Quaternion,
+        test_util::assert_close,
+        vector,
+        vector::{Vector2, Vector3},
+    };
+
+    fn basis<const N: usize>(i: usize) -> Vector<N, f64> {
+        let mut res = Vector::new_val(0.0);
+        res[i] = 1.0;
+
+        res
+    }
+
+    #[test]
+    fn wedge() {
+        let a: Vector3<f64> = vector!(1.0, 2.0, 3.0);
+        let b = vector!(-1.0, 0.5, 2.0);
+        let ab = a.wedge(&b);
+
+        assert_eq!(ab, -b.wedge(&a));
+        assert_eq!(a.wedge(&a), Bivector::zero());
+
+        // In 3D the bivector components are the cross product's, dualized
+        let cross = a.cross(&b);
+        assert_eq!(
+            vector!(ab.component(1, 2), ab.component(2, 0), ab.component(0, 1)),
+            cross
+        );
+        assert!((ab.magnitude() - cross.length()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rotate_2d() {
+        let rotor = Rotor::from_plane(&Bivector::<2, f64>::basis(0, 1), Rad(FRAC_PI_2));
+
+        assert_close(rotor.rotate(&basis(0)), basis(1), 1e-9);
+        assert_close(rotor.rotate(&vector!(1.0, 1.0)), vector!(-1.0, 1.0), 1e-9);
+    }
+
+    #[test]
+    fn matches_quaternion() {
+        let axis: Vector3<f64> = vector!(1.0, -2.0, 0.5);
+        let q = Quaternion::from_axis_angle(axis, 1.3);
+
+        // The rotation plane is the one orthogonal to the axis
+        let u = axis.cross(&vector!(0.0, 0.0, 1.0)).normalized();
+        let plane = u.wedge(&axis.normalized().cross(&u));
+        let rotor = Rotor::from_plane(&plane, Rad(1.3));
+
+        let v = vector!(0.3, 0.7, -1.1);
+        assert_close(rotor.rotate(&v), q.rotate(v), 1e-9);
+    }
+
+    #[test]
+    fn from_vectors() {
+        let from: Vector<5, f64> = vector!(1.0, 2.0, 0.0, -1.0, 3.0);
+        let to = vector!(0.0, -1.0, 4.0, 2.0, 0.5);
+        let rotor = Rotor::from_vectors(&from, &to).unwrap();
+
+        assert_close(rotor.rotate(&from).normalized(), to.normalized(), 1e-9);
+        assert!((rotor.rotate(&from).length() - from.length()).abs() < 1e-9);
+        assert!(Rotor::from_vectors(&from, &-from).is_none());
+    }
+
+    #[test]
+    fn high_dimensional() {
+        let from: Vector<48, f64> = Vector::new(std::array::from_fn(|i| (i as f64 * 0.7).sin()));
+        let to = Vector::new(std::array::from_fn(|i| (i as f64 * 1.3).cos()));
+        let rotor = Rotor::from_vectors(&from, &to).unwrap();
+
+        // Only the scalar and the bivector blades are stored
+        assert!(rotor.0.len() <= 1 + 48 * 47 / 2);
+        assert!(rotor.is_simple());
+        assert_close(rotor.rotate(&from).normalized(), to.normalized(), 1e-9);
+
+        // Vectors orthogonal to the rotation plane stay put
+        let u = from.normalized();
+        let w = (to - u * to.dot(&u)).normalized();
+        let other: Vector<48, f64> = Vector::new(std::array::from_fn(|i| i as f64));
+        let fixed = other - u * other.dot(&u) - w * other.dot(&w);
+        assert_close(rotor.rotate(&fixed), fixed, 1e-9);
+    }
+
+    #[test]
+    fn composition_and_inverse() {
+        let a = Rotor::<4, f64>::from_plane(&Bivector::basis(0, 1), Rad(0.4));
+        let b = Rotor::<4, f64>::from_plane(&Bivector::basis(2, 3), Rad(1.1));
+        let v = vector!(1.0, -0.5, 2.0, 0.25);
+
+        // Rotations in orthogonal planes make a non simple double rotation
+        let ab = &a * &b;
+        assert!(!ab.is_simple());
+        assert_close(ab.rotate(&v), a.rotate(&b.rotate(&v)), 1e-9);
+        assert_close(ab.inverse().unwrap().rotate(&ab.rotate(&v)), v, 1e-9);
+
+        let m = ab.to_matrix();
+        assert_close(m * v, ab.rotate(&v), 1e-9);
+        assert_close(m * m.transpose().column(2), basis(2), 1e-9);
+    }
+
+    #[test]
+    fn interpolation() {
+        let plane = Bivector::<3, f64>::basis(0, 1);
+        let start = Rotor::from_plane(&plane, Rad(0.0));
+        let end = Rotor::from_plane(&plane, Rad(PI * 0.75));
+
+        let mid = start.slerp(&end, 1.0 / 3.0);
+        assert_close(
+            mid.rotate(&basis(0)),
+            Vector3::from(Vector2::from_angle(Rad(FRAC_PI_4))),
+            1e-9,
+        );
+
+        let nlerp = start.nlerp(&end, 0.5);
+        assert!((nlerp.magnitude() - 1.0).abs() < 1e-9);
+        assert_close(
+            nlerp.rotate(&basis(0)),
+            Vector3::from(Vector2::from_angle(Rad(PI * 0.375))),
+            1e-9,
+        );
+
+        assert_close(
+            start.slerp(&end, 1.0).rotate(&basis(1)),
+            end.rotate(&basis(1)),
+            1e-9,
+        );
+    }
+}
@@ -0,0 +1,714 @@
+use num_traits::real::Real;
+
+use crate::{
+    aabb::{Aabb, Aabb3},
+    gjk::gjk_distance,
+    vector::{Vector, Vector3},
+};
+
+pub type Sphere2<T> = Sphere<2, T>;
+pub type Sphere3<T> = Sphere<3, T>;
+
+macro_rules! sphere_types {
+    ($($n:literal),*) => {
+        paste::paste! {
+            $(
+                pub type [< Sphere $n F >] = [< Sphere $n >]<f32>;
+                pub type [< Sphere $n D >] = [< Sphere $n >]<f64>;
+            )*
+        }
+    };
+}
+
+sphere_types!(2, 3);
+
+pub type PlaneF = Plane<f32>;
+pub type PlaneD = Plane<f64>;
+pub type CapsuleF = Capsule<f32>;
+pub type CapsuleD = Capsule<f64>;
+pub type CylinderF = Cylinder<f32>;
+pub type CylinderD = Cylinder<f64>;
+
+fn clamp01<T>(val: T) -> T
+where
+    T: Real,
+{
+    val.max(T::zero()).min(T::one())
+}
+
+pub fn closest_point_on_segment<const N: usize, T>(
+    a: &Vector<N, T>,
+    b: &Vector<N, T>,
+    point: &Vector<N, T>,
+) -> Vector<N, T>
+where
+    T: Default + Real,
+{
+    let ab = *b - a;
+    let len_sq = ab.length_squared();
+
+    if len_sq <= T::zero() {
+        return *a;
+    }
+
+    *a + ab * clamp01((*point - a).dot(&ab) / len_sq)
+}
+
+// Closest pair of points between the segments p1-q1 and p2-q2 (Ericson 5.1.9)
+pub fn closest_points_on_segments<const N: usize, T>(
+    p1: &Vector<N, T>,
+    q1: &Vector<N, T>,
+    p2: &Vector<N, T>,
+    q2: &Vector<N, T>,
+) -> (Vector<N, T>, Vector<N, T>)
+where
+    T: Default + Real,
+{
+    let d1 = *q1 - p1;
+    let d2 = *q2 - p2;
+    let r = *p1 - p2;
+    let a = d1.length_squared();
+    let e = d2.length_squared();
+    let f = d2.dot(&r);
+
+    let (s, t) = if a <= T::epsilon() && e <= T::epsilon() {
+        (T::zero(), T::zero())
+    } else if a <= T::epsilon() {
+        (T::zero(), clamp01(f / e))
+    } else {
+        let c = d1.dot(&r);
+
+        if e <= T::epsilon() {
+            (clamp01(-c / a), T::zero())
+        } else {
+            let b = d1.dot(&d2);
+            let denom = a * e - b * b;
+
+            // Parallel segments have no unique pair, any point of the first will do
+            let s = if denom > T::zero() {
+                clamp01((b * f - c * e) / denom)
+            } else {
+                T::zero()
+            };
+            let t = (b * s + f) / e;
+
+            if t < T::zero() {
+                (clamp01(-c / a), T::zero())
+            } else if t > T::one() {
+                (clamp01((b - c) / a), T::one())
+            } else {
+                (s, t)
+            }
+        }
+    };
+
+    (*p1 + d1 * s, *p2 + d2 * t)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Sphere<const N: usize, T> {
+    pub center: Vector<N, T>,
+    pub radius: T,
+}
+
+impl<const N: usize, T> Sphere<N, T> {
+    pub const fn new(center: Vector<N, T>, radius: T) -> Self {
+        Self { center, radius }
+    }
+
+    pub fn contains(&self, point: &Vector<N, T>) -> bool
+    where
+        T: Default + Real,
+    {
+        (*point - self.center).length_squared() <= self.radius * self.radius
+    }
+
+    pub fn closest_point(&self, point: &Vector<N, T>) -> Vector<N, T>
+    where
+        T: Default + Real,
+    {
+        if self.contains(point) {
+            return *point;
+        }
+
+        self.center + (*point - self.center).normalized() * self.radius
+    }
+
+    pub fn bounding_box(&self) -> Aabb<N, T>
+    where
+        T: Real,
+    {
+        Aabb::from_center_half_extents(self.center, Vector::new_val(self.radius))
+    }
+
+    pub fn intersects_sphere(&self, other: &Self) -> bool
+    where
+        T: Default + Real,
+    {
+        let radii = self.radius + other.radius;
+
+        (other.center - self.center).length_squared() <= radii * radii
+    }
+
+    pub fn intersects_aabb(&self, aabb: &Aabb<N, T>) -> bool
+    where
+        T: Default + Real,
+    {
+        aabb.distance_squared(&self.center) <= self.radius * self.radius
+    }
+}
+
+impl<T> Sphere3<T> {
+    pub fn intersects_plane(&self, plane: &Plane<T>) -> bool
+    where
+        T: Default + Real,
+    {
+        plane.signed_distance(&self.center) <= self.radius
+    }
+
+    pub fn intersects_capsule(&self, capsule: &Capsule<T>) -> bool
+    where
+        T: Default + Real,
+    {
+        let radii = self.radius + capsule.radius;
+        let closest = closest_point_on_segment(&capsule.a, &capsule.b, &self.center);
+
+        (self.center - closest).length_squared() <= radii * radii
+    }
+
+    pub fn intersects_cylinder(&self, cylinder: &Cylinder<T>) -> bool
+    where
+        T: Default + Real,
+    {
+        self.contains(&cylinder.closest_point(&self.center))
+    }
+}
+
+// Points x where normal · x = distance, the normal is expected to be normalized. Containment and
+// overlap tests treat it as the solid half-space behind the plane
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Plane<T> {
+    pub normal: Vector3<T>,
+    pub distance: T,
+}
+
+impl<T> Plane<T> {
+    pub const fn new(normal: Vector3<T>, distance: T) -> Self {
+        Self { normal, distance }
+    }
+
+    pub fn from_point_normal(point: &Vector3<T>, normal: &Vector3<T>) -> Self
+    where
+        T: Default + Real,
+    {
+        let normal = normal.normalized();
+
+        Self::new(normal, normal.dot(point))
+    }
+
+    // The normal faces the side from which a -> b -> c is counter-clockwise, None if collinear
+    pub fn from_points(a: &Vector3<T>, b: &Vector3<T>, c: &Vector3<T>) -> Option<Self>
+    where
+        T: Default + Real,
+    {
+        let normal = (*b - a).cross(&(*c - a));
+
+        (normal.length() > T::epsilon()).then(|| Self::from_point_normal(a, &normal))
+    }
+
+    // Positive in front of the plane
+    pub fn signed_distance(&self, point: &Vector3<T>) -> T
+    where
+        T: Default + Real,
+    {
+        self.normal.dot(point) - self.distance
+    }
+
+    pub fn project(&self, point: &Vector3<T>) -> Vector3<T>
+    where
+        T: Default + Real,
+    {
+        *point - self.normal * self.signed_distance(point)
+    }
+
+    pub fn flipped(&self) -> Self
+    where
+        T: Real,
+    {
+        Self::new(-self.normal, -self.distance)
+    }
+
+    pub fn contains(&self, point: &Vector3<T>) -> bool
+    where
+        T: Default + Real,
+    {
+        self.signed_distance(point) <= T::zero()
+    }
+
+    pub fn closest_point(&self, point: &Vector3<T>) -> Vector3<T>
+    where
+        T: Default + Real,
+    {
+        if self.contains(point) {
+            *point
+        } else {
+            self.project(point)
+        }
+    }
+
+    pub fn intersects_sphere(&self, sphere: &Sphere3<T>) -> bool
+    where
+        T: Default + Real,
+    {
+        sphere.intersects_plane(self)
+    }
+
+    pub fn intersects_capsule(&self, capsule: &Capsule<T>) -> bool
+    where
+        T: Default + Real,
+    {
+        capsule.intersects_plane(self)
+    }
+
+    pub fn intersects_cylinder(&self, cylinder: &Cylinder<T>) -> bool
+    where
+        T: Default + Real,
+    {
+        cylinder.intersects_plane(self)
+    }
+}
+
+// Sphere swept along the segment a-b
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Capsule<T> {
+    pub a: Vector3<T>,
+    pub b: Vector3<T>,
+    pub radius: T,
+}
+
+impl<T> Capsule<T> {
+    pub const fn new(a: Vector3<T>, b: Vector3<T>, radius: T) -> Self {
+        Self { a, b, radius }
+    }
+
+    pub fn contains(&self, point: &Vector3<T>) -> bool
+    where
+        T: Default + Real,
+    {
+        let closest = closest_point_on_segment(&self.a, &self.b, point);
+
+        (*point - closest).length_squared() <= self.radius * self.radius
+    }
+
+    pub fn closest_point(&self, point: &Vector3<T>) -> Vector3<T>
+    where
+        T: Default + Real,
+    {
+        let closest = closest_point_on_segment(&self.a, &self.b, point);
+        let offset = *point - closest;
+
+        if offset.length_squared() <= self.radius * self.radius {
+            *point
+        } else {
+            closest + offset.normalized() * self.radius
+        }
+    }
+
+    pub fn bounding_box(&self) -> Aabb3<T>
+    where
+        T: Real,
+    {
+        let radius = Vector::new_val(self.radius);
+
+        Aabb::new(self.a.min(&self.b) - radius, self.a.max(&self.b) + radius)
+    }
+
+    pub fn intersects_plane(&self, plane: &Plane<T>) -> bool
+    where
+        T: Default + Real,
+    {
+        plane
+            .signed_distance(&self.a)
+            .min(plane.signed_distance(&self.b))
+            <= self.radius
+    }
+
+    pub fn intersects_sphere(&self, sphere: &Sphere3<T>) -> bool
+    where
+        T: Default + Real,
+    {
+        sphere.intersects_capsule(self)
+    }
+
+    pub fn intersects_capsule(&self, other: &Self) -> bool
+    where
+        T: Default + Real,
+    {
+        let radii = self.radius + other.radius;
+        let (p, q) = closest_points_on_segments(&self.a, &self.b, &other.a, &other.b);
+
+        (q - p).length_squared() <= radii * radii
+    }
+
+    pub fn intersects_cylinder(&self, cylinder: &Cylinder<T>) -> bool
+    where
+        T: Default + Real,
+    {
+        // The segment at the capsule's core against the cylinder, then inflated
+        gjk_distance(&[self.a, self.b][..], cylinder)
+            .is_none_or(|separation| separation.distance <= self.radius)
+    }
+}
+
+// Solid cylinder with flat caps centered on a and b
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Cylinder<T> {
+    pub a: Vector3<T>,
+    pub b: Vector3<T>,
+    pub radius: T,
+}
+
+impl<T> Cylinder<T> {
+    pub const fn new(a: Vector3<T>, b: Vector3<T>, radius: T) -> Self {
+        Self { a, b, radius }
+    }
+
+    pub fn center(&self) -> Vector3<T>
+    where
+        T: Real,
+    {
+        (self.a + self.b) / (T::one() + T::one())
+    }
+
+    // Unclamped position along the axis in 0..1 between the caps, and the radial offset from it
+    fn decompose(&self, point: &Vector3<T>) -> (T, Vector3<T>)
+    where
+        T: Default + Real,
+    {
+        let ab = self.b - self.a;
+        let len_sq = ab.length_squared();
+        let t = if len_sq > T::zero() {
+            (*point - self.a).dot(&ab) / len_sq
+        } else {
+            T::zero()
+        };
+
+        (t, *point - (self.a + ab * t))
+    }
+
+    pub fn contains(&self, point: &Vector3<T>) -> bool
+    where
+        T: Default + Real,
+    {
+        let (t, radial) = self.decompose(point);
+
+        t >= T::zero() && t <= T::one() && radial.length_squared() <= self.radius * self.radius
+    }
+
+    pub fn closest_point(&self, point: &Vector3<T>) -> Vector3<T>
+    where
+        T: Default + Real,
+    {
+        if self.contains(point) {
+            return *point;
+        }
+
+        // The axial and radial parts are orthogonal, so each can be clamped on its own
+        let (t, radial) = self.decompose(point);
+        let len = radial.length();
+        let radial = if len > self.radius {
+            radial * (self.radius / len)
+        } else {
+            radial
+        };
+
+        self.a + (self.b - self.a) * clamp01(t) + radial
+    }
+
+    // Extent of the cap disks along each world axis
+    fn cap_extents(&self) -> Vector3<T>
+    where
+        T: Default + Real,
+    {
+        let axis = (self.b - self.a).normalized();
+
+        Vector::new(
+            axis.0
+                .map(|val| self.radius * (T::one() - val * val).max(T::zero()).sqrt()),
+        )
+    }
+
+    pub fn bounding_box(&self) -> Aabb3<T>
+    where
+        T: Default + Real,
+    {
+        let extents = self.cap_extents();
+
+        Aabb::new(self.a.min(&self.b) - extents, self.a.max(&self.b) + extents)
+    }
+
+    pub fn intersects_plane(&self, plane: &Plane<T>) -> bool
+    where
+        T: Default + Real,
+    {
+        let axis = (self.b - self.a).normalized();
+        let cos = plane.normal.dot(&axis);
+        let rim = self.radius * (T::one() - cos * cos).max(T::zero()).sqrt();
+
+        plane
+            .signed_distance(&self.a)
+            .min(plane.signed_distance(&self.b))
+            - rim
+            <= T::zero()
+    }
+
+    pub fn intersects_sphere(&self, sphere: &Sphere3<T>) -> bool
+    where
+        T: Default + Real,
+    {
+        sphere.intersects_cylinder(self)
+    }
+
+    pub fn intersects_capsule(&self, capsule: &Capsule<T>) -> bool
+    where
+        T: Default + Real,
+    {
+        capsule.intersects_cylinder(self)
+    }
+
+    pub fn intersects_cylinder(&self, other: &Self) -> bool
+    where
+        T: Default + Real,
+    {
+        gjk_distance(self, other).is_none_or(|separation| {
+            separation.distance <= T::epsilon().sqrt() * (self.radius + other.radius)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{test_util::assert_close, vector};
+
+    #[test]
+    fn segments() {
+        let (a, b) = (vector!(0.0, 0.0, 0.0), vector!(4.0, 0.0, 0.0));
+
+        assert_eq!(
+            closest_point_on_segment(&a, &b, &vector!(2.0, 3.0, 0.0)),
+            vector!(2.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            closest_point_on_segment(&a, &b, &vector!(-2.0, 3.0, 0.0)),
+            a
+        );
+
+        let (p, q) =
+            closest_points_on_segments(&a, &b, &vector!(1.0, -1.0, 2.0), &vector!(1.0, 1.0, 2.0));
+        assert_close(p, vector!(1.0, 0.0, 0.0), 1e-9);
+        assert_close(q, vector!(1.0, 0.0, 2.0), 1e-9);
+
+        // Parallel and past the end
+        let (p, q) =
+            closest_points_on_segments(&a, &b, &vector!(6.0, 1.0, 0.0), &vector!(9.0, 1.0, 0.0));
+        assert_close(p, b, 1e-9);
+        assert_close(q, vector!(6.0, 1.0, 0.0), 1e-9);
+    }
+
+    #[test]
+    fn plane() {
+        let plane = Plane::from_points(
+            &vector!(0.0, 2.0, 0.0),
+            &vector!(0.0, 2.0, 1.0),
+            &vector!(1.0, 2.0, 0.0),
+        )
+        .unwrap();
+
+        assert_close(plane.normal, vector!(0.0, 1.0, 0.0), 1e-9);
+        assert_eq!(plane.distance, 2.0);
+        assert_eq!(plane.signed_distance(&vector!(3.0, 5.0, 1.0)), 3.0);
+        assert_eq!(
+            plane.project(&vector!(3.0, 5.0, 1.0)),
+            vector!(3.0, 2.0, 1.0)
+        );
+        assert_eq!(
+            plane.flipped().signed_distance(&vector!(3.0, 5.0, 1.0)),
+            -3.0
+        );
+
+        assert!(plane.contains(&vector!(0.0, 1.0, 0.0)));
+        assert!(!plane.contains(&vector!(0.0, 3.0, 0.0)));
+        assert_eq!(
+            plane.closest_point(&vector!(1.0, 1.0, 1.0)),
+            vector!(1.0, 1.0, 1.0)
+        );
+
+        assert!(PlaneD::from_points(
+            &vector!(0.0, 0.0, 0.0),
+            &vector!(1.0, 1.0, 1.0),
+            &vector!(2.0, 2.0, 2.0)
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn sphere() {
+        let sphere = Sphere3D::new(vector!(1.0, 0.0, 0.0), 2.0);
+
+        assert!(sphere.contains(&vector!(2.0, 1.0, 1.0)));
+        assert!(!sphere.contains(&vector!(3.0, 1.0, 0.0)));
+        assert_eq!(
+            sphere.closest_point(&vector!(1.0, 5.0, 0.0)),
+            vector!(1.0, 2.0, 0.0)
+        );
+        assert_eq!(
+            sphere.bounding_box(),
+            Aabb::new(vector!(-1.0, -2.0, -2.0), vector!(3.0, 2.0, 2.0))
+        );
+
+        let circle = Sphere2D::new(vector!(0.0, 0.0), 1.0);
+        assert!(circle.intersects_sphere(&Sphere::new(vector!(1.5, 0.0), 0.5)));
+        assert!(!circle.intersects_sphere(&Sphere::new(vector!(1.5, 0.0), 0.4)));
+        assert!(circle.intersects_aabb(&Aabb::new(vector!(0.5, 0.5), vector!(2.0, 2.0))));
+        assert!(!circle.intersects_aabb(&Aabb::new(vector!(0.8, 0.8), vector!(2.0, 2.0))));
+    }
+
+    #[test]
+    fn capsule() {
+        let capsule = CapsuleD::new(vector!(0.0, 0.0, 0.0), vector!(0.0, 4.0, 0.0), 1.0);
+
+        assert!(capsule.contains(&vector!(0.5, 2.0, 0.5)));
+        assert!(capsule.contains(&vector!(0.0, 4.9, 0.0)));
+        assert!(!capsule.contains(&vector!(0.9, 4.9, 0.0)));
+        assert_close(
+            capsule.closest_point(&vector!(3.0, 2.0, 0.0)),
+            vector!(1.0, 2.0, 0.0),
+            1e-9,
+        );
+        assert_close(
+            capsule.closest_point(&vector!(0.0, -3.0, 0.0)),
+            vector!(0.0, -1.0, 0.0),
+            1e-9,
+        );
+        assert_eq!(
+            capsule.bounding_box(),
+            Aabb::new(vector!(-1.0, -1.0, -1.0), vector!(1.0, 5.0, 1.0))
+        );
+    }
+
+    #[test]
+    fn cylinder() {
+        let cylinder = CylinderD::new(vector!(0.0, 0.0, 0.0), vector!(0.0, 4.0, 0.0), 1.0);
+
+        assert!(cylinder.contains(&vector!(0.5, 2.0, 0.5)));
+        assert!(!cylinder.contains(&vector!(0.0, 4.5, 0.0)));
+        assert_close(
+            cylinder.closest_point(&vector!(3.0, 6.0, 0.0)),
+            vector!(1.0, 4.0, 0.0),
+            1e-9,
+        );
+        assert_close(
+            cylinder.closest_point(&vector!(0.5, -2.0, 0.0)),
+            vector!(0.5, 0.0, 0.0),
+            1e-9,
+        );
+        assert_eq!(
+            cylinder.bounding_box(),
+            Aabb::new(vector!(-1.0, 0.0, -1.0), vector!(1.0, 4.0, 1.0))
+        );
+
+        // Tilted 45° in the xy plane, the caps stick out by cos 45° on x and y
+        let tilted = CylinderD::new(vector!(0.0, 0.0, 0.0), vector!(2.0, 2.0, 0.0), 1.0);
+        let h = std::f64::consts::FRAC_1_SQRT_2;
+        let aabb = tilted.bounding_box();
+        assert_close(aabb.min, vector!(-h, -h, -1.0), 1e-9);
+        assert_close(aabb.max, vector!(2.0 + h, 2.0 + h, 1.0), 1e-9);
+    }
+
+    #[test]
+    fn overlaps() {
+        let ground = PlaneD::new(vector!(0.0, 1.0, 0.0), 0.0);
+        let sphere = Sphere3D::new(vector!(0.0, 0.9, 0.0), 1.0);
+        let capsule = CapsuleD::new(vector!(2.0, 1.5, 0.0), vector!(2.0, 4.0, 0.0), 1.0);
+        let lying = CylinderD::new(vector!(-1.0, 0.5, 3.0), vector!(1.0, 0.5, 3.0), 0.6);
+        let standing = CylinderD::new(vector!(-3.0, 1.0, 0.0), vector!(-3.0, 3.0, 0.0), 1.0);
+
+        assert!(ground.intersects_sphere(&sphere));
+        assert!(!ground.intersects_capsule(&capsule));
+        assert!(ground.intersects_cylinder(&lying));
+        assert!(!ground.intersects_cylinder(&standing));
+
+        // Tilted off the plane, the rim dips below it
+        let tilted = CylinderD::new(vector!(0.0, 0.5, 0.0), vector!(1.0, 1.5, 0.0), 1.0);
+        assert!(ground.intersects_cylinder(&tilted));
+
+        assert_eq!(
+            sphere.intersects_capsule(&capsule),
+            capsule.intersects_sphere(&sphere)
+        );
+        assert!(sphere.intersects_capsule(&CapsuleD::new(
+            vector!(1.5, 0.0, 0.0),
+            vector!(1.5, 4.0, 0.0),
+            0.6
+        )));
+        assert!(!sphere.intersects_capsule(&capsule));
+        assert_eq!(
+            sphere.intersects_cylinder(&standing),
+            standing.intersects_sphere(&sphere)
+        );
+        assert!(!sphere.intersects_cylinder(&standing));
+        assert!(sphere.intersects_cylinder(&CylinderD::new(
+            vector!(-2.5, 0.0, 0.0),
+            vector!(-2.5, 1.0, 0.0),
+            1.6
+        )));
+
+        let crossing = CapsuleD::new(vector!(3.5, 3.0, -2.0), vector!(3.5, 3.0, 2.0), 0.6);
+        assert!(capsule.intersects_capsule(&crossing));
+        assert!(!capsule.intersects_capsule(&CapsuleD::new(
+            vector!(3.7, 3.0, -2.0),
+            vector!(3.7, 3.0, 2.0),
+            0.6
+        )));
+
+        // Capsule above the cylinder's top cap, then dipping onto it
+        let above = CapsuleD::new(vector!(-3.0, 3.5, -2.0), vector!(-3.0, 3.5, 2.0), 0.4);
+        assert!(!above.intersects_cylinder(&standing));
+        assert!(standing.intersects_capsule(&CapsuleD::new(
+            vector!(-3.0, 3.5, -2.0),
+            vector!(-3.0, 3.5, 2.0),
+            0.6
+        )));
+
+        // Crossed cylinders touching near the rim and apart
+        let across = CylinderD::new(vector!(-5.0, 3.5, 0.0), vector!(-1.0, 3.5, 0.0), 0.6);
+        assert!(standing.intersects_cylinder(&across));
+        assert!(!standing.intersects_cylinder(&CylinderD::new(
+            vector!(-5.0, 3.5, 0.0),
+            vector!(-1.0, 3.5, 0.0),
+            0.4
+        )));
+        let beside = CylinderD::new(vector!(-5.0, 2.0, 1.5), vector!(-1.0, 2.0, 1.5), 0.6);
+        assert!(standing.intersects_cylinder(&beside));
+        assert!(!standing.intersects_cylinder(&CylinderD::new(
+            vector!(-5.0, 2.0, 1.7),
+            vector!(-1.0, 2.0, 1.7),
+            0.6
+        )));
+        // Oblique pair overlapping by about a tenth of the radius, in both orders
+        let a = CylinderD::new(
+            vector!(0.9781, 1.8801, 0.5450),
+            vector!(1.2073, 1.1019, 1.2389),
+            0.5,
+        );
+        let b = CylinderD::new(
+            vector!(0.9137, 0.5677, 0.5935),
+            vector!(1.2226, 1.8706, -1.5496),
+            0.5,
+        );
+        assert!(a.intersects_cylinder(&b));
+        assert!(b.intersects_cylinder(&a));
+    }
+}
@@ -0,0 +1,368 @@
+use std::collections::HashMap;
+
+use num_traits::real::Real;
+
+use crate::{aabb::Aabb, vector::Vector};
+
+pub type SpatialHash2<T, V> = SpatialHash<2, T, V>;
+pub type SpatialHash3<T, V> = SpatialHash<3, T, V>;
+
+macro_rules! spatial_hash_types {
+    ($($n:literal),*) => {
+        paste::paste! {
+            $(
+                pub type [< SpatialHash $n F >]<V> = [< SpatialHash $n >]<f32, V>;
+                pub type [< SpatialHash $n D >]<V> = [< SpatialHash $n >]<f64, V>;
+            )*
+        }
+    };
+}
+
+spatial_hash_types!(2, 3);
+
+// Every cell from min to max inclusive
+fn cells_between<const N: usize>(
+    min: Vector<N, i32>,
+    max: Vector<N, i32>,
+) -> impl Iterator<Item = Vector<N, i32>> {
+    let mut next = (0..N).all(|i| min[i] <= max[i]).then_some(min);
+
+    std::iter::from_fn(move || {
+        let current = next?;
+
+        // Count up like an odometer, the first axis turning fastest
+        let mut cell = current;
+        next = (0..N)
+            .find(|&i| {
+                if cell[i] < max[i] {
+                    cell[i] += 1;
+                    true
+                } else {
+                    cell[i] = min[i];
+                    false
+                }
+            })
+            .map(|_| cell);
+
+        Some(current)
+    })
+}
+
+// Items bucketed by the grid cell their position falls in, cell c covers c * size up to
+// (c + 1) * size on each axis. Ids are handed out on insert and reused once removed
+#[derive(Debug, Clone)]
+pub struct SpatialHash<const N: usize, T, V> {
+    cell_size: T,
+    cells: HashMap<Vector<N, i32>, Vec<usize>>,
+    items: Vec<Option<(Vector<N, T>, V)>>,
+    free: Vec<usize>,
+}
+
+impl<const N: usize, T, V> SpatialHash<N, T, V> {
+    pub fn new(cell_size: T) -> Self
+    where
+        T: Real,
+    {
+        assert!(cell_size > T::zero(), "cell size has to be positive");
+
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+            items: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+
+    pub fn cell_size(&self) -> T
+    where
+        T: Copy,
+    {
+        self.cell_size
+    }
+
+    // Cells past the range of i32 are clamped to it
+    pub fn cell(&self, position: &Vector<N, T>) -> Vector<N, i32>
+    where
+        T: Real,
+    {
+        Vector::new(std::array::from_fn(|i| {
+            let cell = (position[i] / self.cell_size).floor();
+
+            cell.to_i32()
+                .unwrap_or(if cell < T::zero() { i32::MIN } else { i32::MAX })
+        }))
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len() - self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn insert(&mut self, position: Vector<N, T>, value: V) -> usize
+    where
+        T: Real,
+    {
+        let id = match self.free.pop() {
+            Some(id) => {
+                self.items[id] = Some((position, value));
+                id
+            }
+            None => {
+                self.items.push(Some((position, value)));
+                self.items.len() - 1
+            }
+        };
+
+        self.cells.entry(self.cell(&position)).or_default().push(id);
+
+        id
+    }
+
+    fn unlink(&mut self, id: usize, cell: &Vector<N, i32>) {
+        if let Some(ids) = self.cells.get_mut(cell) {
+            ids.retain(|&other| other != id);
+
+            if ids.is_empty() {
+                self.cells.remove(cell);
+            }
+        }
+    }
+
+    pub fn remove(&mut self, id: usize) -> Option<(Vector<N, T>, V)>
+    where
+        T: Real,
+    {
+        let (position, value) = self.items.get_mut(id)?.take()?;
+
+        self.unlink(id, &self.cell(&position));
+        self.free.push(id);
+
+        Some((position, value))
+    }
+
+    // False if there's no such item
+    pub fn move_to(&mut self, id: usize, position: Vector<N, T>) -> bool
+    where
+        T: Real,
+    {
+        let Some(Some((current, _))) = self.items.get(id) else {
+            return false;
+        };
+
+        let (from, to) = (self.cell(current), self.cell(&position));
+        if from != to {
+            self.unlink(id, &from);
+            self.cells.entry(to).or_default().push(id);
+        }
+
+        if let Some((current, _)) = &mut self.items[id] {
+            *current = position;
+        }
+
+        true
+    }
+
+    pub fn get(&self, id: usize) -> Option<(&Vector<N, T>, &V)> {
+        self.items
+            .get(id)?
+            .as_ref()
+            .map(|(position, value)| (position, value))
+    }
+
+    pub fn get_mut(&mut self, id: usize) -> Option<&mut V> {
+        self.items.get_mut(id)?.as_mut().map(|(_, value)| value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &Vector<N, T>, &V)> {
+        self.items
+            .iter()
+            .enumerate()
+            .filter_map(|(id, item)| item.as_ref().map(|(position, value)| (id, position, value)))
+    }
+
+    // Ids of the items in a cell
+    pub fn cell_items(&self, cell: &Vector<N, i32>) -> &[usize] {
+        self.cells.get(cell).map_or(&[], |ids| ids.as_slice())
+    }
+
+    pub fn occupied_cells(&self) -> impl Iterator<Item = (&Vector<N, i32>, &[usize])> {
+        self.cells.iter().map(|(cell, ids)| (cell, ids.as_slice()))
+    }
+
+    // The cell itself and all around it up to range cells away on every axis, 3^N of them for a
+    // range of one
+    pub fn neighbor_cells(
+        &self,
+        cell: &Vector<N, i32>,
+        range: i32,
+    ) -> impl Iterator<Item = Vector<N, i32>> {
+        let min = Vector::new(std::array::from_fn(|i| cell[i].saturating_sub(range)));
+        let max = Vector::new(std::array::from_fn(|i| cell[i].saturating_add(range)));
+
+        cells_between(min, max)
+    }
+
+    // Ids of the items in the cells touching the box, visiting only the occupied ones when the box
+    // spans more cells than that
+    fn candidates(&self, aabb: &Aabb<N, T>) -> Vec<usize>
+    where
+        T: Real,
+    {
+        let (min, max) = (self.cell(&aabb.min), self.cell(&aabb.max));
+        let count = (0..N).fold(1u64, |count, i| {
+            count.saturating_mul((max[i] as i64 - min[i] as i64 + 1).max(0) as u64)
+        });
+
+        if count > self.cells.len() as u64 {
+            self.cells
+                .iter()
+                .filter(|(cell, _)| (0..N).all(|i| min[i] <= cell[i] && cell[i] <= max[i]))
+                .flat_map(|(_, ids)| ids.iter().copied())
+                .collect()
+        } else {
+            cells_between(min, max)
+                .flat_map(|cell| self.cell_items(&cell).iter().copied())
+                .collect()
+        }
+    }
+
+    // Ids of the items positioned inside the box
+    pub fn query_aabb(&self, aabb: &Aabb<N, T>) -> Vec<usize>
+    where
+        T: Real,
+    {
+        self.candidates(aabb)
+            .into_iter()
+            .filter(|&id| {
+                self.get(id)
+                    .is_some_and(|(position, _)| aabb.contains_point(position))
+            })
+            .collect()
+    }
+
+    // Ids of the items within the distance of the center
+    pub fn query_radius(&self, center: &Vector<N, T>, radius: T) -> Vec<usize>
+    where
+        T: Default + Real,
+    {
+        let aabb = Aabb::new(*center, *center).expand(radius);
+
+        self.candidates(&aabb)
+            .into_iter()
+            .filter(|&id| {
+                self.get(id).is_some_and(|(position, _)| {
+                    (*position - center).length_squared() <= radius * radius
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::vector;
+
+    fn sorted(mut ids: Vec<usize>) -> Vec<usize> {
+        ids.sort_unstable();
+        ids
+    }
+
+    #[test]
+    fn cells() {
+        let grid = SpatialHash2D::<()>::new(2.0);
+
+        assert_eq!(grid.cell(&vector!(0.0, 1.9)), vector!(0, 0));
+        assert_eq!(grid.cell(&vector!(-0.1, 2.0)), vector!(-1, 1));
+        assert_eq!(
+            grid.cell(&vector!(1e20, -1e20)),
+            vector!(i32::MAX, i32::MIN)
+        );
+
+        let around: Vec<_> = grid.neighbor_cells(&vector!(0, 0), 1).collect();
+        assert_eq!(around.len(), 9);
+        assert_eq!(around[0], vector!(-1, -1));
+        assert_eq!(around[1], vector!(0, -1));
+        assert_eq!(around[8], vector!(1, 1));
+
+        assert_eq!(
+            SpatialHash::<4, f32, ()>::new(1.0)
+                .neighbor_cells(&Vector::new_val(0), 1)
+                .count(),
+            81
+        );
+    }
+
+    #[test]
+    fn insert_remove_move() {
+        let mut grid = SpatialHash3F::new(1.0);
+
+        let a = grid.insert(vector!(0.5, 0.5, 0.5), "a");
+        let b = grid.insert(vector!(0.6, 0.2, 0.9), "b");
+        let c = grid.insert(vector!(5.5, 0.5, 0.5), "c");
+        assert_eq!(grid.len(), 3);
+        assert_eq!(
+            sorted(grid.cell_items(&vector!(0, 0, 0)).to_vec()),
+            vec![a, b]
+        );
+
+        assert!(grid.move_to(b, vector!(5.1, 0.1, 0.1)));
+        assert_eq!(grid.cell_items(&vector!(0, 0, 0)), &[a]);
+        assert_eq!(
+            sorted(grid.cell_items(&vector!(5, 0, 0)).to_vec()),
+            vec![b, c]
+        );
+        assert_eq!(grid.get(b), Some((&vector!(5.1, 0.1, 0.1), &"b")));
+
+        assert_eq!(grid.remove(a), Some((vector!(0.5, 0.5, 0.5), "a")));
+        assert_eq!(grid.remove(a), None);
+        assert!(!grid.move_to(a, vector!(0.0, 0.0, 0.0)));
+        assert!(grid.cell_items(&vector!(0, 0, 0)).is_empty());
+        assert_eq!(grid.occupied_cells().count(), 1);
+
+        // Freed ids get reused
+        let d = grid.insert(vector!(-3.0, 0.0, 0.0), "d");
+        assert_eq!(d, a);
+        *grid.get_mut(d).unwrap() = "e";
+        assert_eq!(
+            grid.iter().map(|(_, _, v)| *v).collect::<Vec<_>>(),
+            ["e", "b", "c"]
+        );
+    }
+
+    #[test]
+    fn queries() {
+        let mut grid = SpatialHash2D::new(1.0);
+        let mut ids = Vec::new();
+        for x in -10..10 {
+            for y in -10..10 {
+                let position = vector!(x as f64 * 0.5, y as f64 * 0.5);
+                ids.push((grid.insert(position, ()), position));
+            }
+        }
+
+        let center = vector!(0.3, -0.2);
+        let expected: Vec<_> = ids
+            .iter()
+            .filter(|(_, p)| (*p - center).length() <= 1.7)
+            .map(|(id, _)| *id)
+            .collect();
+        assert_eq!(sorted(grid.query_radius(&center, 1.7)), expected);
+
+        let aabb = Aabb::new(vector!(-1.0, 0.0), vector!(0.75, 2.5));
+        let expected: Vec<_> = ids
+            .iter()
+            .filter(|(_, p)| aabb.contains_point(p))
+            .map(|(id, _)| *id)
+            .collect();
+        assert_eq!(sorted(grid.query_aabb(&aabb)), expected);
+
+        // Far larger than the occupied cells, still finds everything
+        let all = Aabb::new(vector!(-1e6, -1e6), vector!(1e6, 1e6));
+        assert_eq!(grid.query_aabb(&all).len(), 400);
+    }
+}
@@ -0,0 +1,542 @@
+use num_traits::real::Real;
+
+use crate::{curve::Curve, vector::Vector};
+
+pub type CatmullRom2<T> = CatmullRom<2, T>;
+pub type CatmullRom3<T> = CatmullRom<3, T>;
+
+pub type CubicHermite2<T> = CubicHermite<2, T>;
+pub type CubicHermite3<T> = CubicHermite<3, T>;
+
+pub type BSpline2<T> = BSpline<2, T>;
+pub type BSpline3<T> = BSpline<3, T>;
+
+macro_rules! spline_types {
+    ($($name:ident),*) => {
+        paste::paste! {
+            $(
+                pub type [< $name 2F >] = [< $name 2 >]<f32>;
+                pub type [< $name 2D >] = [< $name 2 >]<f64>;
+                pub type [< $name 3F >] = [< $name 3 >]<f32>;
+                pub type [< $name 3D >] = [< $name 3 >]<f64>;
+            )*
+        }
+    };
+}
+
+spline_types!(CatmullRom, CubicHermite, BSpline);
+
+// Segment index and the parameter within it for splines with segments over 0..1, 1..2 and so on
+fn locate<T>(t: T, segments: usize) -> (usize, T)
+where
+    T: Real,
+{
+    let t = t.max(T::zero()).min(T::from(segments).unwrap());
+    let i = t.floor().to_usize().unwrap_or(0).min(segments - 1);
+
+    (i, t - T::from(i).unwrap())
+}
+
+// Cubic from p0 to p1 over s in 0..1 leaving with tangent m0 and arriving with m1, differentiated
+// order times
+fn hermite<const N: usize, T>(
+    p0: Vector<N, T>,
+    m0: Vector<N, T>,
+    p1: Vector<N, T>,
+    m1: Vector<N, T>,
+    s: T,
+    order: usize,
+) -> Vector<N, T>
+where
+    T: Real,
+{
+    let c = |x: f64| T::from(x).unwrap();
+    let (s2, s3) = (s * s, s * s * s);
+
+    let [h00, h10, h01, h11] = match order {
+        0 => [
+            c(2.0) * s3 - c(3.0) * s2 + T::one(),
+            s3 - c(2.0) * s2 + s,
+            c(3.0) * s2 - c(2.0) * s3,
+            s3 - s2,
+        ],
+        1 => [
+            c(6.0) * s2 - c(6.0) * s,
+            c(3.0) * s2 - c(4.0) * s + T::one(),
+            c(6.0) * s - c(6.0) * s2,
+            c(3.0) * s2 - c(2.0) * s,
+        ],
+        2 => [
+            c(12.0) * s - c(6.0),
+            c(6.0) * s - c(4.0),
+            c(6.0) - c(12.0) * s,
+            c(6.0) * s - c(2.0),
+        ],
+        3 => [c(12.0), c(6.0), c(-12.0), c(6.0)],
+        _ => return Vector::new_val(T::zero()),
+    };
+
+    p0 * h00 + m0 * h10 + p1 * h01 + m1 * h11
+}
+
+// How far apart consecutive points are in parameter, which trades between overshooting and
+// cutting corners. Centripetal never forms cusps or loops within a segment
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Parametrization {
+    Uniform,
+    #[default]
+    Centripetal,
+    Chordal,
+}
+
+impl Parametrization {
+    fn alpha<T: Real>(&self) -> T {
+        match self {
+            Parametrization::Uniform => T::zero(),
+            Parametrization::Centripetal => T::from(0.5).unwrap(),
+            Parametrization::Chordal => T::one(),
+        }
+    }
+}
+
+// Passes through every point, with the point i at t = i. The ends get a mirrored neighbor so the
+// first and last segments are curved like the rest
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatmullRom<const N: usize, T> {
+    pub points: Vec<Vector<N, T>>,
+    pub parametrization: Parametrization,
+}
+
+impl<const N: usize, T> CatmullRom<N, T> {
+    // None with fewer than two points
+    pub fn new(points: Vec<Vector<N, T>>, parametrization: Parametrization) -> Option<Self> {
+        (points.len() >= 2).then_some(Self {
+            points,
+            parametrization,
+        })
+    }
+
+    // Segment i as a Hermite cubic over 0..1
+    fn segment(&self, i: usize) -> [Vector<N, T>; 4]
+    where
+        T: Default + Real,
+    {
+        let last = self.points.len() - 1;
+        let (p1, p2) = (self.points[i], self.points[i + 1]);
+        let p0 = if i > 0 {
+            self.points[i - 1]
+        } else {
+            p1 * (T::one() + T::one()) - p2
+        };
+        let p3 = if i + 1 < last {
+            self.points[i + 2]
+        } else {
+            p2 * (T::one() + T::one()) - p1
+        };
+
+        // Knot spacing, with repeated points spaced as if uniform instead of dividing by zero
+        let alpha = self.parametrization.alpha();
+        let spacing = |a: Vector<N, T>, b: Vector<N, T>| {
+            let d = (b - a).length().powf(alpha);
+
+            if d > T::zero() {
+                d
+            } else {
+                T::one()
+            }
+        };
+        let (d0, d1, d2) = (spacing(p0, p1), spacing(p1, p2), spacing(p2, p3));
+
+        let m1 = ((p1 - p0) / d0 - (p2 - p0) / (d0 + d1) + (p2 - p1) / d1) * d1;
+        let m2 = ((p2 - p1) / d1 - (p3 - p1) / (d1 + d2) + (p3 - p2) / d2) * d1;
+
+        [p1, m1, p2, m2]
+    }
+
+    fn evaluate(&self, t: T, order: usize) -> Vector<N, T>
+    where
+        T: Default + Real,
+    {
+        let (i, s) = locate(t, self.points.len() - 1);
+        let [p0, m0, p1, m1] = self.segment(i);
+
+        hermite(p0, m0, p1, m1, s, order)
+    }
+}
+
+impl<const N: usize, T> Curve<N, T> for CatmullRom<N, T>
+where
+    T: Default + Real,
+{
+    fn domain(&self) -> (T, T) {
+        (T::zero(), T::from(self.points.len() - 1).unwrap())
+    }
+
+    fn point(&self, t: T) -> Vector<N, T> {
+        self.evaluate(t, 0)
+    }
+
+    fn derivative(&self, t: T) -> Vector<N, T> {
+        self.evaluate(t, 1)
+    }
+
+    fn second_derivative(&self, t: T) -> Vector<N, T> {
+        self.evaluate(t, 2)
+    }
+
+    fn third_derivative(&self, t: T) -> Vector<N, T> {
+        self.evaluate(t, 3)
+    }
+}
+
+// Passes through every point with the given tangent, with the point i at t = i
+#[derive(Debug, Clone, PartialEq)]
+pub struct CubicHermite<const N: usize, T> {
+    pub points: Vec<Vector<N, T>>,
+    pub tangents: Vec<Vector<N, T>>,
+}
+
+impl<const N: usize, T> CubicHermite<N, T> {
+    // None with fewer than two points or a tangent missing for some
+    pub fn new(points: Vec<Vector<N, T>>, tangents: Vec<Vector<N, T>>) -> Option<Self> {
+        (points.len() >= 2 && points.len() == tangents.len()).then_some(Self { points, tangents })
+    }
+
+    fn evaluate(&self, t: T, order: usize) -> Vector<N, T>
+    where
+        T: Real,
+    {
+        let (i, s) = locate(t, self.points.len() - 1);
+
+        hermite(
+            self.points[i],
+            self.tangents[i],
+            self.points[i + 1],
+            self.tangents[i + 1],
+            s,
+            order,
+        )
+    }
+}
+
+impl<const N: usize, T> Curve<N, T> for CubicHermite<N, T>
+where
+    T: Real,
+{
+    fn domain(&self) -> (T, T) {
+        (T::zero(), T::from(self.points.len() - 1).unwrap())
+    }
+
+    fn point(&self, t: T) -> Vector<N, T> {
+        self.evaluate(t, 0)
+    }
+
+    fn derivative(&self, t: T) -> Vector<N, T> {
+        self.evaluate(t, 1)
+    }
+
+    fn second_derivative(&self, t: T) -> Vector<N, T> {
+        self.evaluate(t, 2)
+    }
+
+    fn third_derivative(&self, t: T) -> Vector<N, T> {
+        self.evaluate(t, 3)
+    }
+}
+
+// Piecewise polynomial of the degree shaped by the control points without passing through them.
+// The knots are nondecreasing and there are points.len() + degree + 1 of them, the curve runs from
+// knots[degree] to knots[points.len()]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BSpline<const N: usize, T> {
+    degree: usize,
+    points: Vec<Vector<N, T>>,
+    knots: Vec<T>,
+}
+
+impl<const N: usize, T> BSpline<N, T> {
+    // None if there aren't more points than the degree or the knots don't fit them
+    pub fn new(degree: usize, points: Vec<Vector<N, T>>, knots: Vec<T>) -> Option<Self>
+    where
+        T: Real,
+    {
+        let valid = degree >= 1
+            && points.len() > degree
+            && knots.len() == points.len() + degree + 1
+            && knots.windows(2).all(|w| w[0] <= w[1])
+            && knots[degree] < knots[points.len()];
+
+        valid.then_some(Self {
+            degree,
+            points,
+            knots,
+        })
+    }
+
+    // Knots 0, 1, 2 and so on. The curve starts and ends away from the first and last points
+    pub fn uniform(degree: usize, points: Vec<Vector<N, T>>) -> Option<Self>
+    where
+        T: Real,
+    {
+        let knots = (0..points.len() + degree + 1)
+            .map(|i| T::from(i).unwrap())
+            .collect();
+
+        Self::new(degree, points, knots)
+    }
+
+    // Evenly spaced knots with the ends repeated, so the curve starts at the first point and ends at
+    // the last, running over 0..1
+    pub fn clamped(degree: usize, points: Vec<Vector<N, T>>) -> Option<Self>
+    where
+        T: Real,
+    {
+        let spans = T::from(points.len().saturating_sub(degree).max(1)).unwrap();
+        let knots = (0..points.len() + degree + 1)
+            .map(|i| {
+                let inner = i
+                    .saturating_sub(degree)
+                    .min(points.len() - degree.min(points.len()));
+                T::from(inner).unwrap() / spans
+            })
+            .collect();
+
+        Self::new(degree, points, knots)
+    }
+
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    pub fn points(&self) -> &[Vector<N, T>] {
+        &self.points
+    }
+
+    pub fn knots(&self) -> &[T] {
+        &self.knots
+    }
+
+    // de Boor's algorithm on the points affecting t's span, after differentiating them order times
+    fn evaluate(&self, t: T, order: usize) -> Vector<N, T>
+    where
+        T: Real,
+    {
+        let (p, n, u) = (self.degree, self.points.len(), &self.knots);
+        if order > p {
+            return Vector::new_val(T::zero());
+        }
+
+        let t = t.max(u[p]).min(u[n]);
+        let k = (u.partition_point(|&knot| knot <= t) - 1).clamp(p, n - 1);
+        let base = k - p;
+
+        let mut local = self.points[base..=k].to_vec();
+
+        for r in 1..=order {
+            let d = T::from(p - r + 1).unwrap();
+
+            for j in 0..=p - r {
+                let span = u[base + j + p + 1] - u[base + j + r];
+                local[j] = if span > T::zero() {
+                    (local[j + 1] - local[j]) * d / span
+                } else {
+                    Vector::new_val(T::zero())
+                };
+            }
+        }
+
+        let q = p - order;
+        for r in 1..=q {
+            for j in (r..=q).rev() {
+                let (lo, hi) = (u[base + j + order], u[base + j + 1 + p - r]);
+                let alpha = if hi > lo {
+                    (t - lo) / (hi - lo)
+                } else {
+                    T::zero()
+                };
+
+                local[j] = local[j - 1] + (local[j] - local[j - 1]) * alpha;
+            }
+        }
+
+        local[q]
+    }
+}
+
+impl<const N: usize, T> Curve<N, T> for BSpline<N, T>
+where
+    T: Real,
+{
+    fn domain(&self) -> (T, T) {
+        (self.knots[self.degree], self.knots[self.points.len()])
+    }
+
+    fn point(&self, t: T) -> Vector<N, T> {
+        self.evaluate(t, 0)
+    }
+
+    fn derivative(&self, t: T) -> Vector<N, T> {
+        self.evaluate(t, 1)
+    }
+
+    fn second_derivative(&self, t: T) -> Vector<N, T> {
+        self.evaluate(t, 2)
+    }
+
+    fn third_derivative(&self, t: T) -> Vector<N, T> {
+        self.evaluate(t, 3)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        bezier::{integrate, Bezier},
+        curve::ArcLengthTable,
+        test_util::assert_close,
+        vector,
+    };
+
+    // Derivatives against central differences
+    fn check_derivatives<C: Curve<2, f64>>(curve: &C) {
+        let (start, end) = curve.domain();
+        let h = 1e-5;
+
+        for i in 1..20 {
+            let t = start + (end - start) * (i as f64 + 0.37) / 20.0;
+            let d1 = (curve.point(t + h) - curve.point(t - h)) / (2.0 * h);
+            let d2 = (curve.derivative(t + h) - curve.derivative(t - h)) / (2.0 * h);
+            let d3 = (curve.second_derivative(t + h) - curve.second_derivative(t - h)) / (2.0 * h);
+
+            assert!((curve.derivative(t) - d1).length() < 1e-4);
+            assert!((curve.second_derivative(t) - d2).length() < 1e-4);
+            assert!((curve.third_derivative(t) - d3).length() < 1e-4);
+        }
+    }
+
+    fn waypoints() -> Vec<Vector<2, f64>> {
+        vec![
+            vector!(0.0, 0.0),
+            vector!(1.0, 2.0),
+            vector!(1.2, 2.1),
+            vector!(4.0, 0.0),
+            vector!(5.0, 3.0),
+        ]
+    }
+
+    #[test]
+    fn catmull_rom() {
+        assert!(CatmullRom2D::new(vec![vector!(0.0, 0.0)], Parametrization::Uniform).is_none());
+
+        for parametrization in [
+            Parametrization::Uniform,
+            Parametrization::Centripetal,
+            Parametrization::Chordal,
+        ] {
+            let spline = CatmullRom::new(waypoints(), parametrization).unwrap();
+            assert_eq!(spline.domain(), (0.0, 4.0));
+
+            for (i, p) in waypoints().iter().enumerate() {
+                assert_close(spline.point(i as f64), *p, 1e-6);
+            }
+            check_derivatives(&spline);
+        }
+
+        // Uniform tangents are half the difference of the neighbors
+        let spline = CatmullRom::new(waypoints(), Parametrization::Uniform).unwrap();
+        assert_close(
+            spline.derivative(1.0),
+            (waypoints()[2] - waypoints()[0]) / 2.0,
+            1e-6,
+        );
+
+        // Points on a line stay on it
+        let line = CatmullRom2D::new(
+            vec![vector!(0.0, 0.0), vector!(1.0, 1.0), vector!(3.0, 3.0)],
+            Parametrization::Centripetal,
+        )
+        .unwrap();
+        let p = line.point(1.5);
+        assert!((p[0] - p[1]).abs() < 1e-12);
+    }
+
+    #[test]
+    fn cubic_hermite() {
+        assert!(CubicHermite2D::new(waypoints(), Vec::new()).is_none());
+
+        let tangents = vec![vector!(1.0, 0.0); 5];
+        let spline = CubicHermite::new(waypoints(), tangents).unwrap();
+
+        for (i, p) in waypoints().iter().enumerate() {
+            assert_close(spline.point(i as f64), *p, 1e-6);
+            assert_close(spline.derivative(i as f64), vector!(1.0, 0.0), 1e-6);
+        }
+        check_derivatives(&spline);
+        assert_close(spline.point(-3.0), waypoints()[0], 1e-6);
+        assert_close(spline.point(10.0), waypoints()[4], 1e-6);
+    }
+
+    #[test]
+    fn b_spline() {
+        assert!(BSpline2D::uniform(3, waypoints()[..3].to_vec()).is_none());
+        assert!(BSpline2D::new(2, waypoints(), vec![0.0; 4]).is_none());
+
+        // A single uniform cubic span is the usual matrix form
+        let points = waypoints()[..4].to_vec();
+        let spline = BSpline::uniform(3, points.clone()).unwrap();
+        assert_eq!(spline.domain(), (3.0, 4.0));
+        for s in [0.0, 0.3, 1.0] {
+            let (s2, s3) = (s * s, s * s * s);
+            let weights = [
+                (1.0 - s) * (1.0 - s) * (1.0 - s) / 6.0,
+                (3.0 * s3 - 6.0 * s2 + 4.0) / 6.0,
+                (-3.0 * s3 + 3.0 * s2 + 3.0 * s + 1.0) / 6.0,
+                s3 / 6.0,
+            ];
+            let expected = points
+                .iter()
+                .zip(weights)
+                .fold(Vector::new_val(0.0), |sum, (p, w)| sum + *p * w);
+            assert_close(spline.point(3.0 + s), expected, 1e-6);
+        }
+
+        // Clamped with as many points as the order is a Bezier curve
+        let clamped = BSpline::clamped(3, points.clone()).unwrap();
+        let bezier = Bezier::new([points[0], points[1], points[2], points[3]]);
+        assert_eq!(clamped.domain(), (0.0, 1.0));
+        for t in [0.0, 0.2, 0.7, 1.0] {
+            assert_close(clamped.point(t), bezier.point(t), 1e-6);
+            assert_close(clamped.derivative(t), bezier.derivative(t), 1e-6);
+            assert_close(
+                clamped.second_derivative(t),
+                bezier.second_derivative(t),
+                1e-6,
+            );
+        }
+
+        let clamped = BSpline::clamped(2, waypoints()).unwrap();
+        assert_close(clamped.point(0.0), waypoints()[0], 1e-6);
+        assert_close(clamped.point(1.0), waypoints()[4], 1e-6);
+        check_derivatives(&clamped);
+
+        let knots = vec![0.0, 0.0, 0.0, 0.5, 2.0, 3.0, 3.0, 3.0];
+        let spline = BSpline::new(2, waypoints(), knots).unwrap();
+        check_derivatives(&spline);
+        check_derivatives(&BSpline::uniform(4, waypoints()).unwrap());
+    }
+
+    #[test]
+    fn constant_speed() {
+        let spline = CatmullRom::new(waypoints(), Parametrization::Centripetal).unwrap();
+        let table = ArcLengthTable::new(&spline, 400);
+
+        let ts: Vec<_> = table.uniform_parameters(51).collect();
+        let speed = |t: f64| spline.derivative(t).length();
+        let step = table.length() / 50.0;
+        for w in ts.windows(2) {
+            assert!((integrate(&speed, w[0], w[1]) - step).abs() < 1e-3 * step);
+        }
+    }
+}
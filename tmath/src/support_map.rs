@@ -0,0 +1,216 @@
+use num_traits::real::Real;
+
+use crate::{
+    aabb::Aabb,
+    obb::Obb,
+    shapes::{Capsule, Cylinder, Sphere},
+    triangle::Triangle,
+    vector::{Vector, Vector3},
+};
+
+// Convex shape described by its furthest point in any direction, the direction doesn't have to be
+// normalized
+pub trait SupportMap<const N: usize, T> {
+    fn support(&self, direction: &Vector<N, T>) -> Vector<N, T>;
+}
+
+impl<const N: usize, T, S> SupportMap<N, T> for &S
+where
+    S: SupportMap<N, T> + ?Sized,
+{
+    fn support(&self, direction: &Vector<N, T>) -> Vector<N, T> {
+        (**self).support(direction)
+    }
+}
+
+// Every point of a, offset by every point of b, e.g. a box plus a sphere is a rounded box
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MinkowskiSum<A, B> {
+    pub a: A,
+    pub b: B,
+}
+
+impl<A, B> MinkowskiSum<A, B> {
+    pub const fn new(a: A, b: B) -> Self {
+        Self { a, b }
+    }
+}
+
+impl<const N: usize, T, A, B> SupportMap<N, T> for MinkowskiSum<A, B>
+where
+    T: Real,
+    A: SupportMap<N, T>,
+    B: SupportMap<N, T>,
+{
+    fn support(&self, direction: &Vector<N, T>) -> Vector<N, T> {
+        self.a.support(direction) + self.b.support(direction)
+    }
+}
+
+// Furthest of a set of points, the support of their convex hull
+fn furthest<'a, const N: usize, T, I>(points: I, direction: &Vector<N, T>) -> Vector<N, T>
+where
+    T: Default + Real + 'a,
+    I: IntoIterator<Item = &'a Vector<N, T>>,
+{
+    let mut points = points.into_iter();
+    let first = *points.next().expect("support of an empty point set");
+
+    points
+        .fold((first, first.dot(direction)), |(best, best_dot), point| {
+            let dot = point.dot(direction);
+
+            if dot > best_dot {
+                (*point, dot)
+            } else {
+                (best, best_dot)
+            }
+        })
+        .0
+}
+
+impl<const N: usize, T> SupportMap<N, T> for Vector<N, T>
+where
+    T: Copy,
+{
+    fn support(&self, _: &Vector<N, T>) -> Vector<N, T> {
+        *self
+    }
+}
+
+// Convex hull of the points, which must not be empty
+impl<const N: usize, T> SupportMap<N, T> for [Vector<N, T>]
+where
+    T: Default + Real,
+{
+    fn support(&self, direction: &Vector<N, T>) -> Vector<N, T> {
+        furthest(self, direction)
+    }
+}
+
+impl<const N: usize, T> SupportMap<N, T> for Vec<Vector<N, T>>
+where
+    T: Default + Real,
+{
+    fn support(&self, direction: &Vector<N, T>) -> Vector<N, T> {
+        furthest(self, direction)
+    }
+}
+
+impl<const N: usize, T> SupportMap<N, T> for Triangle<N, T>
+where
+    T: Default + Real,
+{
+    fn support(&self, direction: &Vector<N, T>) -> Vector<N, T> {
+        furthest(&self.vertices(), direction)
+    }
+}
+
+impl<const N: usize, T> SupportMap<N, T> for Sphere<N, T>
+where
+    T: Default + Real,
+{
+    fn support(&self, direction: &Vector<N, T>) -> Vector<N, T> {
+        self.center + direction.normalized() * self.radius
+    }
+}
+
+impl<const N: usize, T> SupportMap<N, T> for Aabb<N, T>
+where
+    T: Real,
+{
+    fn support(&self, direction: &Vector<N, T>) -> Vector<N, T> {
+        Vector::new(std::array::from_fn(|i| {
+            if direction[i] >= T::zero() {
+                self.max[i]
+            } else {
+                self.min[i]
+            }
+        }))
+    }
+}
+
+impl<const N: usize, T> SupportMap<N, T> for Obb<N, T>
+where
+    T: Default + Real,
+{
+    fn support(&self, direction: &Vector<N, T>) -> Vector<N, T> {
+        (0..N).fold(self.center, |point, i| {
+            let axis = self.axis(i);
+
+            if axis.dot(direction) >= T::zero() {
+                point + axis * self.half_extents[i]
+            } else {
+                point - axis * self.half_extents[i]
+            }
+        })
+    }
+}
+
+impl<T> SupportMap<3, T> for Capsule<T>
+where
+    T: Default + Real,
+{
+    fn support(&self, direction: &Vector3<T>) -> Vector3<T> {
+        furthest(&[self.a, self.b], direction) + direction.normalized() * self.radius
+    }
+}
+
+impl<T> SupportMap<3, T> for Cylinder<T>
+where
+    T: Default + Real,
+{
+    fn support(&self, direction: &Vector3<T>) -> Vector3<T> {
+        let axis = (self.b - self.a).normalized();
+        let radial = (*direction - axis * axis.dot(direction)).normalized();
+
+        furthest(&[self.a, self.b], direction) + radial * self.radius
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::vector;
+
+    #[test]
+    fn supports() {
+        let dir = vector!(1.0, 2.0, -1.0);
+
+        let aabb = Aabb::new(vector!(-1.0, -2.0, -3.0), vector!(1.0, 2.0, 3.0));
+        assert_eq!(aabb.support(&dir), vector!(1.0, 2.0, -3.0));
+        assert_eq!(Obb::from_aabb(&aabb).support(&dir), vector!(1.0, 2.0, -3.0));
+
+        let sphere = Sphere::new(vector!(0.0, 0.0, 1.0), 2.0);
+        assert_eq!(
+            sphere.support(&vector!(0.0, 0.0, -5.0)),
+            vector!(0.0, 0.0, -1.0)
+        );
+
+        let capsule = Capsule::new(vector!(0.0, 0.0, 0.0), vector!(0.0, 4.0, 0.0), 1.0);
+        assert_eq!(
+            capsule.support(&vector!(0.0, 1.0, 0.0)),
+            vector!(0.0, 5.0, 0.0)
+        );
+
+        let cylinder = Cylinder::new(vector!(0.0, 0.0, 0.0), vector!(0.0, 4.0, 0.0), 1.0);
+        assert_eq!(
+            cylinder.support(&vector!(3.0, -1.0, 0.0)),
+            vector!(1.0, 0.0, 0.0)
+        );
+
+        let points = [vector!(0.0, 0.0), vector!(3.0, 1.0), vector!(1.0, 2.0)];
+        assert_eq!(points[..].support(&vector!(0.0, 1.0)), vector!(1.0, 2.0));
+        assert_eq!(
+            Triangle::new(points[0], points[1], points[2]).support(&vector!(1.0, 0.0)),
+            vector!(3.0, 1.0)
+        );
+
+        let rounded = MinkowskiSum::new(aabb, Sphere::new(vector!(0.0, 0.0, 0.0), 1.0));
+        assert_eq!(
+            rounded.support(&vector!(0.0, 0.0, 1.0)),
+            vector!(1.0, 2.0, 4.0)
+        );
+    }
+}
@@ -0,0 +1,620 @@
+use num_traits::real::Real;
+
+use crate::{
+    aabb::{Aabb, Aabb3},
+    gjk::{epa_penetration, gjk_distance, Separation},
+    ray::Ray,
+    shapes::{closest_point_on_segment, Capsule, Plane, Sphere3},
+    support_map::{MinkowskiSum, SupportMap},
+    triangle::Triangle3,
+    vector::{Vector, Vector3},
+};
+
+const ADVANCEMENT_ITERATIONS: usize = 64;
+
+// Time is the fraction of the displacement covered before touching, zero if they already overlap.
+// The normal points from the target towards the moving shape
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SweepHit<const N: usize, T> {
+    pub time: T,
+    pub point: Vector<N, T>,
+    pub normal: Vector<N, T>,
+}
+
+// Normal from the contact point towards the reference point of the moving shape. Without a
+// direction to go by it faces against the motion
+fn contact<const N: usize, T>(
+    time: T,
+    point: Vector<N, T>,
+    reference: Vector<N, T>,
+    displacement: &Vector<N, T>,
+) -> SweepHit<N, T>
+where
+    T: Default + Real,
+{
+    let offset = reference - point;
+    let normal = if offset.length_squared() > T::epsilon() * T::epsilon() {
+        offset.normalized()
+    } else {
+        -displacement.normalized()
+    };
+
+    SweepHit {
+        time,
+        point,
+        normal,
+    }
+}
+
+// Conservative advancement: step along the motion by the current distance over the closing speed
+// towards the closest points, which can never step past the first contact
+pub fn time_of_impact<const N: usize, T, A, B>(
+    moving: &A,
+    displacement: &Vector<N, T>,
+    target: &B,
+) -> Option<SweepHit<N, T>>
+where
+    T: Default + Real,
+    A: SupportMap<N, T> + ?Sized,
+    B: SupportMap<N, T> + ?Sized,
+{
+    let tolerance = T::epsilon().sqrt() * displacement.length().max(T::one());
+    let two = T::one() + T::one();
+    let mut time = T::zero();
+    let mut last: Option<(T, Separation<N, T>)> = None;
+
+    for _ in 0..ADVANCEMENT_ITERATIONS {
+        let moved = MinkowskiSum::new(moving, *displacement * time);
+
+        let Some(separation) = gjk_distance(&moved, target) else {
+            // Closer than GJK can tell apart, carry the moving shape's closest point along to here
+            if let Some((previous, separation)) = last {
+                return Some(SweepHit {
+                    time,
+                    point: separation.point_a + *displacement * (time - previous),
+                    normal: (separation.point_a - separation.point_b).normalized(),
+                });
+            }
+
+            let penetration = epa_penetration(&moved, target)?;
+
+            return Some(SweepHit {
+                time,
+                point: moved.support(&penetration.normal),
+                normal: -penetration.normal,
+            });
+        };
+
+        let normal = (separation.point_a - separation.point_b) / separation.distance;
+        if separation.distance <= tolerance {
+            return Some(SweepHit {
+                time,
+                point: (separation.point_a + separation.point_b) / two,
+                normal,
+            });
+        }
+
+        let closing = -displacement.dot(&normal);
+        if closing <= T::zero() {
+            return None;
+        }
+
+        last = Some((time, separation));
+        time = time + separation.distance / closing;
+        if time > T::one() {
+            return None;
+        }
+    }
+
+    // Out of iterations this close to the contact, report where it got to
+    last.map(|(previous, separation)| SweepHit {
+        time: previous,
+        point: (separation.point_a + separation.point_b) / two,
+        normal: (separation.point_a - separation.point_b).normalized(),
+    })
+}
+
+impl<T> Sphere3<T> {
+    pub fn sweep_plane(&self, plane: &Plane<T>, displacement: &Vector3<T>) -> Option<SweepHit<3, T>>
+    where
+        T: Default + Real,
+    {
+        let start = plane.signed_distance(&self.center) - self.radius;
+        if start <= T::zero() {
+            return Some(SweepHit {
+                time: T::zero(),
+                point: plane.project(&self.center),
+                normal: plane.normal,
+            });
+        }
+
+        let speed = -plane.normal.dot(displacement);
+        if speed <= T::zero() || start > speed {
+            return None;
+        }
+
+        let time = start / speed;
+
+        Some(SweepHit {
+            time,
+            point: self.center + *displacement * time - plane.normal * self.radius,
+            normal: plane.normal,
+        })
+    }
+
+    pub fn sweep_sphere(
+        &self,
+        sphere: &Sphere3<T>,
+        displacement: &Vector3<T>,
+    ) -> Option<SweepHit<3, T>>
+    where
+        T: Default + Real,
+    {
+        let time = if self.intersects_sphere(sphere) {
+            T::zero()
+        } else {
+            let hit = Ray::new(self.center, *displacement)
+                .intersect_sphere(&sphere.center, self.radius + sphere.radius)?;
+
+            (hit.distance <= T::one()).then_some(hit.distance)?
+        };
+
+        let center = self.center + *displacement * time;
+
+        Some(contact(
+            time,
+            sphere.closest_point(&center),
+            center,
+            displacement,
+        ))
+    }
+
+    // Ray against the box grown by the radius, with capsules around its edges for the rounded
+    // parts (Ericson 5.5.7)
+    pub fn sweep_aabb(&self, aabb: &Aabb3<T>, displacement: &Vector3<T>) -> Option<SweepHit<3, T>>
+    where
+        T: Default + Real,
+    {
+        let time = if self.intersects_aabb(aabb) {
+            T::zero()
+        } else {
+            let ray = Ray::new(self.center, *displacement);
+            let hit = ray.intersect_aabb(&aabb.expand(self.radius))?;
+            let point = hit.point;
+
+            let outside: Vec<_> = (0..3)
+                .filter(|&i| point[i] < aabb.min[i] || point[i] > aabb.max[i])
+                .collect();
+            let corner = aabb.closest_point(&point);
+
+            let time = if outside.len() < 2 {
+                hit.distance
+            } else {
+                // Edges along the axes the point is within, or all three meeting at a corner
+                (0..3)
+                    .filter(|axis| outside.len() == 3 || !outside.contains(axis))
+                    .filter_map(|axis| {
+                        let mut a = corner;
+                        let mut b = corner;
+                        a[axis] = aabb.min[axis];
+                        b[axis] = aabb.max[axis];
+
+                        ray.intersect_capsule(&a, &b, self.radius)
+                    })
+                    .map(|hit| hit.distance)
+                    .fold(None, |best: Option<T>, t| {
+                        Some(best.map_or(t, |best| best.min(t)))
+                    })?
+            };
+
+            (time <= T::one()).then_some(time)?
+        };
+
+        let center = self.center + *displacement * time;
+
+        Some(contact(
+            time,
+            aabb.closest_point(&center),
+            center,
+            displacement,
+        ))
+    }
+
+    // Two sided, against the face and the capsules around the edges
+    pub fn sweep_triangle(
+        &self,
+        triangle: &Triangle3<T>,
+        displacement: &Vector3<T>,
+    ) -> Option<SweepHit<3, T>>
+    where
+        T: Default + Real,
+    {
+        let closest = triangle.closest_point(&self.center);
+
+        let time = if (closest - self.center).length_squared() <= self.radius * self.radius {
+            T::zero()
+        } else {
+            let ray = Ray::new(self.center, *displacement);
+
+            let mut normal = triangle.normal();
+            if normal.dot(&(self.center - triangle.a)) < T::zero() {
+                normal = -normal;
+            }
+
+            let speed = -normal.dot(displacement);
+            let face = (speed > T::zero())
+                .then(|| (normal.dot(&(self.center - triangle.a)) - self.radius) / speed)
+                .filter(|&t| t >= T::zero())
+                .filter(|&t| {
+                    let center = ray.at(t);
+
+                    triangle.contains_projection(&(center - normal * self.radius))
+                });
+
+            let edges = [
+                (triangle.a, triangle.b),
+                (triangle.b, triangle.c),
+                (triangle.c, triangle.a),
+            ]
+            .into_iter()
+            .filter_map(|(a, b)| ray.intersect_capsule(&a, &b, self.radius))
+            .map(|hit| hit.distance);
+
+            let time = face
+                .into_iter()
+                .chain(edges)
+                .fold(None, |best: Option<T>, t| {
+                    Some(best.map_or(t, |best| best.min(t)))
+                })?;
+
+            (time <= T::one()).then_some(time)?
+        };
+
+        let center = self.center + *displacement * time;
+
+        Some(contact(
+            time,
+            triangle.closest_point(&center),
+            center,
+            displacement,
+        ))
+    }
+}
+
+impl<T> Aabb3<T> {
+    pub fn sweep_plane(&self, plane: &Plane<T>, displacement: &Vector3<T>) -> Option<SweepHit<3, T>>
+    where
+        T: Default + Real,
+    {
+        let half_extents = self.half_extents();
+        let radius = half_extents.dot(&Vector3::new(plane.normal.0.map(|val| val.abs())));
+
+        let hit = Sphere3::new(self.center(), radius).sweep_plane(plane, displacement)?;
+
+        // Center of the touching face, edge or corner
+        let center = self.center() + *displacement * hit.time;
+        let offset = Vector3::new(std::array::from_fn(|i| {
+            if plane.normal[i].abs() <= T::epsilon() {
+                T::zero()
+            } else {
+                half_extents[i] * plane.normal[i].signum()
+            }
+        }));
+
+        Some(SweepHit {
+            point: if hit.time > T::zero() {
+                center - offset
+            } else {
+                hit.point
+            },
+            ..hit
+        })
+    }
+
+    pub fn sweep_aabb(&self, aabb: &Aabb3<T>, displacement: &Vector3<T>) -> Option<SweepHit<3, T>>
+    where
+        T: Default + Real,
+    {
+        let (time, normal) = if self.intersects(aabb) {
+            let penetration = epa_penetration(self, aabb)?;
+
+            (T::zero(), -penetration.normal)
+        } else {
+            let grown = Aabb::new(
+                aabb.min - self.half_extents(),
+                aabb.max + self.half_extents(),
+            );
+            let hit = Ray::new(self.center(), *displacement).intersect_aabb(&grown)?;
+
+            (hit.distance <= T::one()).then_some((hit.distance, hit.normal))?
+        };
+
+        // Center of the touching region
+        let offset = *displacement * time;
+        let moved = Aabb::new(self.min + offset, self.max + offset);
+        let overlap = Aabb::new(moved.min.max(&aabb.min), moved.max.min(&aabb.max));
+
+        Some(SweepHit {
+            time,
+            point: overlap.center(),
+            normal,
+        })
+    }
+
+    pub fn sweep_sphere(
+        &self,
+        sphere: &Sphere3<T>,
+        displacement: &Vector3<T>,
+    ) -> Option<SweepHit<3, T>>
+    where
+        T: Default + Real,
+    {
+        let hit = sphere.sweep_aabb(self, &-*displacement)?;
+
+        Some(SweepHit {
+            time: hit.time,
+            point: hit.point + *displacement * hit.time,
+            normal: -hit.normal,
+        })
+    }
+
+    pub fn sweep_triangle(
+        &self,
+        triangle: &Triangle3<T>,
+        displacement: &Vector3<T>,
+    ) -> Option<SweepHit<3, T>>
+    where
+        T: Default + Real,
+    {
+        time_of_impact(self, displacement, triangle)
+    }
+}
+
+impl<T> Capsule<T> {
+    pub fn sweep_plane(&self, plane: &Plane<T>, displacement: &Vector3<T>) -> Option<SweepHit<3, T>>
+    where
+        T: Default + Real,
+    {
+        let a = Sphere3::new(self.a, self.radius).sweep_plane(plane, displacement);
+        let b = Sphere3::new(self.b, self.radius).sweep_plane(plane, displacement);
+
+        match (a, b) {
+            // Lying flat, touching along the whole segment
+            (Some(a), Some(b)) if (a.time - b.time).abs() <= T::epsilon() => Some(SweepHit {
+                point: (a.point + b.point) / (T::one() + T::one()),
+                ..a
+            }),
+            (Some(a), Some(b)) => Some(if a.time < b.time { a } else { b }),
+            (a, b) => a.or(b),
+        }
+    }
+
+    pub fn sweep_sphere(
+        &self,
+        sphere: &Sphere3<T>,
+        displacement: &Vector3<T>,
+    ) -> Option<SweepHit<3, T>>
+    where
+        T: Default + Real,
+    {
+        let time = if sphere.intersects_capsule(self) {
+            T::zero()
+        } else {
+            let hit = Ray::new(sphere.center, -*displacement).intersect_capsule(
+                &self.a,
+                &self.b,
+                self.radius + sphere.radius,
+            )?;
+
+            (hit.distance <= T::one()).then_some(hit.distance)?
+        };
+
+        let offset = *displacement * time;
+        let closest =
+            closest_point_on_segment(&(self.a + offset), &(self.b + offset), &sphere.center);
+
+        Some(contact(
+            time,
+            sphere.closest_point(&closest),
+            closest,
+            displacement,
+        ))
+    }
+
+    pub fn sweep_aabb(&self, aabb: &Aabb3<T>, displacement: &Vector3<T>) -> Option<SweepHit<3, T>>
+    where
+        T: Default + Real,
+    {
+        time_of_impact(self, displacement, aabb)
+    }
+
+    pub fn sweep_triangle(
+        &self,
+        triangle: &Triangle3<T>,
+        displacement: &Vector3<T>,
+    ) -> Option<SweepHit<3, T>>
+    where
+        T: Default + Real,
+    {
+        time_of_impact(self, displacement, triangle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{shapes::Sphere, test_util::assert_close, vector};
+
+    fn assert_time(hit: &SweepHit<3, f64>, time: f64) {
+        assert!((hit.time - time).abs() < 1e-6, "{} != {}", hit.time, time);
+    }
+
+    #[test]
+    fn sphere() {
+        let sphere = Sphere::new(vector!(0.0, 5.0, 0.0), 1.0);
+        let down = vector!(0.0, -10.0, 0.0);
+
+        let ground = Plane::new(vector!(0.0, 1.0, 0.0), 0.0);
+        let hit = sphere.sweep_plane(&ground, &down).unwrap();
+        assert_time(&hit, 0.4);
+        assert_close(hit.point, vector!(0.0, 0.0, 0.0), 1e-6);
+        assert_close(hit.normal, vector!(0.0, 1.0, 0.0), 1e-6);
+        assert!(sphere
+            .sweep_plane(&ground, &vector!(0.0, -1.0, 0.0))
+            .is_none());
+        assert!(sphere
+            .sweep_plane(&ground, &vector!(0.0, 1.0, 0.0))
+            .is_none());
+
+        let other = Sphere::new(vector!(0.0, -3.0, 0.0), 2.0);
+        let hit = sphere.sweep_sphere(&other, &down).unwrap();
+        assert_time(&hit, 0.5);
+        assert_close(hit.point, vector!(0.0, -1.0, 0.0), 1e-6);
+        assert_close(hit.normal, vector!(0.0, 1.0, 0.0), 1e-6);
+        assert!(sphere
+            .sweep_sphere(&Sphere::new(vector!(3.5, -3.0, 0.0), 2.0), &down)
+            .is_none());
+
+        // Thin wall that a discrete test would step over
+        let wall = Aabb::new(vector!(-5.0, -0.05, -5.0), vector!(5.0, 0.05, 5.0));
+        let hit = sphere.sweep_aabb(&wall, &down).unwrap();
+        assert_time(&hit, 0.395);
+        assert_close(hit.point, vector!(0.0, 0.05, 0.0), 1e-6);
+        assert_close(hit.normal, vector!(0.0, 1.0, 0.0), 1e-6);
+
+        // Grazing past a box edge, only the rounded part is hit
+        let edge = Aabb::new(vector!(0.5, -2.0, -5.0), vector!(3.0, 0.0, 5.0));
+        let hit = Sphere::new(vector!(-0.3, 5.0, 0.0), 1.0)
+            .sweep_aabb(&edge, &down)
+            .unwrap();
+        assert_close(hit.point, vector!(0.5, 0.0, 0.0), 1e-6);
+        assert!((hit.normal - vector!(-0.8, 0.6, 0.0)).length() < 1e-6);
+        assert!(Sphere::new(vector!(-0.6, 5.0, 0.0), 1.0)
+            .sweep_aabb(
+                &Aabb::new(vector!(0.5, -2.0, 0.5), vector!(3.0, 0.0, 3.0)),
+                &down
+            )
+            .is_none());
+
+        let triangle = Triangle3::new(
+            vector!(-2.0, 0.0, -2.0),
+            vector!(2.0, 0.0, -2.0),
+            vector!(0.0, 0.0, 2.0),
+        );
+        let hit = sphere.sweep_triangle(&triangle, &down).unwrap();
+        assert_time(&hit, 0.4);
+        assert_close(hit.normal, vector!(0.0, 1.0, 0.0), 1e-6);
+
+        // Hitting the edge from the side
+        let hit = Sphere::new(vector!(0.0, 0.5, -5.0), 1.0)
+            .sweep_triangle(&triangle, &vector!(0.0, 0.0, 10.0))
+            .unwrap();
+        assert_close(hit.point, vector!(0.0, 0.0, -2.0), 1e-6);
+        assert!(hit.normal.z() < 0.0);
+
+        // Overlapping the plane off to the side and moving away
+        assert!(Sphere::new(vector!(5.0, 0.5, 0.0), 1.0)
+            .sweep_triangle(&triangle, &vector!(10.0, -1.0, 0.0))
+            .is_none());
+    }
+
+    #[test]
+    fn aabb() {
+        let aabb = Aabb::new(vector!(-1.0, 4.0, -1.0), vector!(1.0, 6.0, 1.0));
+        let down = vector!(0.0, -10.0, 0.0);
+
+        let hit = aabb
+            .sweep_plane(&Plane::new(vector!(0.0, 1.0, 0.0), 0.0), &down)
+            .unwrap();
+        assert_time(&hit, 0.4);
+        assert_close(hit.point, vector!(0.0, 0.0, 0.0), 1e-6);
+
+        let floor = Aabb::new(vector!(0.5, -1.0, -3.0), vector!(5.0, 0.0, 3.0));
+        let hit = aabb.sweep_aabb(&floor, &down).unwrap();
+        assert_time(&hit, 0.4);
+        assert_close(hit.normal, vector!(0.0, 1.0, 0.0), 1e-6);
+        assert_close(hit.point, vector!(0.75, 0.0, 0.0), 1e-6);
+
+        let hit = aabb
+            .sweep_sphere(&Sphere::new(vector!(0.0, -2.0, 0.0), 1.0), &down)
+            .unwrap();
+        assert_time(&hit, 0.5);
+        assert_close(hit.point, vector!(0.0, -1.0, 0.0), 1e-6);
+        assert_close(hit.normal, vector!(0.0, 1.0, 0.0), 1e-6);
+
+        let triangle = Triangle3::new(
+            vector!(-3.0, 0.0, -3.0),
+            vector!(3.0, 0.0, -3.0),
+            vector!(0.0, 2.0, 3.0),
+        );
+        let hit = aabb.sweep_triangle(&triangle, &down).unwrap();
+        let moved = Aabb::new(aabb.min + down * hit.time, aabb.max + down * hit.time);
+        assert!(moved.expand(1e-6).contains_point(&hit.point));
+        assert!((triangle.closest_point(&hit.point) - hit.point).length() < 1e-6);
+        assert!(hit.normal.y() > 0.0);
+
+        assert!(aabb.sweep_aabb(&floor, &vector!(0.0, 1.0, 0.0)).is_none());
+    }
+
+    #[test]
+    fn capsule() {
+        let capsule = Capsule::new(vector!(-2.0, 5.0, 0.0), vector!(2.0, 5.0, 0.0), 1.0);
+        let down = vector!(0.0, -10.0, 0.0);
+
+        let hit = capsule
+            .sweep_plane(&Plane::new(vector!(0.0, 1.0, 0.0), 0.0), &down)
+            .unwrap();
+        assert_time(&hit, 0.4);
+        assert_close(hit.point, vector!(0.0, 0.0, 0.0), 1e-6);
+
+        let hit = capsule
+            .sweep_sphere(&Sphere::new(vector!(1.0, -1.0, 0.0), 1.0), &down)
+            .unwrap();
+        assert_time(&hit, 0.4);
+        assert_close(hit.point, vector!(1.0, 0.0, 0.0), 1e-6);
+        assert_close(hit.normal, vector!(0.0, 1.0, 0.0), 1e-6);
+
+        let floor = Aabb::new(vector!(-5.0, -1.0, -5.0), vector!(5.0, 0.0, 5.0));
+        let hit = capsule.sweep_aabb(&floor, &down).unwrap();
+        assert_time(&hit, 0.4);
+        assert_close(hit.normal, vector!(0.0, 1.0, 0.0), 1e-6);
+
+        let triangle = Triangle3::new(
+            vector!(-3.0, 0.0, -3.0),
+            vector!(3.0, 0.0, -3.0),
+            vector!(0.0, 0.0, 3.0),
+        );
+        let hit = capsule.sweep_triangle(&triangle, &down).unwrap();
+        assert_time(&hit, 0.4);
+        assert!(capsule.sweep_triangle(&triangle, &-down).is_none());
+    }
+
+    #[test]
+    fn conservative_advancement() {
+        let tetrahedron = [
+            vector!(0.0, 0.0, 0.0),
+            vector!(1.0, 0.0, 0.0),
+            vector!(0.0, 1.0, 0.0),
+            vector!(0.0, 0.0, 1.0),
+        ];
+        let target = Sphere::new(vector!(5.0, 0.0, 0.0), 1.0);
+
+        let hit = time_of_impact(&tetrahedron[..], &vector!(10.0, 0.0, 0.0), &target).unwrap();
+        assert_time(&hit, 0.3);
+        assert_close(hit.point, vector!(4.0, 0.0, 0.0), 1e-6);
+        assert_close(hit.normal, vector!(-1.0, 0.0, 0.0), 1e-6);
+
+        assert!(time_of_impact(&tetrahedron[..], &vector!(2.0, 0.0, 0.0), &target).is_none());
+        assert!(time_of_impact(&tetrahedron[..], &vector!(0.0, 10.0, 0.0), &target).is_none());
+
+        // Already overlapping
+        let hit = time_of_impact(
+            &tetrahedron[..],
+            &vector!(1.0, 0.0, 0.0),
+            &vector!(0.1, 0.1, 0.1),
+        )
+        .unwrap();
+        assert_eq!(hit.time, 0.0);
+    }
+}
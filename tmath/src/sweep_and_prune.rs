@@ -0,0 +1,415 @@
+use std::{cmp::Ordering, collections::HashSet};
+
+use num_traits::real::Real;
+
+use crate::aabb::Aabb;
+
+pub type SweepAndPrune2<T> = SweepAndPrune<2, T>;
+pub type SweepAndPrune3<T> = SweepAndPrune<3, T>;
+
+macro_rules! sweep_and_prune_types {
+    ($($n:literal),*) => {
+        paste::paste! {
+            $(
+                pub type [< SweepAndPrune $n F >] = [< SweepAndPrune $n >]<f32>;
+                pub type [< SweepAndPrune $n D >] = [< SweepAndPrune $n >]<f64>;
+            )*
+        }
+    };
+}
+
+sweep_and_prune_types!(2, 3);
+
+#[derive(Debug, Copy, Clone)]
+struct Endpoint<T> {
+    value: T,
+    id: usize,
+    max: bool,
+}
+
+impl<T: PartialOrd> Endpoint<T> {
+    // Mins go before maxes at the same value, so touching boxes overlap like in Aabb::intersects
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value
+            .partial_cmp(&other.value)
+            .unwrap_or(Ordering::Equal)
+            .then(self.max.cmp(&other.max))
+    }
+}
+
+// Pairs are ordered with the smaller id first
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PairEvents {
+    pub added: Vec<(usize, usize)>,
+    pub removed: Vec<(usize, usize)>,
+}
+
+fn pair(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+// Keeps the box endpoints sorted along every axis. Frame to frame the boxes barely move, so an
+// insertion sort only does a few swaps, and a swap is exactly where two boxes start or stop
+// overlapping along that axis
+#[derive(Debug, Clone)]
+pub struct SweepAndPrune<const N: usize, T> {
+    boxes: Vec<Option<Aabb<N, T>>>,
+    free: Vec<usize>,
+    axes: [Vec<Endpoint<T>>; N],
+    // Endpoints at the back of the axes added since the last update
+    pending: usize,
+    pairs: HashSet<(usize, usize)>,
+    removed: Vec<(usize, usize)>,
+}
+
+impl<const N: usize, T> Default for SweepAndPrune<N, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize, T> SweepAndPrune<N, T> {
+    pub fn new() -> Self {
+        Self {
+            boxes: Vec::new(),
+            free: Vec::new(),
+            axes: std::array::from_fn(|_| Vec::new()),
+            pending: 0,
+            pairs: HashSet::new(),
+            removed: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.boxes.len() - self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, id: usize) -> Option<&Aabb<N, T>> {
+        self.boxes.get(id)?.as_ref()
+    }
+
+    // Overlaps as of the last update
+    pub fn pairs(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.pairs.iter().copied()
+    }
+
+    pub fn overlapping(&self, a: usize, b: usize) -> bool {
+        self.pairs.contains(&pair(a, b))
+    }
+
+    // Its overlaps show up on the next update
+    pub fn insert(&mut self, aabb: Aabb<N, T>) -> usize
+    where
+        T: Copy,
+    {
+        let id = match self.free.pop() {
+            Some(id) => {
+                self.boxes[id] = Some(aabb);
+                id
+            }
+            None => {
+                self.boxes.push(Some(aabb));
+                self.boxes.len() - 1
+            }
+        };
+
+        for (axis, endpoints) in self.axes.iter_mut().enumerate() {
+            endpoints.push(Endpoint {
+                value: aabb.min[axis],
+                id,
+                max: false,
+            });
+            endpoints.push(Endpoint {
+                value: aabb.max[axis],
+                id,
+                max: true,
+            });
+        }
+        self.pending += 2;
+
+        id
+    }
+
+    // The new bounds take effect on the next update, false if there's no such box
+    pub fn set(&mut self, id: usize, aabb: Aabb<N, T>) -> bool {
+        match self.boxes.get_mut(id) {
+            Some(Some(current)) => {
+                *current = aabb;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // Its pairs are reported removed on the next update
+    pub fn remove(&mut self, id: usize) -> Option<Aabb<N, T>> {
+        let aabb = self.boxes.get_mut(id)?.take()?;
+
+        // Still waiting at the back of the axes if added since the last update
+        if let Some(endpoints) = self.axes.first() {
+            let start = endpoints.len() - self.pending;
+            if endpoints[start..].iter().any(|e| e.id == id) {
+                self.pending -= 2;
+            }
+        }
+        for endpoints in &mut self.axes {
+            endpoints.retain(|e| e.id != id);
+        }
+
+        let pairs: Vec<_> = self
+            .pairs
+            .iter()
+            .copied()
+            .filter(|&(a, b)| a == id || b == id)
+            .collect();
+        for pair in pairs {
+            self.pairs.remove(&pair);
+            self.removed.push(pair);
+        }
+
+        self.free.push(id);
+
+        Some(aabb)
+    }
+
+    fn overlap(&self, a: usize, b: usize) -> bool
+    where
+        T: PartialOrd,
+    {
+        match (&self.boxes[a], &self.boxes[b]) {
+            (Some(a), Some(b)) => a.intersects(b),
+            _ => false,
+        }
+    }
+
+    // Brings the endpoints up to date with the boxes and reports how the overlapping pairs changed,
+    // removals first
+    pub fn update(&mut self) -> PairEvents
+    where
+        T: Real,
+    {
+        let mut events = PairEvents {
+            added: Vec::new(),
+            removed: std::mem::take(&mut self.removed),
+        };
+
+        for (axis, endpoints) in self.axes.iter_mut().enumerate() {
+            for endpoint in endpoints.iter_mut() {
+                if let Some(aabb) = &self.boxes[endpoint.id] {
+                    endpoint.value = if endpoint.max {
+                        aabb.max[axis]
+                    } else {
+                        aabb.min[axis]
+                    };
+                }
+            }
+        }
+
+        // Sliding lots of new boxes in from the end one by one is quadratic, sorting from scratch
+        // and sweeping once is cheaper then
+        let total = self.axes.first().map_or(0, |endpoints| endpoints.len());
+        if self.pending * 4 > total {
+            self.rebuild(&mut events);
+        } else {
+            for axis in 0..N {
+                self.sort_axis(axis, &mut events);
+            }
+        }
+        self.pending = 0;
+
+        events
+    }
+
+    fn sort_axis(&mut self, axis: usize, events: &mut PairEvents)
+    where
+        T: Real,
+    {
+        let mut endpoints = std::mem::take(&mut self.axes[axis]);
+
+        for i in 1..endpoints.len() {
+            let mut j = i;
+
+            // Each swap moves the endpoint at j left past the one before it
+            while j > 0 && endpoints[j].cmp(&endpoints[j - 1]) == Ordering::Less {
+                let (moving, passed) = (endpoints[j], endpoints[j - 1]);
+
+                if moving.id != passed.id {
+                    let pair = pair(moving.id, passed.id);
+
+                    if !moving.max && passed.max {
+                        // Started overlapping along this axis, the other axes have the final say
+                        if self.overlap(moving.id, passed.id) && self.pairs.insert(pair) {
+                            events.added.push(pair);
+                        }
+                    } else if moving.max && !passed.max && self.pairs.remove(&pair) {
+                        // Stopped overlapping along this axis
+                        events.removed.push(pair);
+                    }
+                }
+
+                endpoints.swap(j, j - 1);
+                j -= 1;
+            }
+        }
+
+        self.axes[axis] = endpoints;
+    }
+
+    // Sorts every axis and finds all overlaps with one sweep along the first
+    fn rebuild(&mut self, events: &mut PairEvents)
+    where
+        T: Real,
+    {
+        for endpoints in &mut self.axes {
+            endpoints.sort_by(|a, b| a.cmp(b));
+        }
+
+        let mut pairs = HashSet::new();
+        let mut active: Vec<usize> = Vec::new();
+
+        if let Some(endpoints) = self.axes.first() {
+            for endpoint in endpoints {
+                if endpoint.max {
+                    active.retain(|&id| id != endpoint.id);
+                    continue;
+                }
+
+                for &other in &active {
+                    if self.overlap(endpoint.id, other) {
+                        pairs.insert(pair(endpoint.id, other));
+                    }
+                }
+                active.push(endpoint.id);
+            }
+        }
+
+        events
+            .removed
+            .extend(self.pairs.difference(&pairs).copied());
+        events.added.extend(pairs.difference(&self.pairs).copied());
+        self.pairs = pairs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::{vector, vector::Vector};
+
+    fn random_box<const N: usize>(rng: &mut StdRng) -> Aabb<N, f64> {
+        let center = Vector::new(std::array::from_fn(|_| rng.gen_range(-20.0..20.0)));
+        let half = Vector::new(std::array::from_fn(|_| rng.gen_range(0.1..2.0)));
+
+        Aabb::from_center_half_extents(center, half)
+    }
+
+    fn brute_force<const N: usize>(sap: &SweepAndPrune<N, f64>) -> HashSet<(usize, usize)> {
+        let mut pairs = HashSet::new();
+        for a in 0..sap.boxes.len() {
+            for b in a + 1..sap.boxes.len() {
+                if sap.overlap(a, b) {
+                    pairs.insert((a, b));
+                }
+            }
+        }
+
+        pairs
+    }
+
+    fn check<const N: usize>(seed: u64) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut sap = SweepAndPrune::<N, f64>::new();
+        let mut current = HashSet::new();
+
+        for _ in 0..300 {
+            sap.insert(random_box(&mut rng));
+        }
+
+        for frame in 0..40 {
+            for id in 0..sap.boxes.len() {
+                let Some(aabb) = sap.get(id).copied() else {
+                    continue;
+                };
+                let offset = Vector::new(std::array::from_fn(|_| rng.gen_range(-0.5..0.5)));
+                assert!(sap.set(id, Aabb::new(aabb.min + offset, aabb.max + offset)));
+            }
+
+            // Bodies coming and going now and then
+            if frame % 5 == 0 {
+                for _ in 0..10 {
+                    sap.remove(rng.gen_range(0..sap.boxes.len()));
+                    sap.insert(random_box(&mut rng));
+                }
+            }
+
+            let events = sap.update();
+            for pair in &events.removed {
+                assert!(current.remove(pair), "{pair:?} wasn't overlapping");
+            }
+            for pair in &events.added {
+                assert!(pair.0 < pair.1);
+                assert!(current.insert(*pair), "{pair:?} was already overlapping");
+            }
+
+            let expected = brute_force(&sap);
+            assert_eq!(current, expected);
+            assert_eq!(sap.pairs().collect::<HashSet<_>>(), expected);
+            assert!(sap.axes.iter().all(|endpoints| endpoints
+                .windows(2)
+                .all(|w| w[0].cmp(&w[1]) != Ordering::Greater)));
+        }
+    }
+
+    #[test]
+    fn random() {
+        check::<2>(1);
+        check::<3>(2);
+    }
+
+    #[test]
+    fn events() {
+        let mut sap = SweepAndPrune2F::new();
+        let a = sap.insert(Aabb::new(vector!(0.0, 0.0), vector!(1.0, 1.0)));
+        let b = sap.insert(Aabb::new(vector!(3.0, 0.0), vector!(4.0, 1.0)));
+        assert_eq!(sap.update(), PairEvents::default());
+
+        // Touching counts as overlapping
+        sap.set(b, Aabb::new(vector!(1.0, 0.5), vector!(2.0, 1.5)));
+        assert_eq!(
+            sap.update(),
+            PairEvents {
+                added: vec![(a, b)],
+                removed: Vec::new(),
+            }
+        );
+        assert!(sap.overlapping(b, a));
+
+        // Overlapping along x only
+        sap.set(b, Aabb::new(vector!(0.5, 2.0), vector!(1.5, 3.0)));
+        assert_eq!(sap.update().removed, [(a, b)]);
+
+        let c = sap.insert(Aabb::new(vector!(-1.0, -1.0), vector!(2.0, 5.0)));
+        let mut added = sap.update().added;
+        added.sort_unstable();
+        assert_eq!(added, [(a, c), (b, c)]);
+
+        assert_eq!(
+            sap.remove(c),
+            Some(Aabb::new(vector!(-1.0, -1.0), vector!(2.0, 5.0)))
+        );
+        assert_eq!(sap.remove(c), None);
+        assert!(!sap.set(c, Aabb::new(vector!(0.0, 0.0), vector!(1.0, 1.0))));
+        let mut removed = sap.update().removed;
+        removed.sort_unstable();
+        assert_eq!(removed, [(a, c), (b, c)]);
+        assert_eq!(sap.len(), 2);
+        assert_eq!(sap.pairs().count(), 0);
+    }
+}
@@ -0,0 +1,5 @@
+use crate::vector::Vector;
+
+pub(crate) fn assert_close<const N: usize>(a: Vector<N, f64>, b: Vector<N, f64>, tolerance: f64) {
+    assert!((a - b).length() < tolerance, "{a:?} != {b:?}");
+}
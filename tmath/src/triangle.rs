@@ -0,0 +1,350 @@
+use num_traits::real::Real;
+
+use crate::{
+    shapes::Plane,
+    vector::{Vector, Vector3},
+};
+
+pub type Triangle2<T> = Triangle<2, T>;
+pub type Triangle3<T> = Triangle<3, T>;
+
+macro_rules! triangle_types {
+    ($($n:literal),*) => {
+        paste::paste! {
+            $(
+                pub type [< Triangle $n F >] = [< Triangle $n >]<f32>;
+                pub type [< Triangle $n D >] = [< Triangle $n >]<f64>;
+            )*
+        }
+    };
+}
+
+triangle_types!(2, 3);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Triangle<const N: usize, T> {
+    pub a: Vector<N, T>,
+    pub b: Vector<N, T>,
+    pub c: Vector<N, T>,
+}
+
+impl<const N: usize, T> Triangle<N, T> {
+    pub const fn new(a: Vector<N, T>, b: Vector<N, T>, c: Vector<N, T>) -> Self {
+        Self { a, b, c }
+    }
+
+    pub fn vertices(&self) -> [Vector<N, T>; 3]
+    where
+        T: Copy,
+    {
+        [self.a, self.b, self.c]
+    }
+
+    // Lagrange's identity, so it works in any dimension
+    pub fn area(&self) -> T
+    where
+        T: Default + Real,
+    {
+        let ab = self.b - self.a;
+        let ac = self.c - self.a;
+        let dot = ab.dot(&ac);
+        let squared = ab.length_squared() * ac.length_squared() - dot * dot;
+
+        squared.max(T::zero()).sqrt() / (T::one() + T::one())
+    }
+
+    pub fn perimeter(&self) -> T
+    where
+        T: Default + Real,
+    {
+        (self.b - self.a).length() + (self.c - self.b).length() + (self.a - self.c).length()
+    }
+
+    pub fn centroid(&self) -> Vector<N, T>
+    where
+        T: Real,
+    {
+        (self.a + self.b + self.c) / (T::one() + T::one() + T::one())
+    }
+
+    // Weights of a, b and c for the point, projected onto the triangle's plane first. None for
+    // degenerate triangles
+    pub fn barycentric(&self, point: &Vector<N, T>) -> Option<Vector3<T>>
+    where
+        T: Default + Real,
+    {
+        let v0 = self.b - self.a;
+        let v1 = self.c - self.a;
+        let v2 = *point - self.a;
+        let d00 = v0.dot(&v0);
+        let d01 = v0.dot(&v1);
+        let d11 = v1.dot(&v1);
+        let d20 = v2.dot(&v0);
+        let d21 = v2.dot(&v1);
+        let denom = d00 * d11 - d01 * d01;
+
+        if denom <= T::epsilon() * d00 * d11 {
+            return None;
+        }
+
+        let v = (d11 * d20 - d01 * d21) / denom;
+        let w = (d00 * d21 - d01 * d20) / denom;
+
+        Some(Vector3::new([T::one() - v - w, v, w]))
+    }
+
+    pub fn from_barycentric(&self, weights: &Vector3<T>) -> Vector<N, T>
+    where
+        T: Real,
+    {
+        self.a * weights[0] + self.b * weights[1] + self.c * weights[2]
+    }
+
+    // Inside or on the edges, and on the triangle's plane up to rounding relative to its size
+    pub fn contains_point(&self, point: &Vector<N, T>) -> bool
+    where
+        T: Default + Real,
+    {
+        self.barycentric(point).is_some_and(|weights| {
+            let offset = (*point - self.from_barycentric(&weights)).length();
+
+            weights.0.iter().all(|&weight| weight >= T::zero())
+                && offset <= T::epsilon().sqrt() * self.perimeter()
+        })
+    }
+
+    // Whether the point's projection onto the triangle's plane is inside or on the edges, however
+    // far off the plane the point is
+    pub fn contains_projection(&self, point: &Vector<N, T>) -> bool
+    where
+        T: Default + Real,
+    {
+        self.barycentric(point)
+            .is_some_and(|weights| weights.0.iter().all(|&weight| weight >= T::zero()))
+    }
+
+    // Ericson's Voronoi region method
+    pub fn closest_point(&self, point: &Vector<N, T>) -> Vector<N, T>
+    where
+        T: Default + Real,
+    {
+        let (a, b, c) = (self.a, self.b, self.c);
+        let ab = b - a;
+        let ac = c - a;
+
+        let ap = *point - a;
+        let d1 = ab.dot(&ap);
+        let d2 = ac.dot(&ap);
+        if d1 <= T::zero() && d2 <= T::zero() {
+            return a;
+        }
+
+        let bp = *point - b;
+        let d3 = ab.dot(&bp);
+        let d4 = ac.dot(&bp);
+        if d3 >= T::zero() && d4 <= d3 {
+            return b;
+        }
+
+        let vc = d1 * d4 - d3 * d2;
+        if vc <= T::zero() && d1 >= T::zero() && d3 <= T::zero() {
+            return a + ab * (d1 / (d1 - d3));
+        }
+
+        let cp = *point - c;
+        let d5 = ab.dot(&cp);
+        let d6 = ac.dot(&cp);
+        if d6 >= T::zero() && d5 <= d6 {
+            return c;
+        }
+
+        let vb = d5 * d2 - d1 * d6;
+        if vb <= T::zero() && d2 >= T::zero() && d6 <= T::zero() {
+            return a + ac * (d2 / (d2 - d6));
+        }
+
+        let va = d3 * d6 - d5 * d4;
+        if va <= T::zero() && d4 - d3 >= T::zero() && d5 - d6 >= T::zero() {
+            return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+        }
+
+        let denom = T::one() / (va + vb + vc);
+
+        a + ab * (vb * denom) + ac * (vc * denom)
+    }
+
+    // Center of the circumscribed circle, None for degenerate triangles
+    pub fn circumcenter(&self) -> Option<Vector<N, T>>
+    where
+        T: Default + Real,
+    {
+        let u = self.b - self.a;
+        let v = self.c - self.a;
+        let uu = u.dot(&u);
+        let uv = u.dot(&v);
+        let vv = v.dot(&v);
+        let denom = (uu * vv - uv * uv) * (T::one() + T::one());
+
+        if denom <= T::epsilon() * uu * vv {
+            return None;
+        }
+
+        let s = vv * (uu - uv) / denom;
+        let t = uu * (vv - uv) / denom;
+
+        Some(self.a + u * s + v * t)
+    }
+
+    // Center of the inscribed circle
+    pub fn incenter(&self) -> Vector<N, T>
+    where
+        T: Default + Real,
+    {
+        let la = (self.c - self.b).length();
+        let lb = (self.a - self.c).length();
+        let lc = (self.b - self.a).length();
+        let perimeter = la + lb + lc;
+
+        if perimeter <= T::zero() {
+            return self.a;
+        }
+
+        (self.a * la + self.b * lb + self.c * lc) / perimeter
+    }
+}
+
+impl<T> Triangle2<T> {
+    // Positive for counter-clockwise winding
+    pub fn signed_area(&self) -> T
+    where
+        T: Real,
+    {
+        let ab = self.b - self.a;
+        let ac = self.c - self.a;
+
+        (ab.x() * ac.y() - ab.y() * ac.x()) / (T::one() + T::one())
+    }
+}
+
+impl<T> Triangle3<T> {
+    // Follows the a -> b -> c winding, zero for degenerate triangles
+    pub fn normal(&self) -> Vector3<T>
+    where
+        T: Default + Real,
+    {
+        (self.b - self.a).cross(&(self.c - self.a)).normalized()
+    }
+
+    pub fn plane(&self) -> Option<Plane<T>>
+    where
+        T: Default + Real,
+    {
+        Plane::from_points(&self.a, &self.b, &self.c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{test_util::assert_close, vector};
+
+    #[test]
+    fn measures() {
+        let tri = Triangle2D::new(vector!(0.0, 0.0), vector!(4.0, 0.0), vector!(0.0, 3.0));
+
+        assert_eq!(tri.area(), 6.0);
+        assert_eq!(tri.signed_area(), 6.0);
+        assert_eq!(Triangle::new(tri.a, tri.c, tri.b).signed_area(), -6.0);
+        assert_eq!(tri.perimeter(), 12.0);
+        assert_close(tri.centroid(), vector!(4.0 / 3.0, 1.0), 1e-9);
+
+        let tri = Triangle3D::new(
+            vector!(0.0, 0.0, 1.0),
+            vector!(0.0, 4.0, 1.0),
+            vector!(3.0, 0.0, 1.0),
+        );
+        assert!((tri.area() - 6.0).abs() < 1e-9);
+        assert_eq!(tri.normal(), vector!(0.0, 0.0, -1.0));
+        assert_eq!(tri.plane().unwrap().distance, -1.0);
+    }
+
+    #[test]
+    fn barycentric() {
+        let tri = Triangle3D::new(
+            vector!(1.0, 0.0, 0.0),
+            vector!(0.0, 2.0, 0.0),
+            vector!(0.0, 0.0, 3.0),
+        );
+        let weights = vector!(0.2, 0.3, 0.5);
+        let point = tri.from_barycentric(&weights);
+
+        assert_close(tri.barycentric(&point).unwrap(), weights, 1e-9);
+        assert!(tri.contains_point(&point));
+        assert!(tri.contains_point(&tri.b));
+        assert!(!tri.contains_point(&tri.from_barycentric(&vector!(-0.1, 0.6, 0.5))));
+
+        // Off the plane only the projection is inside
+        let above = point + tri.normal() * 100.0;
+        assert!(!tri.contains_point(&above));
+        assert!(tri.contains_projection(&above));
+        assert!(!tri.contains_projection(&tri.from_barycentric(&vector!(-0.1, 0.6, 0.5))));
+
+        let flat = Triangle2D::new(vector!(0.0, 0.0), vector!(1.0, 1.0), vector!(2.0, 2.0));
+        assert!(flat.barycentric(&vector!(1.0, 1.0)).is_none());
+        assert!(!flat.contains_point(&vector!(1.0, 1.0)));
+    }
+
+    #[test]
+    fn closest_point() {
+        let tri = Triangle3D::new(
+            vector!(0.0, 0.0, 0.0),
+            vector!(4.0, 0.0, 0.0),
+            vector!(0.0, 4.0, 0.0),
+        );
+
+        // Face, vertices and edges
+        assert_eq!(
+            tri.closest_point(&vector!(1.0, 1.0, 5.0)),
+            vector!(1.0, 1.0, 0.0)
+        );
+        assert_eq!(tri.closest_point(&vector!(-1.0, -1.0, 1.0)), tri.a);
+        assert_eq!(tri.closest_point(&vector!(6.0, -1.0, 0.0)), tri.b);
+        assert_eq!(tri.closest_point(&vector!(-1.0, 7.0, 0.0)), tri.c);
+        assert_eq!(
+            tri.closest_point(&vector!(2.0, -3.0, 0.0)),
+            vector!(2.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            tri.closest_point(&vector!(-3.0, 2.0, 2.0)),
+            vector!(0.0, 2.0, 0.0)
+        );
+        assert_close(
+            tri.closest_point(&vector!(3.0, 3.0, 0.0)),
+            vector!(2.0, 2.0, 0.0),
+            1e-9,
+        );
+    }
+
+    #[test]
+    fn centers() {
+        let tri = Triangle2D::new(vector!(0.0, 0.0), vector!(4.0, 0.0), vector!(0.0, 3.0));
+
+        // Right triangle, the circumcenter is the middle of the hypotenuse
+        assert_close(tri.circumcenter().unwrap(), vector!(2.0, 1.5), 1e-9);
+        // Inradius of a 3-4-5 triangle is 1
+        assert_close(tri.incenter(), vector!(1.0, 1.0), 1e-9);
+
+        let tri3 = Triangle3D::new(
+            vector!(1.0, 0.0, 0.0),
+            vector!(0.0, 1.0, 0.0),
+            vector!(0.0, 0.0, 1.0),
+        );
+        let center = tri3.circumcenter().unwrap();
+        assert_close(center, vector!(1.0, 1.0, 1.0) / 3.0, 1e-9);
+        assert_close(tri3.incenter(), center, 1e-9);
+
+        let flat = Triangle2D::new(vector!(0.0, 0.0), vector!(1.0, 0.0), vector!(2.0, 0.0));
+        assert!(flat.circumcenter().is_none());
+    }
+}
@@ -2,6 +2,8 @@ use std::ops::{Add, DivAssign, Mul, Sub};
 
 use num_traits::{real::Real, Zero};
 
+use crate::angle::Rad;
+
 #[macro_export]
 macro_rules! vector {
     ($($member:expr),*) => {
@@ -48,6 +50,8 @@ pub use equality::*;
 mod equality {
     use super::*;
 
+    use std::hash::{Hash, Hasher};
+
     impl<const N: usize, T> PartialEq for Vector<N, T>
     where
         T: PartialEq,
@@ -58,6 +62,15 @@ mod equality {
     }
 
     impl<const N: usize, T> Eq for Vector<N, T> where T: Eq {}
+
+    impl<const N: usize, T> Hash for Vector<N, T>
+    where
+        T: Hash,
+    {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.0.hash(state);
+        }
+    }
 }
 
 impl<const N: usize, T> Vector<N, T> {
@@ -127,6 +140,107 @@ impl<const N: usize, T> Vector<N, T> {
             *self
         }
     }
+
+    pub fn min(&self, other: &Self) -> Self
+    where
+        T: Copy + PartialOrd,
+    {
+        let mut res = *self;
+        res.0
+            .iter_mut()
+            .zip(other.0)
+            .filter(|(val, other)| other < *val)
+            .for_each(|(val, other)| *val = other);
+
+        res
+    }
+
+    pub fn max(&self, other: &Self) -> Self
+    where
+        T: Copy + PartialOrd,
+    {
+        let mut res = *self;
+        res.0
+            .iter_mut()
+            .zip(other.0)
+            .filter(|(val, other)| other > *val)
+            .for_each(|(val, other)| *val = other);
+
+        res
+    }
+
+    pub fn min_element(&self) -> T
+    where
+        T: Copy + PartialOrd,
+    {
+        self.0
+            .iter()
+            .fold(self.0[0], |min, &val| if val < min { val } else { min })
+    }
+
+    pub fn max_element(&self) -> T
+    where
+        T: Copy + PartialOrd,
+    {
+        self.0
+            .iter()
+            .fold(self.0[0], |max, &val| if val > max { val } else { max })
+    }
+
+    pub fn angle_between(&self, other: &Self) -> Rad<T>
+    where
+        T: Default + Real,
+    {
+        let len = self.length() * other.length();
+
+        if len > T::zero() {
+            Rad::acos((self.dot(other) / len).max(-T::one()).min(T::one()))
+        } else {
+            Rad::zero()
+        }
+    }
+}
+
+impl<T> Vector2<T> {
+    pub fn from_angle<A>(angle: A) -> Self
+    where
+        A: Into<Rad<T>>,
+        T: Real,
+    {
+        let (sin, cos) = angle.into().sin_cos();
+
+        Self([cos, sin])
+    }
+
+    pub fn angle(&self) -> Rad<T>
+    where
+        T: Real,
+    {
+        Rad::atan2(self.y(), self.x())
+    }
+
+    pub fn signed_angle_to(&self, other: &Self) -> Rad<T>
+    where
+        T: Real,
+    {
+        let cross = self.x() * other.y() - self.y() * other.x();
+        let dot = self.x() * other.x() + self.y() * other.y();
+
+        Rad::atan2(cross, dot)
+    }
+
+    pub fn rotated<A>(&self, angle: A) -> Self
+    where
+        A: Into<Rad<T>>,
+        T: Real,
+    {
+        let (sin, cos) = angle.into().sin_cos();
+
+        Self([
+            self.x() * cos - self.y() * sin,
+            self.x() * sin + self.y() * cos,
+        ])
+    }
 }
 
 impl<T> Vector3<T> {
@@ -140,6 +254,18 @@ impl<T> Vector3<T> {
             self.x() * other.y() - self.y() * other.x(),
         ])
     }
+
+    // Rodrigues' rotation formula, the axis doesn't have to be normalized
+    pub fn rotated_around<A>(&self, axis: &Self, angle: A) -> Self
+    where
+        A: Into<Rad<T>>,
+        T: Default + Real,
+    {
+        let axis = axis.normalized();
+        let (sin, cos) = angle.into().sin_cos();
+
+        *self * cos + axis.cross(self) * sin + axis * (axis.dot(self) * (T::one() - cos))
+    }
 }
 
 pub use deref::*;
@@ -949,6 +1075,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn hash() {
+        use std::collections::HashSet;
+
+        let cells: HashSet<Vector3I> = [vector!(1, 2, 3), vector!(-1, 0, 4), vector!(1, 2, 3)]
+            .into_iter()
+            .collect();
+
+        assert_eq!(cells.len(), 2);
+        assert!(cells.contains(&vector!(-1, 0, 4)));
+    }
+
     #[test]
     fn length() {
         let v = vector!(2.0, 3.0, 1.0);
@@ -980,6 +1118,37 @@ mod tests {
         assert_eq!(v1, target);
     }
 
+    #[test]
+    fn min_max() {
+        let v1: Vector3I = vector!(2, -3, 1);
+        let v2: Vector3I = vector!(1, 2, 1);
+
+        assert_eq!(v1.min(&v2), vector!(1, -3, 1));
+        assert_eq!(v1.max(&v2), vector!(2, 2, 1));
+        assert_eq!(v1.min_element(), -3);
+        assert_eq!(v1.max_element(), 2);
+    }
+
+    #[test]
+    fn angles() {
+        use crate::angle::Deg;
+
+        let x: Vector2D = vector!(1.0, 0.0);
+        let y = Vector2D::from_angle(Deg(90.0));
+
+        assert!((y - vector!(0.0, 1.0)).length() < 1e-9);
+        assert!((x.angle_between(&y).0 - std::f64::consts::FRAC_PI_2).abs() < 1e-9);
+        assert!((x.signed_angle_to(&y).0 - std::f64::consts::FRAC_PI_2).abs() < 1e-9);
+        assert!((y.signed_angle_to(&x).0 + std::f64::consts::FRAC_PI_2).abs() < 1e-9);
+        assert!((x.rotated(Deg(-45.0)).angle().to_degrees().0 + 45.0).abs() < 1e-9);
+
+        let v: Vector3D = vector!(1.0, 0.0, 0.0);
+        let rotated = v.rotated_around(&vector!(0.0, 0.0, 2.0), Rad(std::f64::consts::FRAC_PI_2));
+
+        assert!((rotated - vector!(0.0, 1.0, 0.0)).length() < 1e-9);
+        assert_eq!(Vector3D::default().angle_between(&v), Rad(0.0));
+    }
+
     mod indexing {
         use super::*;
 
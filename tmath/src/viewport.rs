@@ -0,0 +1,187 @@
+use num_traits::real::Real;
+
+use crate::{
+    matrix::Matrix4,
+    ray::{Ray, Ray3},
+    vector::{Vector2, Vector3, Vector4},
+};
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum YAxis {
+    #[default]
+    Down,
+    Up,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Viewport<T> {
+    pub origin: Vector2<T>,
+    pub size: Vector2<T>,
+    pub y_axis: YAxis,
+}
+
+impl<T> Viewport<T> {
+    pub const fn new(origin: Vector2<T>, size: Vector2<T>, y_axis: YAxis) -> Self {
+        Self {
+            origin,
+            size,
+            y_axis,
+        }
+    }
+
+    // Window coordinates -> normalized device coordinates (-1..1 on every axis)
+    pub fn to_ndc(&self, window: Vector3<T>) -> Vector3<T>
+    where
+        T: Real,
+    {
+        let two = T::one() + T::one();
+
+        let x = (window.x() - self.origin.x()) / self.size.x() * two - T::one();
+        let y = (window.y() - self.origin.y()) / self.size.y() * two - T::one();
+        let z = window.z() * two - T::one();
+
+        match self.y_axis {
+            YAxis::Down => Vector3::new([x, -y, z]),
+            YAxis::Up => Vector3::new([x, y, z]),
+        }
+    }
+
+    // Normalized device coordinates -> window coordinates, depth mapped to 0..1
+    pub fn from_ndc(&self, ndc: Vector3<T>) -> Vector3<T>
+    where
+        T: Real,
+    {
+        let two = T::one() + T::one();
+
+        let y = match self.y_axis {
+            YAxis::Down => -ndc.y(),
+            YAxis::Up => ndc.y(),
+        };
+
+        Vector3::new([
+            self.origin.x() + (ndc.x() + T::one()) / two * self.size.x(),
+            self.origin.y() + (y + T::one()) / two * self.size.y(),
+            (ndc.z() + T::one()) / two,
+        ])
+    }
+}
+
+pub fn project<T>(point: Vector3<T>, view_proj: &Matrix4<T>, viewport: &Viewport<T>) -> Vector3<T>
+where
+    T: Default + Real,
+{
+    let clip = view_proj * Vector4::from((point, T::one()));
+    let ndc = Vector3::from(clip) / clip.w();
+
+    viewport.from_ndc(ndc)
+}
+
+pub fn unproject<T>(
+    window_xy_depth: Vector3<T>,
+    inv_view_proj: &Matrix4<T>,
+    viewport: &Viewport<T>,
+) -> Vector3<T>
+where
+    T: Default + Real,
+{
+    let ndc = viewport.to_ndc(window_xy_depth);
+    let world = inv_view_proj * Vector4::from((ndc, T::one()));
+
+    Vector3::from(world) / world.w()
+}
+
+pub fn screen_ray<T>(
+    pixel: Vector2<T>,
+    inv_view_proj: &Matrix4<T>,
+    viewport: &Viewport<T>,
+) -> Ray3<T>
+where
+    T: Default + Real,
+{
+    let near = unproject(Vector3::from((pixel, T::zero())), inv_view_proj, viewport);
+    let far = unproject(Vector3::from((pixel, T::one())), inv_view_proj, viewport);
+
+    Ray::through(near, far)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{matrix, test_util::assert_close, vector};
+
+    // Right-handed perspective (fov 90°, aspect 2, near 1, far 10) looking down -Z from (0, 0, 5)
+    fn view_proj() -> Matrix4<f64> {
+        let projection = matrix!(
+            [0.5, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, -11.0 / 9.0, -20.0 / 9.0],
+            [0.0, 0.0, -1.0, 0.0]
+        );
+        let view = matrix!(
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, -5.0],
+            [0.0, 0.0, 0.0, 1.0]
+        );
+
+        projection * view
+    }
+
+    #[test]
+    fn project_center() {
+        let viewport = Viewport::new(vector!(10.0, 20.0), vector!(800.0, 400.0), YAxis::Down);
+        let window = project(vector!(0.0, 0.0, 0.0), &view_proj(), &viewport);
+
+        assert_close(vector!(410.0, 220.0, window.z()), window, 1e-9);
+        assert!(window.z() > 0.0 && window.z() < 1.0);
+    }
+
+    #[test]
+    fn y_conventions() {
+        let point = vector!(0.0, 2.0, 0.0);
+        let down = Viewport::new(vector!(0.0, 0.0), vector!(800.0, 400.0), YAxis::Down);
+        let up = Viewport {
+            y_axis: YAxis::Up,
+            ..down
+        };
+
+        let down_window = project(point, &view_proj(), &down);
+        let up_window = project(point, &view_proj(), &up);
+
+        assert!(down_window.y() < 200.0);
+        assert!(up_window.y() > 200.0);
+        assert!((down_window.y() + up_window.y() - 400.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn round_trip() {
+        let view_proj = view_proj();
+        let inv_view_proj = view_proj.inverse().unwrap();
+
+        for y_axis in [YAxis::Down, YAxis::Up] {
+            let viewport = Viewport::new(vector!(0.0, 0.0), vector!(640.0, 480.0), y_axis);
+            let point = vector!(1.5, -0.5, -2.0);
+
+            let window = project(point, &view_proj, &viewport);
+            assert_close(unproject(window, &inv_view_proj, &viewport), point, 1e-9);
+        }
+    }
+
+    #[test]
+    fn screen_ray_hits_projected_point() {
+        let view_proj = view_proj();
+        let inv_view_proj = view_proj.inverse().unwrap();
+        let viewport = Viewport::new(vector!(0.0, 0.0), vector!(640.0, 480.0), YAxis::Down);
+
+        let point = vector!(-1.0, 1.0, 1.0);
+        let window = project(point, &view_proj, &viewport);
+        let ray = screen_ray(vector!(window.x(), window.y()), &inv_view_proj, &viewport);
+
+        let to_point = point - ray.origin;
+        let t = to_point.dot(&ray.direction);
+
+        assert_close(ray.at(t), point, 1e-9);
+        assert!(ray.direction.z() < 0.0);
+    }
+}